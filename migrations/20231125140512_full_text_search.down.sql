@@ -0,0 +1,5 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS questions_search_vector_idx, answers_search_vector_idx;
+
+ALTER TABLE questions DROP COLUMN IF EXISTS search_vector;
+ALTER TABLE answers DROP COLUMN IF EXISTS search_vector;
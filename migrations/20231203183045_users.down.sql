@@ -0,0 +1,5 @@
+-- Add down migration script here
+ALTER TABLE questions DROP COLUMN IF EXISTS author_uuid;
+ALTER TABLE answers DROP COLUMN IF EXISTS author_uuid;
+
+DROP TABLE IF EXISTS sessions, users;
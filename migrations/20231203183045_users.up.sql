@@ -0,0 +1,17 @@
+-- Add up migration script here
+CREATE TABLE IF NOT EXISTS users (
+    user_uuid uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+    username VARCHAR(32) NOT NULL UNIQUE,
+    password_hash VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS sessions (
+    token_hash VARCHAR(64) PRIMARY KEY,
+    user_uuid uuid NOT NULL REFERENCES users (user_uuid) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMP NOT NULL
+);
+
+ALTER TABLE questions ADD COLUMN author_uuid uuid REFERENCES users (user_uuid) ON DELETE SET NULL;
+ALTER TABLE answers ADD COLUMN author_uuid uuid REFERENCES users (user_uuid) ON DELETE SET NULL;
@@ -0,0 +1,16 @@
+-- Add up migration script here
+CREATE TABLE IF NOT EXISTS votes (
+    user_uuid uuid NOT NULL REFERENCES users (user_uuid) ON DELETE CASCADE,
+    question_uuid uuid REFERENCES questions (question_uuid) ON DELETE CASCADE,
+    answer_uuid uuid REFERENCES answers (answer_uuid) ON DELETE CASCADE,
+    value SMALLINT NOT NULL CHECK (value IN (-1, 1)),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK ((question_uuid IS NULL) <> (answer_uuid IS NULL))
+);
+
+-- One vote per user and post.
+CREATE UNIQUE INDEX IF NOT EXISTS votes_user_question_idx ON votes (user_uuid, question_uuid) WHERE question_uuid IS NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS votes_user_answer_idx ON votes (user_uuid, answer_uuid) WHERE answer_uuid IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS votes_question_idx ON votes (question_uuid);
+CREATE INDEX IF NOT EXISTS votes_answer_idx ON votes (answer_uuid);
@@ -0,0 +1,3 @@
+-- Add up migration script here
+ALTER TABLE questions
+    ADD COLUMN accepted_answer_uuid uuid REFERENCES answers (answer_uuid) ON DELETE SET NULL;
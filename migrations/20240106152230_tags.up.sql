@@ -0,0 +1,14 @@
+-- Add up migration script here
+CREATE TABLE IF NOT EXISTS tags (
+    tag_uuid uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(35) NOT NULL UNIQUE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS question_tags (
+    question_uuid uuid NOT NULL REFERENCES questions (question_uuid) ON DELETE CASCADE,
+    tag_uuid uuid NOT NULL REFERENCES tags (tag_uuid) ON DELETE CASCADE,
+    PRIMARY KEY (question_uuid, tag_uuid)
+);
+
+CREATE INDEX IF NOT EXISTS question_tags_tag_idx ON question_tags (tag_uuid);
@@ -0,0 +1,13 @@
+-- Add up migration script here
+CREATE TABLE IF NOT EXISTS comments (
+    comment_uuid uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+    question_uuid uuid REFERENCES questions (question_uuid) ON DELETE CASCADE,
+    answer_uuid uuid REFERENCES answers (answer_uuid) ON DELETE CASCADE,
+    content VARCHAR(600) NOT NULL,
+    author_uuid uuid REFERENCES users (user_uuid) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK ((question_uuid IS NULL) <> (answer_uuid IS NULL))
+);
+
+CREATE INDEX IF NOT EXISTS comments_question_idx ON comments (question_uuid, created_at);
+CREATE INDEX IF NOT EXISTS comments_answer_idx ON comments (answer_uuid, created_at);
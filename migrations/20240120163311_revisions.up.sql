@@ -0,0 +1,24 @@
+-- Add up migration script here
+-- Each row keeps the content a post had before an edit, along with who made that edit.
+CREATE TABLE IF NOT EXISTS question_revisions (
+    revision_uuid uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+    question_uuid uuid NOT NULL REFERENCES questions (question_uuid) ON DELETE CASCADE,
+    title VARCHAR(255) NOT NULL,
+    description VARCHAR(255) NOT NULL,
+    tags TEXT[] NOT NULL DEFAULT '{}',
+    editor_uuid uuid REFERENCES users (user_uuid) ON DELETE SET NULL,
+    edit_summary VARCHAR(255),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS answer_revisions (
+    revision_uuid uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+    answer_uuid uuid NOT NULL REFERENCES answers (answer_uuid) ON DELETE CASCADE,
+    content VARCHAR(255) NOT NULL,
+    editor_uuid uuid REFERENCES users (user_uuid) ON DELETE SET NULL,
+    edit_summary VARCHAR(255),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS question_revisions_question_idx ON question_revisions (question_uuid, created_at);
+CREATE INDEX IF NOT EXISTS answer_revisions_answer_idx ON answer_revisions (answer_uuid, created_at);
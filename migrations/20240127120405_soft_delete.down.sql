@@ -0,0 +1,4 @@
+-- Add down migration script here
+ALTER TABLE answers DROP COLUMN IF EXISTS deleted_at, DROP COLUMN IF EXISTS deleted_by;
+
+ALTER TABLE questions DROP COLUMN IF EXISTS deleted_at, DROP COLUMN IF EXISTS deleted_by;
@@ -0,0 +1,8 @@
+-- Add up migration script here
+ALTER TABLE questions
+    ADD COLUMN deleted_at TIMESTAMP,
+    ADD COLUMN deleted_by uuid REFERENCES users (user_uuid) ON DELETE SET NULL;
+
+ALTER TABLE answers
+    ADD COLUMN deleted_at TIMESTAMP,
+    ADD COLUMN deleted_by uuid REFERENCES users (user_uuid) ON DELETE SET NULL;
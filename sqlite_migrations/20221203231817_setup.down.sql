@@ -0,0 +1,12 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS answer_revisions;
+DROP TABLE IF EXISTS question_revisions;
+DROP TABLE IF EXISTS comments;
+DROP TABLE IF EXISTS question_tags;
+DROP TABLE IF EXISTS tags;
+DROP TABLE IF EXISTS votes;
+DROP TABLE IF EXISTS answers;
+DROP TABLE IF EXISTS questions;
+DROP TABLE IF EXISTS sessions;
+DROP TABLE IF EXISTS users;
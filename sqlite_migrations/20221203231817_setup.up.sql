@@ -0,0 +1,129 @@
+-- Add up migration script here
+-- SQLite counterpart of the Postgres schema in `migrations`. UUIDs are stored as lowercase text and
+-- generated as random (version 4) UUIDs, timestamps are stored as UTC text.
+CREATE TABLE IF NOT EXISTS users (
+    user_uuid TEXT PRIMARY KEY DEFAULT (
+        lower(hex(randomblob(4))) || '-' || lower(hex(randomblob(2))) || '-4' || substr(lower(hex(randomblob(2))), 2) || '-'
+        || substr('89ab', 1 + (abs(random()) % 4), 1) || substr(lower(hex(randomblob(2))), 2) || '-' || lower(hex(randomblob(6)))
+    ),
+    username VARCHAR(32) NOT NULL UNIQUE,
+    password_hash VARCHAR(255) NOT NULL,
+    role VARCHAR(16) NOT NULL DEFAULT 'user' CHECK (role IN ('user', 'moderator', 'admin')),
+    created_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now'))
+);
+
+CREATE TABLE IF NOT EXISTS sessions (
+    token_hash VARCHAR(64) PRIMARY KEY,
+    user_uuid TEXT NOT NULL REFERENCES users (user_uuid) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now')),
+    expires_at TIMESTAMP NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS questions (
+    question_uuid TEXT PRIMARY KEY DEFAULT (
+        lower(hex(randomblob(4))) || '-' || lower(hex(randomblob(2))) || '-4' || substr(lower(hex(randomblob(2))), 2) || '-'
+        || substr('89ab', 1 + (abs(random()) % 4), 1) || substr(lower(hex(randomblob(2))), 2) || '-' || lower(hex(randomblob(6)))
+    ),
+    title VARCHAR(255) NOT NULL,
+    description VARCHAR(255) NOT NULL,
+    author_uuid TEXT REFERENCES users (user_uuid) ON DELETE SET NULL,
+    accepted_answer_uuid TEXT REFERENCES answers (answer_uuid) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now')),
+    deleted_at TIMESTAMP,
+    deleted_by TEXT REFERENCES users (user_uuid) ON DELETE SET NULL
+);
+
+CREATE TABLE IF NOT EXISTS answers (
+    answer_uuid TEXT PRIMARY KEY DEFAULT (
+        lower(hex(randomblob(4))) || '-' || lower(hex(randomblob(2))) || '-4' || substr(lower(hex(randomblob(2))), 2) || '-'
+        || substr('89ab', 1 + (abs(random()) % 4), 1) || substr(lower(hex(randomblob(2))), 2) || '-' || lower(hex(randomblob(6)))
+    ),
+    question_uuid TEXT NOT NULL REFERENCES questions (question_uuid) ON DELETE CASCADE,
+    content VARCHAR(255) NOT NULL,
+    author_uuid TEXT REFERENCES users (user_uuid) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now')),
+    deleted_at TIMESTAMP,
+    deleted_by TEXT REFERENCES users (user_uuid) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS answers_question_idx ON answers (question_uuid, created_at);
+
+CREATE TABLE IF NOT EXISTS votes (
+    user_uuid TEXT NOT NULL REFERENCES users (user_uuid) ON DELETE CASCADE,
+    question_uuid TEXT REFERENCES questions (question_uuid) ON DELETE CASCADE,
+    answer_uuid TEXT REFERENCES answers (answer_uuid) ON DELETE CASCADE,
+    value SMALLINT NOT NULL CHECK (value IN (-1, 1)),
+    created_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now')),
+    CHECK ((question_uuid IS NULL) <> (answer_uuid IS NULL))
+);
+
+-- One vote per user and post.
+CREATE UNIQUE INDEX IF NOT EXISTS votes_user_question_idx ON votes (user_uuid, question_uuid) WHERE question_uuid IS NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS votes_user_answer_idx ON votes (user_uuid, answer_uuid) WHERE answer_uuid IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS votes_question_idx ON votes (question_uuid);
+CREATE INDEX IF NOT EXISTS votes_answer_idx ON votes (answer_uuid);
+
+CREATE TABLE IF NOT EXISTS tags (
+    tag_uuid TEXT PRIMARY KEY DEFAULT (
+        lower(hex(randomblob(4))) || '-' || lower(hex(randomblob(2))) || '-4' || substr(lower(hex(randomblob(2))), 2) || '-'
+        || substr('89ab', 1 + (abs(random()) % 4), 1) || substr(lower(hex(randomblob(2))), 2) || '-' || lower(hex(randomblob(6)))
+    ),
+    name VARCHAR(35) NOT NULL UNIQUE,
+    created_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now'))
+);
+
+CREATE TABLE IF NOT EXISTS question_tags (
+    question_uuid TEXT NOT NULL REFERENCES questions (question_uuid) ON DELETE CASCADE,
+    tag_uuid TEXT NOT NULL REFERENCES tags (tag_uuid) ON DELETE CASCADE,
+    PRIMARY KEY (question_uuid, tag_uuid)
+);
+
+CREATE INDEX IF NOT EXISTS question_tags_tag_idx ON question_tags (tag_uuid);
+
+CREATE TABLE IF NOT EXISTS comments (
+    comment_uuid TEXT PRIMARY KEY DEFAULT (
+        lower(hex(randomblob(4))) || '-' || lower(hex(randomblob(2))) || '-4' || substr(lower(hex(randomblob(2))), 2) || '-'
+        || substr('89ab', 1 + (abs(random()) % 4), 1) || substr(lower(hex(randomblob(2))), 2) || '-' || lower(hex(randomblob(6)))
+    ),
+    question_uuid TEXT REFERENCES questions (question_uuid) ON DELETE CASCADE,
+    answer_uuid TEXT REFERENCES answers (answer_uuid) ON DELETE CASCADE,
+    content VARCHAR(600) NOT NULL,
+    author_uuid TEXT REFERENCES users (user_uuid) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now')),
+    CHECK ((question_uuid IS NULL) <> (answer_uuid IS NULL))
+);
+
+CREATE INDEX IF NOT EXISTS comments_question_idx ON comments (question_uuid, created_at);
+CREATE INDEX IF NOT EXISTS comments_answer_idx ON comments (answer_uuid, created_at);
+
+-- Each row keeps the content a post had before an edit, along with who made that edit.
+-- Question revision tags are stored as a JSON array.
+CREATE TABLE IF NOT EXISTS question_revisions (
+    revision_uuid TEXT PRIMARY KEY DEFAULT (
+        lower(hex(randomblob(4))) || '-' || lower(hex(randomblob(2))) || '-4' || substr(lower(hex(randomblob(2))), 2) || '-'
+        || substr('89ab', 1 + (abs(random()) % 4), 1) || substr(lower(hex(randomblob(2))), 2) || '-' || lower(hex(randomblob(6)))
+    ),
+    question_uuid TEXT NOT NULL REFERENCES questions (question_uuid) ON DELETE CASCADE,
+    title VARCHAR(255) NOT NULL,
+    description VARCHAR(255) NOT NULL,
+    tags TEXT NOT NULL DEFAULT '[]',
+    editor_uuid TEXT REFERENCES users (user_uuid) ON DELETE SET NULL,
+    edit_summary VARCHAR(255),
+    created_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now'))
+);
+
+CREATE TABLE IF NOT EXISTS answer_revisions (
+    revision_uuid TEXT PRIMARY KEY DEFAULT (
+        lower(hex(randomblob(4))) || '-' || lower(hex(randomblob(2))) || '-4' || substr(lower(hex(randomblob(2))), 2) || '-'
+        || substr('89ab', 1 + (abs(random()) % 4), 1) || substr(lower(hex(randomblob(2))), 2) || '-' || lower(hex(randomblob(6)))
+    ),
+    answer_uuid TEXT NOT NULL REFERENCES answers (answer_uuid) ON DELETE CASCADE,
+    content VARCHAR(255) NOT NULL,
+    editor_uuid TEXT REFERENCES users (user_uuid) ON DELETE SET NULL,
+    edit_summary VARCHAR(255),
+    created_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS question_revisions_question_idx ON question_revisions (question_uuid, created_at);
+CREATE INDEX IF NOT EXISTS answer_revisions_answer_idx ON answer_revisions (answer_uuid, created_at);
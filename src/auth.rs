@@ -0,0 +1,98 @@
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use rand::{rngs::OsRng, RngCore};
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome};
+use rocket::{Request, State};
+use sha2::{Digest, Sha256};
+
+use crate::{models::Role, persistance::users_dao::UsersDao};
+
+pub const SESSION_TTL_SECONDS: i64 = 60 * 60 * 24 * 30;
+
+/// Request guard resolving the `Authorization: Bearer <token>` header to the session's user. The lookup is
+/// cached for the request, so the rate limiter and the route share a single query.
+#[derive(Clone)]
+pub struct AuthenticatedUser {
+    pub user_uuid: String,
+    pub role: Role,
+    pub session_token_hash: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum AuthError {
+    MissingToken,
+    InvalidToken,
+    Unavailable,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AuthenticatedUser {
+    type Error = AuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match request.local_cache_async(authenticate(request)).await {
+            Ok(user) => Outcome::Success(user.clone()),
+            Err(err) => Outcome::Error(err.clone()),
+        }
+    }
+}
+
+async fn authenticate(request: &Request<'_>) -> Result<AuthenticatedUser, (Status, AuthError)> {
+    let token = match request
+        .headers()
+        .get_one("Authorization")
+        .and_then(|header| header.strip_prefix("Bearer "))
+    {
+        Some(token) => token,
+        None => return Err((Status::Unauthorized, AuthError::MissingToken)),
+    };
+
+    let users_dao = match request.guard::<&State<Box<dyn UsersDao + Send + Sync>>>().await {
+        Outcome::Success(users_dao) => users_dao,
+        _ => return Err((Status::InternalServerError, AuthError::Unavailable)),
+    };
+
+    let session_token_hash = hash_session_token(token);
+
+    match users_dao.get_session_user(session_token_hash.clone()).await {
+        Ok(Some(user)) => Ok(AuthenticatedUser {
+            user_uuid: user.user_uuid,
+            role: user.role,
+            session_token_hash,
+        }),
+        Ok(None) => Err((Status::Unauthorized, AuthError::InvalidToken)),
+        Err(err) => {
+            error!("{}", err);
+            Err((Status::InternalServerError, AuthError::Unavailable))
+        }
+    }
+}
+
+pub fn hash_password(password: &str) -> Result<String, argon2::password_hash::Error> {
+    let salt = SaltString::generate(&mut OsRng);
+
+    Ok(Argon2::default()
+        .hash_password(password.as_bytes(), &salt)?
+        .to_string())
+}
+
+pub fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash)
+        .map(|hash| Argon2::default().verify_password(password.as_bytes(), &hash).is_ok())
+        .unwrap_or(false)
+}
+
+pub fn generate_session_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Only the SHA-256 of a session token is stored, so a leaked `sessions` table can't be replayed.
+pub fn hash_session_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
@@ -0,0 +1,200 @@
+use std::time::Duration;
+
+use rocket::data::Limits;
+use rocket::figment::{providers::Env, Figment};
+use serde::Deserialize;
+use sqlx::{pool::PoolOptions, Database};
+use thiserror::Error;
+
+use crate::cors::CorsConfig;
+use crate::persistance::StorageBackend;
+use crate::rate_limit::RateLimitConfig;
+
+/// Settings of the API, read from the same sources as Rocket's own (`Rocket.toml` and `ROCKET_*` variables)
+/// plus the unprefixed `DATABASE_URL` and `STORAGE_BACKEND` variables, which `.env` may set. Extracted and
+/// validated once at startup, then managed as state so that handlers can take it as a `&State<AppConfig>`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct AppConfig {
+    pub storage_backend: StorageBackend,
+    /// Required by every backend but `memory`.
+    pub database_url: Option<String>,
+    /// Applies pending migrations before the server starts. When off, run `migrate up` before deploying.
+    pub migrate_on_startup: bool,
+    pub database: PoolConfig,
+    pub cors: CorsConfig,
+    pub rate_limit: RateLimitConfig,
+    /// Limits on request bodies by data type, e.g. `ROCKET_LIMITS='{json="2MiB"}'`, handed on to Rocket.
+    pub limits: Limits,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        AppConfig {
+            storage_backend: StorageBackend::default(),
+            database_url: None,
+            migrate_on_startup: true,
+            database: PoolConfig::default(),
+            cors: CorsConfig::default(),
+            rate_limit: RateLimitConfig::default(),
+            limits: Limits::default(),
+        }
+    }
+}
+
+/// Connection pool settings, read from the `database` table, e.g. `ROCKET_DATABASE='{max_connections=10}'`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct PoolConfig {
+    pub max_connections: u32,
+    /// How long a query waits for a free connection before failing, in seconds.
+    pub acquire_timeout: u64,
+    /// How long a connection may stay idle before it's closed, in seconds. Idle connections are kept when unset.
+    pub idle_timeout: Option<u64>,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        PoolConfig {
+            max_connections: 5,
+            acquire_timeout: 30,
+            idle_timeout: Some(600),
+        }
+    }
+}
+
+impl PoolConfig {
+    pub fn options<DB: Database>(&self) -> PoolOptions<DB> {
+        PoolOptions::new()
+            .max_connections(self.max_connections)
+            .acquire_timeout(Duration::from_secs(self.acquire_timeout))
+            .idle_timeout(self.idle_timeout.map(Duration::from_secs))
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Invalid configuration: {0}")]
+    Extract(#[from] Box<rocket::figment::Error>),
+    #[error("Invalid configuration: {}", .0.join("; "))]
+    Invalid(Vec<String>),
+}
+
+/// Rocket's configuration sources, with `DATABASE_URL` and `STORAGE_BACKEND` taking precedence over every profile.
+pub fn figment() -> Figment {
+    rocket::Config::figment().merge(Env::raw().only(&["DATABASE_URL", "STORAGE_BACKEND"]).global())
+}
+
+impl AppConfig {
+    pub fn from_figment(figment: &Figment) -> Result<Self, ConfigError> {
+        let config: AppConfig = figment.extract().map_err(Box::new)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks every setting, so that all of the problems are reported at once.
+    fn validate(&self) -> Result<(), ConfigError> {
+        let mut errors = vec![];
+
+        if self.storage_backend != StorageBackend::InMemory && self.database_url.is_none() {
+            errors.push(format!("database_url must be set for the {:?} backend", self.storage_backend));
+        }
+        if self.database.max_connections == 0 {
+            errors.push("database.max_connections must be at least 1".to_owned());
+        }
+        if self.database.acquire_timeout == 0 {
+            errors.push("database.acquire_timeout must be at least 1 second".to_owned());
+        }
+        if let Err(err) = self.cors.validate() {
+            errors.push(format!("cors: {}", err));
+        }
+        if let Err(err) = self.rate_limit.validate() {
+            errors.push(format!("rate_limit: {}", err));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(errors))
+        }
+    }
+
+    /// Rocket's own configuration, with the body limits taken from this one.
+    pub fn rocket_figment(&self, figment: Figment) -> Figment {
+        figment.merge(("limits", &self.limits))
+    }
+
+    /// The database URL, which `validate` ensures is set for every backend that needs one.
+    pub fn database_url(&self) -> &str {
+        self.database_url.as_deref().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::data::ToByteUnit;
+    use rocket::Config;
+
+    use super::*;
+    use crate::rate_limit::Limit;
+
+    fn figment() -> Figment {
+        Figment::from(Config::debug_default())
+    }
+
+    #[test]
+    fn config_should_merge_defaults_with_overrides() {
+        let figment = figment()
+            .merge(("database_url", "postgres://localhost/test"))
+            .merge(("database.max_connections", 10))
+            .merge(("cors.allowed_origins", ["https://example.com"]))
+            .merge(("rate_limit.enabled", false))
+            .merge(("limits.json", "2MiB"));
+
+        let config = AppConfig::from_figment(&figment).unwrap();
+
+        assert_eq!(config.storage_backend, StorageBackend::Postgres);
+        assert_eq!(config.database_url(), "postgres://localhost/test");
+        assert!(config.migrate_on_startup);
+        assert_eq!(config.database, PoolConfig { max_connections: 10, ..PoolConfig::default() });
+        assert_eq!(config.cors.allowed_origins, vec!["https://example.com".to_owned()]);
+        assert!(!config.rate_limit.enabled);
+        assert_eq!(config.limits.get("json"), Some(2.mebibytes()));
+
+        let rocket_config: Config = config.rocket_figment(figment).extract().unwrap();
+        assert_eq!(rocket_config.limits.get("json"), Some(2.mebibytes()));
+    }
+
+    #[test]
+    fn config_should_reject_invalid_settings() {
+        let missing_url = AppConfig::from_figment(&figment());
+        assert!(matches!(missing_url, Err(ConfigError::Invalid(_))));
+
+        let memory = AppConfig::from_figment(&figment().merge(("storage_backend", "memory")));
+        assert_eq!(memory.unwrap().storage_backend, StorageBackend::InMemory);
+
+        let unknown_backend = AppConfig::from_figment(&figment().merge(("storage_backend", "mysql")));
+        assert!(matches!(unknown_backend, Err(ConfigError::Extract(_))));
+
+        let empty_pool = figment().merge(("storage_backend", "memory")).merge(("database.max_connections", 0));
+        assert!(matches!(AppConfig::from_figment(&empty_pool), Err(ConfigError::Invalid(_))));
+
+        let credentials_for_any_origin = figment().merge(("storage_backend", "memory")).merge(("cors.allow_credentials", true));
+        assert!(matches!(AppConfig::from_figment(&credentials_for_any_origin), Err(ConfigError::Invalid(_))));
+    }
+
+    #[test]
+    fn config_should_report_every_invalid_setting() {
+        let figment = figment()
+            .merge(("database.max_connections", 0))
+            .merge(("cors.allow_credentials", true))
+            .merge(("rate_limit.default", Limit { burst: 0, per_minute: 60 }));
+
+        let Err(ConfigError::Invalid(errors)) = AppConfig::from_figment(&figment) else {
+            panic!("the configuration should be invalid");
+        };
+
+        assert_eq!(errors.len(), 4, "{:?}", errors);
+        assert!(errors.iter().any(|error| error.starts_with("rate_limit:")));
+    }
+}
@@ -1,8 +1,105 @@
+use std::str::FromStr;
+
 use rocket::fairing::{Fairing, Info, Kind};
-use rocket::http::{ContentType, Header, Method, Status};
+use rocket::http::{Header, Method, Status};
+use rocket::response::Responder;
 use rocket::{Request, Response};
+use serde::Deserialize;
+
+use crate::handlers::APIError;
+
+/// CORS settings, read from the `cors` table of the configuration, e.g.
+/// `ROCKET_CORS='{allowed_origins=["https://example.com"], allow_credentials=true}'`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct CorsConfig {
+    /// Origins allowed to call the API, `*` allows any origin.
+    pub allowed_origins: Vec<String>,
+    pub allowed_methods: Vec<Method>,
+    /// Request headers a preflight may ask for, compared case-insensitively.
+    pub allowed_headers: Vec<String>,
+    /// Response headers, besides the CORS-safelisted ones, that scripts on other origins may read.
+    pub expose_headers: Vec<String>,
+    /// How long browsers may cache a preflight response, in seconds.
+    pub max_age: u64,
+    pub allow_credentials: bool,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        CorsConfig {
+            allowed_origins: vec!["*".to_owned()],
+            allowed_methods: vec![Method::Get, Method::Post, Method::Put, Method::Delete],
+            allowed_headers: vec!["Authorization".to_owned(), "Content-Type".to_owned(), "X-Request-Id".to_owned()],
+            expose_headers: [
+                "X-Request-Id",
+                "RateLimit-Limit",
+                "RateLimit-Remaining",
+                "RateLimit-Reset",
+                "Retry-After",
+                "Deprecation",
+                "Link",
+            ].map(str::to_owned).to_vec(),
+            max_age: 3600,
+            allow_credentials: false,
+        }
+    }
+}
+
+impl CorsConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.allow_credentials && self.allows_any_origin() {
+            return Err("allow_credentials requires explicit allowed_origins instead of `*`".to_owned());
+        }
+
+        Ok(())
+    }
+
+    fn allows_any_origin(&self) -> bool {
+        self.allowed_origins.iter().any(|origin| origin == "*")
+    }
+
+    fn allows_origin(&self, origin: &str) -> bool {
+        self.allows_any_origin() || self.allowed_origins.iter().any(|allowed| allowed.eq_ignore_ascii_case(origin))
+    }
+
+    fn allows_header(&self, header: &str) -> bool {
+        self.allowed_headers.iter().any(|allowed| allowed == "*" || allowed.eq_ignore_ascii_case(header))
+    }
+
+    /// Browsers ignore `Access-Control-Allow-Origin: *` on credentialed requests, so the origin is echoed instead.
+    fn allow_origin_header(&self, origin: &str) -> Header<'static> {
+        let value = if self.allows_any_origin() && !self.allow_credentials { "*" } else { origin };
+        Header::new("Access-Control-Allow-Origin", value.to_owned())
+    }
+
+    fn join_methods(&self) -> String {
+        self.allowed_methods.iter().map(|method| method.as_str()).collect::<Vec<_>>().join(", ")
+    }
+
+    /// Sets the headers every allowed cross-origin response carries, preflight or not.
+    fn set_allowed_headers(&self, response: &mut Response<'_>, origin: &str) {
+        response.set_header(self.allow_origin_header(origin));
+        if self.allow_credentials {
+            response.set_header(Header::new("Access-Control-Allow-Credentials", "true"));
+        }
+        if !self.expose_headers.is_empty() {
+            response.set_header(Header::new("Access-Control-Expose-Headers", self.expose_headers.join(", ")));
+        }
+    }
+}
+
+/// Applies a `CorsConfig` that `AppConfig` has already validated.
+#[allow(clippy::upper_case_acronyms)]
+pub struct CORS {
+    config: CorsConfig,
+}
 
-pub struct CORS;
+impl CORS {
+    pub fn new(config: CorsConfig) -> Self {
+        CORS { config }
+    }
+}
 
 #[rocket::async_trait]
 impl Fairing for CORS {
@@ -13,22 +110,259 @@ impl Fairing for CORS {
         }
     }
 
-    // source: https://stackoverflow.com/questions/62412361/how-to-set-up-cors-or-options-for-rocket-rs
     async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
-        response.set_header(Header::new("Access-Control-Allow-Origin", "*"));
-        response.set_header(Header::new(
-            "Access-Control-Allow-Methods",
-            "POST, GET, PATCH, DELETE, OPTIONS",
-        ));
-        response.set_header(Header::new("Access-Control-Allow-Headers", "*"));
-        response.set_header(Header::new("Access-Control-Allow-Credentials", "true"));
-
-        // source: https://webprogramming.ninja/2022/08/25/handling-options-requests-in-rust-using-rocket-with-cors/
-        if request.method() == Method::Options {
-            let body = "";
-            response.set_header(ContentType::Plain);
-            response.set_sized_body(body.len(), std::io::Cursor::new(body));
-            response.set_status(Status::Ok);
+        let config = &self.config;
+        let Some(origin) = request.headers().get_one("Origin") else {
+            // Caches must not serve this response, which has no CORS headers, to cross-origin requests.
+            response.adjoin_header(Header::new("Vary", "Origin"));
+            return;
+        };
+
+        match request.headers().get_one("Access-Control-Request-Method") {
+            Some(requested_method) if request.method() == Method::Options => {
+                preflight(config, request, response, origin, requested_method);
+            }
+            _ => {
+                if config.allows_origin(origin) {
+                    config.set_allowed_headers(response, origin);
+                }
+            }
+        }
+
+        response.adjoin_header(Header::new("Vary", "Origin"));
+    }
+}
+
+fn preflight<'r>(config: &CorsConfig, request: &'r Request<'_>, response: &mut Response<'r>, origin: &str, requested_method: &str) {
+    let Ok(method) = Method::from_str(requested_method) else {
+        return reject(request, response, format!("Invalid Access-Control-Request-Method: {}", requested_method));
+    };
+
+    // Preflights only succeed for routes that exist, anything else keeps its 404.
+    if !has_route(request, method) {
+        return;
+    }
+
+    if !config.allows_origin(origin) {
+        return reject(request, response, format!("Origin {} is not allowed", origin));
+    }
+    if !config.allowed_methods.contains(&method) {
+        return reject(request, response, format!("Method {} is not allowed", method));
+    }
+
+    let requested_headers = request.headers().get_one("Access-Control-Request-Headers").unwrap_or_default();
+    let requested_headers: Vec<&str> = requested_headers.split(',').map(str::trim).filter(|header| !header.is_empty()).collect();
+    if let Some(header) = requested_headers.iter().find(|header| !config.allows_header(header)) {
+        return reject(request, response, format!("Header {} is not allowed", header));
+    }
+
+    let allowed_headers = if requested_headers.is_empty() { config.allowed_headers.join(", ") } else { requested_headers.join(", ") };
+
+    *response = Response::new();
+    response.set_status(Status::NoContent);
+    config.set_allowed_headers(response, origin);
+    response.set_header(Header::new("Access-Control-Allow-Methods", config.join_methods()));
+    response.set_header(Header::new("Access-Control-Allow-Headers", allowed_headers));
+    response.set_header(Header::new("Access-Control-Max-Age", config.max_age.to_string()));
+}
+
+fn reject<'r>(request: &'r Request<'_>, response: &mut Response<'r>, message: String) {
+    if let Ok(rejection) = APIError::new(Status::Forbidden, "cors_rejected", message).respond_to(request) {
+        *response = rejection;
+    }
+}
+
+/// Whether a mounted route answers `method` on the request's path. Query strings are not considered.
+fn has_route(request: &Request<'_>, method: Method) -> bool {
+    let segments: Vec<&str> = request.uri().path().segments().collect();
+
+    request.rocket().routes()
+        .filter(|route| route.method == method)
+        .any(|route| path_matches(route.uri.path(), &segments))
+}
+
+fn path_matches(route_path: &str, segments: &[&str]) -> bool {
+    let mut patterns = route_path.split('/').filter(|pattern| !pattern.is_empty());
+    let mut segments = segments.iter();
+
+    loop {
+        match (patterns.next(), segments.next()) {
+            (Some(pattern), _) if pattern.starts_with('<') && pattern.ends_with("..>") => return true,
+            (Some(pattern), Some(segment)) if pattern.starts_with('<') || pattern == *segment => continue,
+            (None, None) => return true,
+            _ => return false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rocket::{local::asynchronous::Client, Config};
+
+    use super::*;
+    use crate::handlers::default_catcher;
+
+    #[get("/questions/<_question_uuid>")]
+    fn read_question(_question_uuid: &str) -> &'static str {
+        "question"
+    }
+
+    async fn client(config: CorsConfig) -> Client {
+        let rocket = rocket::custom(Config::debug_default())
+            .mount("/", routes![read_question])
+            .register("/", catchers![default_catcher])
+            .attach(CORS::new(config));
+
+        Client::tracked(rocket).await.unwrap()
+    }
+
+    fn config() -> CorsConfig {
+        CorsConfig {
+            allowed_origins: vec!["https://example.com".to_owned()],
+            ..CorsConfig::default()
+        }
+    }
+
+    #[test]
+    fn path_matches_should_handle_dynamic_and_trailing_segments() {
+        assert!(path_matches("/questions/<question_uuid>", &["questions", "1"]));
+        assert!(!path_matches("/questions/<question_uuid>", &["questions"]));
+        assert!(!path_matches("/questions/<question_uuid>", &["answers", "1"]));
+        assert!(path_matches("/swagger-ui/<_..>", &["swagger-ui", "index.html"]));
+    }
+
+    #[rocket::async_test]
+    async fn allowed_origin_should_be_echoed() {
+        let client = client(config()).await;
+
+        let response = client.get("/questions/1").header(Header::new("Origin", "https://example.com")).dispatch().await;
+
+        assert_eq!(response.headers().get_one("Access-Control-Allow-Origin"), Some("https://example.com"));
+        assert_eq!(response.headers().get_one("Vary"), Some("Origin"));
+        assert_eq!(response.headers().get_one("Access-Control-Allow-Credentials"), None);
+    }
+
+    #[rocket::async_test]
+    async fn allowed_request_should_expose_headers() {
+        let exposed = client(config()).await;
+
+        let response = exposed.get("/questions/1").header(Header::new("Origin", "https://example.com")).dispatch().await;
+
+        assert_eq!(
+            response.headers().get_one("Access-Control-Expose-Headers"),
+            Some("X-Request-Id, RateLimit-Limit, RateLimit-Remaining, RateLimit-Reset, Retry-After, Deprecation, Link")
+        );
+
+        let unexposed = client(CorsConfig { expose_headers: vec![], ..config() }).await;
+
+        let response = unexposed.get("/questions/1").header(Header::new("Origin", "https://example.com")).dispatch().await;
+
+        assert_eq!(response.headers().get_one("Access-Control-Expose-Headers"), None);
+    }
+
+    #[rocket::async_test]
+    async fn unknown_origin_should_not_get_cors_headers() {
+        let client = client(config()).await;
+
+        let response = client.get("/questions/1").header(Header::new("Origin", "https://evil.com")).dispatch().await;
+
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.headers().get_one("Access-Control-Allow-Origin"), None);
+        assert_eq!(response.headers().get_one("Vary"), Some("Origin"));
+    }
+
+    #[rocket::async_test]
+    async fn same_origin_request_should_vary_on_origin() {
+        let client = client(config()).await;
+
+        let response = client.get("/questions/1").dispatch().await;
+
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.headers().get_one("Access-Control-Allow-Origin"), None);
+        assert_eq!(response.headers().get_one("Vary"), Some("Origin"));
+    }
+
+    #[rocket::async_test]
+    async fn valid_preflight_should_succeed() {
+        let client = client(config()).await;
+
+        let response = client.options("/questions/1")
+            .header(Header::new("Origin", "https://example.com"))
+            .header(Header::new("Access-Control-Request-Method", "GET"))
+            .header(Header::new("Access-Control-Request-Headers", "authorization, content-type"))
+            .dispatch().await;
+
+        assert_eq!(response.status(), Status::NoContent);
+        assert_eq!(response.headers().get_one("Access-Control-Allow-Origin"), Some("https://example.com"));
+        assert_eq!(response.headers().get_one("Access-Control-Allow-Methods"), Some("GET, POST, PUT, DELETE"));
+        assert_eq!(response.headers().get_one("Access-Control-Allow-Headers"), Some("authorization, content-type"));
+        assert_eq!(response.headers().get_one("Access-Control-Max-Age"), Some("3600"));
+    }
+
+    #[rocket::async_test]
+    async fn preflight_should_allow_and_expose_request_id() {
+        let client = client(config()).await;
+
+        let response = client.options("/questions/1")
+            .header(Header::new("Origin", "https://example.com"))
+            .header(Header::new("Access-Control-Request-Method", "GET"))
+            .header(Header::new("Access-Control-Request-Headers", "x-request-id"))
+            .dispatch().await;
+
+        assert_eq!(response.status(), Status::NoContent);
+        assert_eq!(response.headers().get_one("Access-Control-Allow-Headers"), Some("x-request-id"));
+        assert!(response.headers().get_one("Access-Control-Expose-Headers").is_some_and(|headers| headers.contains("X-Request-Id")));
+
+        let response = client.options("/questions/1")
+            .header(Header::new("Origin", "https://example.com"))
+            .header(Header::new("Access-Control-Request-Method", "GET"))
+            .dispatch().await;
+
+        assert_eq!(response.headers().get_one("Access-Control-Allow-Headers"), Some("Authorization, Content-Type, X-Request-Id"));
+    }
+
+    #[rocket::async_test]
+    async fn preflight_should_fail_for_unknown_routes() {
+        let client = client(config()).await;
+
+        let response = client.options("/unknown")
+            .header(Header::new("Origin", "https://example.com"))
+            .header(Header::new("Access-Control-Request-Method", "GET"))
+            .dispatch().await;
+
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(response.headers().get_one("Access-Control-Allow-Origin"), None);
+    }
+
+    #[rocket::async_test]
+    async fn preflight_should_reject_disallowed_methods() {
+        let client = client(CorsConfig { allowed_methods: vec![Method::Post], ..config() }).await;
+
+        let response = client.options("/questions/1")
+            .header(Header::new("Origin", "https://example.com"))
+            .header(Header::new("Access-Control-Request-Method", "GET"))
+            .dispatch().await;
+
+        assert_eq!(response.status(), Status::Forbidden);
+        assert_eq!(response.headers().get_one("Access-Control-Allow-Origin"), None);
+    }
+
+    #[rocket::async_test]
+    async fn preflight_should_reject_disallowed_headers() {
+        let client = client(config()).await;
+
+        let response = client.options("/questions/1")
+            .header(Header::new("Origin", "https://example.com"))
+            .header(Header::new("Access-Control-Request-Method", "GET"))
+            .header(Header::new("Access-Control-Request-Headers", "x-custom"))
+            .dispatch().await;
+
+        assert_eq!(response.status(), Status::Forbidden);
+    }
+
+    #[test]
+    fn credentials_should_require_explicit_origins() {
+        assert!(CorsConfig { allow_credentials: true, ..CorsConfig::default() }.validate().is_err());
+        assert!(CorsConfig { allow_credentials: true, ..config() }.validate().is_ok());
+    }
+}
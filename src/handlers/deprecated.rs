@@ -0,0 +1,793 @@
+//! Routes from before the API moved uuids into paths. They are kept for existing clients, delegate to their
+//! successors and mark every response with a `Deprecation` header and a `Link` to the successor route.
+
+use rocket::{http::{Header, RawStr}, request::FromParam, serde::json::Json, State};
+
+use super::{handlers_inner, APIError, ErrorBody, PathUuid};
+use crate::{auth::AuthenticatedUser, models::*, persistance::{questions_dao::QuestionsDao, answers_dao::AnswersDao, comments_dao::CommentsDao}};
+
+#[derive(Responder)]
+pub struct Deprecated<R> {
+    inner: R,
+    deprecation: Header<'static>,
+    link: Header<'static>,
+}
+
+impl<R> Deprecated<R> {
+    fn new(inner: R, successor: String) -> Self {
+        Deprecated {
+            inner,
+            deprecation: Header::new("Deprecation", "true"),
+            link: Header::new("Link", format!("<{}>; rel=\"successor-version\"", successor)),
+        }
+    }
+}
+
+/// A uuid from the request as a segment of the successor's path, normalized when it parses and percent-encoded
+/// when it doesn't, so that request input can't alter the `Link` header.
+fn segment(uuid: &str) -> String {
+    PathUuid::from_param(uuid)
+        .map(PathUuid::into_inner)
+        .unwrap_or_else(|_| RawStr::new(uuid).percent_encode().to_string())
+}
+
+// ---- CRUD for Questions ----
+
+#[utoipa::path(
+    post,
+    path = "/question",
+    tag = "deprecated",
+    operation_id = "deprecated_create_question",
+    request_body = Question,
+    responses(
+        (status = 200, description = "The created question", body = QuestionDetail),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[post("/question", data = "<question>")]
+pub async fn create_question(
+    question: Json<Question>,
+    user: AuthenticatedUser,
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+) -> Deprecated<Result<Json<QuestionDetail>, APIError>> {
+    Deprecated::new(super::create_question(question, user, questions_dao).await, "/questions".to_owned())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/question",
+    tag = "deprecated",
+    operation_id = "deprecated_delete_question",
+    request_body = QuestionId,
+    responses(
+        (status = 200, description = "The question was soft deleted"),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[delete("/question", data = "<question_uuid>")]
+pub async fn delete_question(
+    question_uuid: Json<QuestionId>,
+    user: AuthenticatedUser,
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+) -> Deprecated<Result<(), APIError>> {
+    let uuid = &question_uuid.question_uuid;
+    let result = super::delete_question(PathUuid::from_param(uuid), user, questions_dao).await;
+    Deprecated::new(result, format!("/questions/{}", segment(uuid)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/question/{question_uuid}/undelete",
+    tag = "deprecated",
+    operation_id = "deprecated_undelete_question",
+    params(
+        ("question_uuid" = String, Path, description = "Question uuid"),
+    ),
+    responses(
+        (status = 200, description = "The question was restored"),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[post("/question/<question_uuid>/undelete")]
+pub async fn undelete_question(
+    question_uuid: &str,
+    user: AuthenticatedUser,
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+) -> Deprecated<Result<(), APIError>> {
+    let result = super::undelete_question(PathUuid::from_param(question_uuid), user, questions_dao).await;
+    Deprecated::new(result, format!("/questions/{}/undelete", segment(question_uuid)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/question",
+    tag = "deprecated",
+    operation_id = "deprecated_update_question",
+    request_body = UpdateRequest<Question>,
+    responses(
+        (status = 200, description = "The updated question", body = QuestionDetail),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[put("/question", data = "<update_request>")]
+pub async fn update_question(
+    update_request: Json<UpdateRequest<Question>>,
+    user: AuthenticatedUser,
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+) -> Deprecated<Result<Json<QuestionDetail>, APIError>> {
+    let UpdateRequest { updated_entity, uuid, edit_summary } = update_request.0;
+    let edit = Json(Edit { updated_entity, edit_summary });
+    let result = super::update_question(PathUuid::from_param(&uuid), edit, user, questions_dao).await;
+    Deprecated::new(result, format!("/questions/{}", segment(&uuid)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/question/{question_uuid}/vote",
+    tag = "deprecated",
+    operation_id = "deprecated_vote_question",
+    request_body = Vote,
+    params(
+        ("question_uuid" = String, Path, description = "Question uuid"),
+    ),
+    responses(
+        (status = 200, description = "The question's new score", body = Score),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[post("/question/<question_uuid>/vote", data = "<vote>")]
+pub async fn vote_question(
+    question_uuid: &str,
+    vote: Json<Vote>,
+    user: AuthenticatedUser,
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+) -> Deprecated<Result<Json<Score>, APIError>> {
+    let result = super::vote_question(PathUuid::from_param(question_uuid), vote, user, questions_dao).await;
+    Deprecated::new(result, format!("/questions/{}/vote", segment(question_uuid)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/question/{question_uuid}/vote",
+    tag = "deprecated",
+    operation_id = "deprecated_retract_question_vote",
+    params(
+        ("question_uuid" = String, Path, description = "Question uuid"),
+    ),
+    responses(
+        (status = 200, description = "The question's new score", body = Score),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[delete("/question/<question_uuid>/vote")]
+pub async fn retract_question_vote(
+    question_uuid: &str,
+    user: AuthenticatedUser,
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+) -> Deprecated<Result<Json<Score>, APIError>> {
+    let result = super::retract_question_vote(PathUuid::from_param(question_uuid), user, questions_dao).await;
+    Deprecated::new(result, format!("/questions/{}/vote", segment(question_uuid)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/question/{question_uuid}/accept",
+    tag = "deprecated",
+    operation_id = "deprecated_accept_answer",
+    request_body = AnswerId,
+    params(
+        ("question_uuid" = String, Path, description = "Question uuid"),
+    ),
+    responses(
+        (status = 200, description = "The answer was accepted"),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[post("/question/<question_uuid>/accept", data = "<answer_uuid>")]
+pub async fn accept_answer(
+    question_uuid: &str,
+    answer_uuid: Json<AnswerId>,
+    user: AuthenticatedUser,
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>,
+) -> Deprecated<Result<(), APIError>> {
+    let result = super::accept_answer(PathUuid::from_param(question_uuid), answer_uuid, user, questions_dao, answers_dao).await;
+    Deprecated::new(result, format!("/questions/{}/accept", segment(question_uuid)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/question/{question_uuid}/accept",
+    tag = "deprecated",
+    operation_id = "deprecated_unaccept_answer",
+    params(
+        ("question_uuid" = String, Path, description = "Question uuid"),
+    ),
+    responses(
+        (status = 200, description = "The accepted answer was cleared"),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[delete("/question/<question_uuid>/accept")]
+pub async fn unaccept_answer(
+    question_uuid: &str,
+    user: AuthenticatedUser,
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+) -> Deprecated<Result<(), APIError>> {
+    let result = super::unaccept_answer(PathUuid::from_param(question_uuid), user, questions_dao).await;
+    Deprecated::new(result, format!("/questions/{}/accept", segment(question_uuid)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/question/{question_uuid}/revisions",
+    tag = "deprecated",
+    operation_id = "deprecated_read_question_revisions",
+    params(
+        ("question_uuid" = String, Path, description = "Question uuid"),
+    ),
+    responses(
+        (status = 200, description = "Revisions, newest first", body = Vec<QuestionRevision>),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+    security((), ("session_token" = [])),
+)]
+#[get("/question/<question_uuid>/revisions")]
+pub async fn read_question_revisions(
+    question_uuid: &str,
+    user: Option<AuthenticatedUser>,
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+) -> Deprecated<Result<Json<Vec<QuestionRevision>>, APIError>> {
+    let result = super::read_question_revisions(PathUuid::from_param(question_uuid), user, questions_dao).await;
+    Deprecated::new(result, format!("/questions/{}/revisions", segment(question_uuid)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/question/{question_uuid}/revisions/diff",
+    tag = "deprecated",
+    operation_id = "deprecated_diff_question_revisions",
+    params(
+        ("question_uuid" = String, Path, description = "Question uuid"),
+        ("from" = String, Query, description = "Older revision uuid"),
+        ("to" = String, Query, description = "Newer revision uuid"),
+    ),
+    responses(
+        (status = 200, description = "Changes between the two revisions", body = RevisionDiff),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+    security((), ("session_token" = [])),
+)]
+#[get("/question/<question_uuid>/revisions/diff?<from>&<to>")]
+pub async fn diff_question_revisions(
+    question_uuid: &str,
+    from: String,
+    to: String,
+    user: Option<AuthenticatedUser>,
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+) -> Deprecated<Result<Json<RevisionDiff>, APIError>> {
+    let successor = format!("/questions/{}/revisions/diff?from={}&to={}", segment(question_uuid), segment(&from), segment(&to));
+    let result = super::diff_question_revisions(PathUuid::from_param(question_uuid), from, to, user, questions_dao).await;
+    Deprecated::new(result, successor)
+}
+
+#[utoipa::path(
+    post,
+    path = "/question/{question_uuid}/revisions/{revision_uuid}/rollback",
+    tag = "deprecated",
+    operation_id = "deprecated_rollback_question",
+    params(
+        ("question_uuid" = String, Path, description = "Question uuid"),
+        ("revision_uuid" = String, Path, description = "Revision uuid"),
+    ),
+    responses(
+        (status = 200, description = "The question restored to the revision", body = QuestionDetail),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[post("/question/<question_uuid>/revisions/<revision_uuid>/rollback")]
+pub async fn rollback_question(
+    question_uuid: &str,
+    revision_uuid: &str,
+    user: AuthenticatedUser,
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+) -> Deprecated<Result<Json<QuestionDetail>, APIError>> {
+    let result = super::rollback_question(PathUuid::from_param(question_uuid), PathUuid::from_param(revision_uuid), user, questions_dao).await;
+    Deprecated::new(result, format!("/questions/{}/revisions/{}/rollback", segment(question_uuid), segment(revision_uuid)))
+}
+
+// ---- CRUD for Answers ----
+
+#[utoipa::path(
+    post,
+    path = "/answer",
+    tag = "deprecated",
+    operation_id = "deprecated_create_answer",
+    request_body = Answer,
+    responses(
+        (status = 200, description = "The created answer", body = AnswerDetail),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[post("/answer", data = "<answer>")]
+pub async fn create_answer(
+    answer: Json<Answer>,
+    user: AuthenticatedUser,
+    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>,
+) -> Deprecated<Result<Json<AnswerDetail>, APIError>> {
+    Deprecated::new(super::create_answer(answer, user, answers_dao).await, "/answers".to_owned())
+}
+
+#[utoipa::path(
+    get,
+    path = "/answers",
+    tag = "deprecated",
+    operation_id = "deprecated_read_answers",
+    request_body = QuestionId,
+    params(
+        ("limit" = Option<i64>, Query, description = "Page size, 1 to 100, defaults to 20"),
+        ("cursor" = Option<String>, Query, description = "`next_cursor` of the previous page"),
+        ("include_deleted" = Option<bool>, Query, description = "Include soft deleted posts, moderators only"),
+    ),
+    responses(
+        (status = 200, description = "The question's answers", body = Page<AnswerDetail>),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security((), ("session_token" = [])),
+)]
+#[get("/answers?<limit>&<cursor>&<include_deleted>", data = "<question_uuid>")]
+pub async fn read_answers(
+    question_uuid: Json<QuestionId>,
+    limit: Option<i64>,
+    cursor: Option<String>,
+    include_deleted: Option<bool>,
+    user: Option<AuthenticatedUser>,
+    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>,
+) -> Deprecated<Result<Json<Page<AnswerDetail>>, APIError>> {
+    let uuid = &question_uuid.question_uuid;
+    let result = super::read_answers(PathUuid::from_param(uuid), limit, cursor, include_deleted, user, answers_dao).await;
+    Deprecated::new(result, format!("/questions/{}/answers", segment(uuid)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/answer",
+    tag = "deprecated",
+    operation_id = "deprecated_delete_answer",
+    request_body = AnswerId,
+    responses(
+        (status = 200, description = "The answer was soft deleted"),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[delete("/answer", data = "<answer_uuid>")]
+pub async fn delete_answer(
+    answer_uuid: Json<AnswerId>,
+    user: AuthenticatedUser,
+    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>,
+) -> Deprecated<Result<(), APIError>> {
+    let uuid = &answer_uuid.answer_uuid;
+    let result = super::delete_answer(PathUuid::from_param(uuid), user, answers_dao).await;
+    Deprecated::new(result, format!("/answers/{}", segment(uuid)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/answer/{answer_uuid}/undelete",
+    tag = "deprecated",
+    operation_id = "deprecated_undelete_answer",
+    params(
+        ("answer_uuid" = String, Path, description = "Answer uuid"),
+    ),
+    responses(
+        (status = 200, description = "The answer was restored"),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[post("/answer/<answer_uuid>/undelete")]
+pub async fn undelete_answer(
+    answer_uuid: &str,
+    user: AuthenticatedUser,
+    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>,
+) -> Deprecated<Result<(), APIError>> {
+    let result = super::undelete_answer(PathUuid::from_param(answer_uuid), user, answers_dao).await;
+    Deprecated::new(result, format!("/answers/{}/undelete", segment(answer_uuid)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/answer",
+    tag = "deprecated",
+    operation_id = "deprecated_update_answer",
+    request_body = UpdateRequest<Answer>,
+    responses(
+        (status = 200, description = "The updated answer", body = AnswerDetail),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[put("/answer", data = "<update_request>")]
+pub async fn update_answer(
+    update_request: Json<UpdateRequest<Answer>>,
+    user: AuthenticatedUser,
+    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>,
+) -> Deprecated<Result<Json<AnswerDetail>, APIError>> {
+    let UpdateRequest { updated_entity, uuid, edit_summary } = update_request.0;
+    let edit = Json(Edit { updated_entity, edit_summary });
+    let result = super::update_answer(PathUuid::from_param(&uuid), edit, user, answers_dao).await;
+    Deprecated::new(result, format!("/answers/{}", segment(&uuid)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/answer/{answer_uuid}/vote",
+    tag = "deprecated",
+    operation_id = "deprecated_vote_answer",
+    request_body = Vote,
+    params(
+        ("answer_uuid" = String, Path, description = "Answer uuid"),
+    ),
+    responses(
+        (status = 200, description = "The answer's new score", body = Score),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[post("/answer/<answer_uuid>/vote", data = "<vote>")]
+pub async fn vote_answer(
+    answer_uuid: &str,
+    vote: Json<Vote>,
+    user: AuthenticatedUser,
+    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>,
+) -> Deprecated<Result<Json<Score>, APIError>> {
+    let result = super::vote_answer(PathUuid::from_param(answer_uuid), vote, user, answers_dao).await;
+    Deprecated::new(result, format!("/answers/{}/vote", segment(answer_uuid)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/answer/{answer_uuid}/vote",
+    tag = "deprecated",
+    operation_id = "deprecated_retract_answer_vote",
+    params(
+        ("answer_uuid" = String, Path, description = "Answer uuid"),
+    ),
+    responses(
+        (status = 200, description = "The answer's new score", body = Score),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[delete("/answer/<answer_uuid>/vote")]
+pub async fn retract_answer_vote(
+    answer_uuid: &str,
+    user: AuthenticatedUser,
+    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>,
+) -> Deprecated<Result<Json<Score>, APIError>> {
+    let result = super::retract_answer_vote(PathUuid::from_param(answer_uuid), user, answers_dao).await;
+    Deprecated::new(result, format!("/answers/{}/vote", segment(answer_uuid)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/answer/{answer_uuid}/revisions",
+    tag = "deprecated",
+    operation_id = "deprecated_read_answer_revisions",
+    params(
+        ("answer_uuid" = String, Path, description = "Answer uuid"),
+    ),
+    responses(
+        (status = 200, description = "Revisions, newest first", body = Vec<AnswerRevision>),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+    security((), ("session_token" = [])),
+)]
+#[get("/answer/<answer_uuid>/revisions")]
+pub async fn read_answer_revisions(
+    answer_uuid: &str,
+    user: Option<AuthenticatedUser>,
+    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>,
+) -> Deprecated<Result<Json<Vec<AnswerRevision>>, APIError>> {
+    let result = super::read_answer_revisions(PathUuid::from_param(answer_uuid), user, answers_dao).await;
+    Deprecated::new(result, format!("/answers/{}/revisions", segment(answer_uuid)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/answer/{answer_uuid}/revisions/diff",
+    tag = "deprecated",
+    operation_id = "deprecated_diff_answer_revisions",
+    params(
+        ("answer_uuid" = String, Path, description = "Answer uuid"),
+        ("from" = String, Query, description = "Older revision uuid"),
+        ("to" = String, Query, description = "Newer revision uuid"),
+    ),
+    responses(
+        (status = 200, description = "Changes between the two revisions", body = RevisionDiff),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+    security((), ("session_token" = [])),
+)]
+#[get("/answer/<answer_uuid>/revisions/diff?<from>&<to>")]
+pub async fn diff_answer_revisions(
+    answer_uuid: &str,
+    from: String,
+    to: String,
+    user: Option<AuthenticatedUser>,
+    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>,
+) -> Deprecated<Result<Json<RevisionDiff>, APIError>> {
+    let successor = format!("/answers/{}/revisions/diff?from={}&to={}", segment(answer_uuid), segment(&from), segment(&to));
+    let result = super::diff_answer_revisions(PathUuid::from_param(answer_uuid), from, to, user, answers_dao).await;
+    Deprecated::new(result, successor)
+}
+
+#[utoipa::path(
+    post,
+    path = "/answer/{answer_uuid}/revisions/{revision_uuid}/rollback",
+    tag = "deprecated",
+    operation_id = "deprecated_rollback_answer",
+    params(
+        ("answer_uuid" = String, Path, description = "Answer uuid"),
+        ("revision_uuid" = String, Path, description = "Revision uuid"),
+    ),
+    responses(
+        (status = 200, description = "The answer restored to the revision", body = AnswerDetail),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[post("/answer/<answer_uuid>/revisions/<revision_uuid>/rollback")]
+pub async fn rollback_answer(
+    answer_uuid: &str,
+    revision_uuid: &str,
+    user: AuthenticatedUser,
+    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>,
+) -> Deprecated<Result<Json<AnswerDetail>, APIError>> {
+    let result = super::rollback_answer(PathUuid::from_param(answer_uuid), PathUuid::from_param(revision_uuid), user, answers_dao).await;
+    Deprecated::new(result, format!("/answers/{}/revisions/{}/rollback", segment(answer_uuid), segment(revision_uuid)))
+}
+
+// ---- CRUD for Comments ----
+
+#[utoipa::path(
+    post,
+    path = "/comment",
+    tag = "deprecated",
+    operation_id = "deprecated_create_comment",
+    request_body = Comment,
+    responses(
+        (status = 200, description = "The created comment", body = CommentDetail),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[post("/comment", data = "<comment>")]
+pub async fn create_comment(
+    comment: Json<Comment>,
+    user: AuthenticatedUser,
+    comments_dao: &State<Box<dyn CommentsDao + Send + Sync>>,
+) -> Deprecated<Result<Json<CommentDetail>, APIError>> {
+    Deprecated::new(super::create_comment(comment, user, comments_dao).await, "/comments".to_owned())
+}
+
+#[utoipa::path(
+    get,
+    path = "/comments",
+    tag = "deprecated",
+    operation_id = "deprecated_read_comments",
+    request_body = CommentParent,
+    params(
+        ("limit" = Option<i64>, Query, description = "Page size, 1 to 100, defaults to 20"),
+        ("cursor" = Option<String>, Query, description = "`next_cursor` of the previous page"),
+    ),
+    responses(
+        (status = 200, description = "The post's comments, oldest first", body = Page<CommentDetail>),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+    security((), ("session_token" = [])),
+)]
+#[get("/comments?<limit>&<cursor>", data = "<parent>")]
+pub async fn read_comments(
+    parent: Json<CommentParent>,
+    limit: Option<i64>,
+    cursor: Option<String>,
+    user: Option<AuthenticatedUser>,
+    comments_dao: &State<Box<dyn CommentsDao + Send + Sync>>,
+) -> Deprecated<Result<Json<Page<CommentDetail>>, APIError>> {
+    let successor = match (&parent.question_uuid, &parent.answer_uuid) {
+        (Some(question_uuid), _) => format!("/questions/{}/comments", segment(question_uuid)),
+        (None, Some(answer_uuid)) => format!("/answers/{}/comments", segment(answer_uuid)),
+        (None, None) => "/questions/{question_uuid}/comments".to_owned(),
+    };
+
+    let result = async {
+        let page = handlers_inner::page_request(limit, cursor)?;
+        let comments = handlers_inner::read_comments(parent.0, page, user.as_ref(), comments_dao).await
+                                                                .map_err(Into::<APIError>::into)?;
+        Ok(Json(comments))
+    }.await;
+
+    Deprecated::new(result, successor)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/comment",
+    tag = "deprecated",
+    operation_id = "deprecated_delete_comment",
+    request_body = CommentId,
+    responses(
+        (status = 200, description = "The comment was deleted"),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[delete("/comment", data = "<comment_uuid>")]
+pub async fn delete_comment(
+    comment_uuid: Json<CommentId>,
+    user: AuthenticatedUser,
+    comments_dao: &State<Box<dyn CommentsDao + Send + Sync>>,
+) -> Deprecated<Result<(), APIError>> {
+    let uuid = &comment_uuid.comment_uuid;
+    let result = super::delete_comment(PathUuid::from_param(uuid), user, comments_dao).await;
+    Deprecated::new(result, format!("/comments/{}", segment(uuid)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/comment",
+    tag = "deprecated",
+    operation_id = "deprecated_update_comment",
+    request_body = UpdateRequest<Comment>,
+    responses(
+        (status = 200, description = "The updated comment", body = CommentDetail),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[put("/comment", data = "<update_request>")]
+pub async fn update_comment(
+    update_request: Json<UpdateRequest<Comment>>,
+    user: AuthenticatedUser,
+    comments_dao: &State<Box<dyn CommentsDao + Send + Sync>>,
+) -> Deprecated<Result<Json<CommentDetail>, APIError>> {
+    let UpdateRequest { updated_entity, uuid, edit_summary } = update_request.0;
+    let edit = Json(Edit { updated_entity, edit_summary });
+    let result = super::update_comment(PathUuid::from_param(&uuid), edit, user, comments_dao).await;
+    Deprecated::new(result, format!("/comments/{}", segment(&uuid)))
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::{http::Status, local::asynchronous::Client, Config};
+
+    use super::*;
+    use crate::{
+        handlers::default_catcher,
+        persistance::in_memory::{InMemoryQuestionsDao, InMemoryStore},
+    };
+
+    #[test]
+    fn segment_should_normalize_or_encode_uuids() {
+        assert_eq!(segment("A22ABCD2-22AB-4222-A22B-2ABC2A2B22CC"), "a22abcd2-22ab-4222-a22b-2abc2a2b22cc");
+        assert_eq!(segment("x>; rel=\"evil\"\r\n"), "x%3E;%20rel%3D%22evil%22%0D%0A");
+    }
+
+    #[rocket::async_test]
+    async fn malformed_uuid_should_not_reach_link_header() {
+        let questions_dao: Box<dyn QuestionsDao + Sync + Send> = Box::new(InMemoryQuestionsDao::new(InMemoryStore::new()));
+        let rocket = rocket::custom(Config::debug_default())
+            .mount("/", routes![diff_question_revisions])
+            .register("/", catchers![default_catcher])
+            .manage(questions_dao);
+        let client = Client::tracked(rocket).await.unwrap();
+
+        let response = client
+            .get("/question/not%3E%20a%2Fuuid/revisions/diff?from=a%26b&to=A22ABCD2-22AB-4222-A22B-2ABC2A2B22CC")
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest);
+        assert_eq!(
+            response.headers().get_one("Link"),
+            Some("</questions/not%3E%20a%2Fuuid/revisions/diff?from=a%26b&to=a22abcd2-22ab-4222-a22b-2abc2a2b22cc>; rel=\"successor-version\"")
+        );
+    }
+}
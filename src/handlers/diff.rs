@@ -0,0 +1,29 @@
+use similar::{ChangeTag, TextDiff};
+
+use crate::models::{DiffChange, DiffOp, FieldDiff};
+
+/// Word level diff of a single field, consecutive words with the same change are merged into one entry.
+pub fn diff_field(field: &str, old: &str, new: &str) -> FieldDiff {
+    let mut changes: Vec<DiffChange> = Vec::new();
+
+    for change in TextDiff::from_words(old, new).iter_all_changes() {
+        let op = match change.tag() {
+            ChangeTag::Equal => DiffOp::Equal,
+            ChangeTag::Insert => DiffOp::Insert,
+            ChangeTag::Delete => DiffOp::Delete,
+        };
+
+        match changes.last_mut() {
+            Some(last) if last.op == op => last.value.push_str(change.value()),
+            _ => changes.push(DiffChange {
+                op,
+                value: change.value().to_owned(),
+            }),
+        }
+    }
+
+    FieldDiff {
+        field: field.to_owned(),
+        changes,
+    }
+}
@@ -0,0 +1,154 @@
+use rocket::{
+    http::Status,
+    response::{self, Responder},
+    serde::json::{json, Json, Value},
+    Request, Response,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::handlers_inner::HandlerError;
+
+/// JSON body of every error response, e.g. `{"code": "question_not_found", "message": "...", "details": null}`.
+#[derive(Serialize, ToSchema, Debug, PartialEq)]
+pub struct ErrorBody {
+    /// Machine-readable snake_case code, stable across releases.
+    pub code: String,
+    pub message: String,
+    #[schema(value_type = Option<Object>)]
+    pub details: Option<Value>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct APIError {
+    pub status: Status,
+    pub body: ErrorBody,
+}
+
+impl APIError {
+    pub fn new(status: Status, code: &str, message: impl Into<String>) -> Self {
+        APIError {
+            status,
+            body: ErrorBody {
+                code: code.to_owned(),
+                message: message.into(),
+                details: None,
+            },
+        }
+    }
+
+    /// An error whose code is derived from the status, e.g. `not_found` for a 404.
+    pub fn from_status(status: Status, message: impl Into<String>) -> Self {
+        let code = status.reason().unwrap_or("error").to_lowercase().replace([' ', '-'], "_");
+        Self::new(status, &code, message)
+    }
+
+    pub fn with_details(mut self, details: Value) -> Self {
+        self.body.details = Some(details);
+        self
+    }
+}
+
+impl From<HandlerError> for APIError {
+    fn from(value: HandlerError) -> Self {
+        match value {
+            HandlerError::BadRequest(code, s) => Self::new(Status::BadRequest, code, s),
+            HandlerError::Unauthorized(s) => Self::from_status(Status::Unauthorized, s),
+            HandlerError::Forbidden(s) => Self::from_status(Status::Forbidden, s),
+            HandlerError::NotFound(code, s) => Self::new(Status::NotFound, code, s),
+            HandlerError::Conflict(code, s) => Self::new(Status::Conflict, code, s),
+            HandlerError::Unavailable(s) => Self::from_status(Status::ServiceUnavailable, s),
+            HandlerError::Validation(fields) => {
+                Self::new(Status::UnprocessableEntity, "validation_failed", "The request body has invalid fields")
+                    .with_details(json!({ "fields": fields }))
+            }
+            HandlerError::InternalError(s) => Self::from_status(Status::InternalServerError, s),
+        }
+    }
+}
+
+impl<'r> Responder<'r, 'static> for APIError {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        Response::build_from(Json(self.body).respond_to(request)?)
+            .status(self.status)
+            .ok()
+    }
+}
+
+/// Turns the errors Rocket produces itself, such as unmatched routes, failing guards and unparsable
+/// bodies, into the same JSON bodies the handlers return.
+#[catch(default)]
+pub fn default_catcher(status: Status, request: &Request) -> APIError {
+    let message = match status.code {
+        404 => format!("No route matches {} {}", request.method(), request.uri().path()),
+        422 => "The request body could not be parsed".to_owned(),
+        _ => status.reason_lossy().to_owned(),
+    };
+
+    APIError::from_status(status, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::validation::FieldError;
+    use crate::models::DBError;
+
+    #[test]
+    fn from_status_should_derive_code_from_reason() {
+        let error = APIError::from_status(Status::UnprocessableEntity, "test message");
+
+        assert_eq!(error.status, Status::UnprocessableEntity);
+        assert_eq!(error.body.code, "unprocessable_entity");
+        assert_eq!(error.body.message, "test message");
+    }
+
+    #[test]
+    fn handler_errors_should_keep_their_status() {
+        let error: APIError = HandlerError::Forbidden("test message".to_owned()).into();
+
+        assert_eq!(error, APIError::new(Status::Forbidden, "forbidden", "test message"));
+    }
+
+    #[test]
+    fn database_handler_errors_should_map_to_their_status() {
+        let not_found: APIError = HandlerError::NotFound("answer_not_found", "test message".to_owned()).into();
+        let conflict: APIError = HandlerError::Conflict("conflict", "test message".to_owned()).into();
+        let unavailable: APIError = HandlerError::Unavailable("test message".to_owned()).into();
+
+        assert_eq!((not_found.status, not_found.body.code.as_str()), (Status::NotFound, "answer_not_found"));
+        assert_eq!((conflict.status, conflict.body.code.as_str()), (Status::Conflict, "conflict"));
+        assert_eq!((unavailable.status, unavailable.body.code.as_str()), (Status::ServiceUnavailable, "service_unavailable"));
+    }
+
+    #[test]
+    fn database_errors_should_keep_their_domain_codes() {
+        let cases = [
+            (DBError::not_found("comment_not_found", "Comment not found"), Status::NotFound, "comment_not_found"),
+            (DBError::InvalidUUID("malformed".to_owned()), Status::BadRequest, "invalid_uuid"),
+            (DBError::DuplicateUsername("test_user".to_owned()), Status::Conflict, "duplicate_username"),
+        ];
+
+        for (err, status, code) in cases {
+            let error = APIError::from(HandlerError::from(err));
+
+            assert_eq!((error.status, error.body.code.as_str()), (status, code));
+        }
+    }
+
+    #[test]
+    fn validation_errors_should_list_the_fields() {
+        let error: APIError = HandlerError::Validation(vec![FieldError {
+            field: "title".to_owned(),
+            message: "must not be blank".to_owned(),
+        }])
+        .into();
+
+        assert_eq!(error.status, Status::UnprocessableEntity);
+        assert_eq!(error.body.code, "validation_failed");
+        assert_eq!(
+            error.body.details,
+            Some(json!({ "fields": [{ "field": "title", "message": "must not be blank" }] }))
+        );
+    }
+}
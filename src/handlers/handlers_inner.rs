@@ -1,11 +1,35 @@
+#![allow(clippy::borrowed_box)]
+
+use super::diff::diff_field;
+use super::policy::{can_accept_answer, can_modify_post, can_undelete_post, can_view_deleted_posts, can_vote_on_post};
+use super::validation::{validate, FieldError, Normalize, Validator, EDIT_SUMMARY};
 use crate::{
-    models::{Answer, AnswerDetail, AnswerId, DBError, Question, QuestionDetail, QuestionId},
-    persistance::{answers_dao::AnswersDao, questions_dao::QuestionsDao},
+    auth::{
+        generate_session_token, hash_password, hash_session_token, verify_password, AuthenticatedUser,
+        SESSION_TTL_SECONDS,
+    },
+    models::{
+        Answer, AnswerDetail, AnswerId, AnswerRevision, Comment, CommentDetail, CommentId, CommentParent, Credentials, Cursor, DBError, HealthCheck,
+        HealthReport, HealthStatus, MigrationStatus, Page, PageRequest, Question, QuestionDetail, QuestionId, QuestionRevision, QuestionSearchResult,
+        QuestionWithAnswers, RevisionDiff, Score, Session, TagDetail, UserDetail, Vote,
+    },
+    persistance::{
+        answers_dao::AnswersDao, comments_dao::CommentsDao, questions_dao::QuestionsDao, users_dao::UsersDao,
+    },
 };
 
+/// `BadRequest`, `NotFound` and `Conflict` carry the code of the error body along with the message, e.g.
+/// `answer_not_found`, so that clients can tell the cases apart without parsing messages.
 #[derive(Debug, PartialEq)]
 pub enum HandlerError {
-    BadRequest(String),
+    BadRequest(&'static str, String),
+    Unauthorized(String),
+    Forbidden(String),
+    NotFound(&'static str, String),
+    /// The body was well-formed but some of its fields break the rules in `validation`.
+    Validation(Vec<FieldError>),
+    Conflict(&'static str, String),
+    Unavailable(String),
     InternalError(String),
 }
 
@@ -15,43 +39,445 @@ impl HandlerError {
     }
 }
 
+impl From<DBError> for HandlerError {
+    fn from(err: DBError) -> Self {
+        match err {
+            DBError::InvalidUUID(s) => HandlerError::BadRequest("invalid_uuid", s),
+            DBError::NotFound { code, message, .. } => HandlerError::NotFound(code, message),
+            DBError::Conflict { code, message, .. } => HandlerError::Conflict(code, message),
+            DBError::DuplicateUsername(_) => HandlerError::Conflict("duplicate_username", err.to_string()),
+            DBError::Unavailable { .. } => {
+                HandlerError::Unavailable("Service is temporarily unavailable! Please try again later.".to_owned())
+            }
+            DBError::Other(_) => HandlerError::default_internal_error(),
+        }
+    }
+}
+
+async fn get_question_author(
+    question_uuid: &str,
+    include_deleted: bool,
+    questions_dao: &Box<dyn QuestionsDao + Sync + Send>,
+) -> Result<Option<String>, HandlerError> {
+    questions_dao
+        .get_question_author(question_uuid.to_owned(), include_deleted)
+        .await
+        .map_err(|err| {
+            error!("{}", err);
+
+            HandlerError::from(err)
+        })
+}
+
+async fn get_answer_author(
+    answer_uuid: &str,
+    include_deleted: bool,
+    answers_dao: &Box<dyn AnswersDao + Sync + Send>,
+) -> Result<Option<String>, HandlerError> {
+    answers_dao
+        .get_answer_author(answer_uuid.to_owned(), include_deleted)
+        .await
+        .map_err(|err| {
+            error!("{}", err);
+
+            HandlerError::from(err)
+        })
+}
+
+async fn authorize_question_change(
+    question_uuid: &str,
+    user: &AuthenticatedUser,
+    questions_dao: &Box<dyn QuestionsDao + Sync + Send>,
+) -> Result<(), HandlerError> {
+    // Moderators can still edit a deleted question, for everyone else it is gone.
+    let author_uuid = get_question_author(question_uuid, can_view_deleted_posts(Some(user)), questions_dao).await?;
+
+    if !can_modify_post(user, author_uuid.as_deref()) {
+        return Err(HandlerError::Forbidden(
+            "Only the author or a moderator can modify this question".to_owned(),
+        ));
+    }
+
+    Ok(())
+}
+
+async fn authorize_answer_change(
+    answer_uuid: &str,
+    user: &AuthenticatedUser,
+    answers_dao: &Box<dyn AnswersDao + Sync + Send>,
+) -> Result<(), HandlerError> {
+    let author_uuid = get_answer_author(answer_uuid, can_view_deleted_posts(Some(user)), answers_dao).await?;
+
+    if !can_modify_post(user, author_uuid.as_deref()) {
+        return Err(HandlerError::Forbidden(
+            "Only the author or a moderator can modify this answer".to_owned(),
+        ));
+    }
+
+    Ok(())
+}
+
+async fn authorize_comment_change(
+    comment_uuid: &str,
+    user: &AuthenticatedUser,
+    comments_dao: &Box<dyn CommentsDao + Sync + Send>,
+) -> Result<(), HandlerError> {
+    let author_uuid = comments_dao
+        .get_comment_author(comment_uuid.to_owned())
+        .await
+        .map_err(|err| {
+            error!("{}", err);
+
+            HandlerError::from(err)
+        })?;
+
+    if !can_modify_post(user, author_uuid.as_deref()) {
+        return Err(HandlerError::Forbidden(
+            "Only the author or a moderator can modify this comment".to_owned(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn check_comment_parent(parent: &CommentParent) -> Result<(), HandlerError> {
+    if parent.question_uuid.is_some() == parent.answer_uuid.is_some() {
+        return Err(HandlerError::BadRequest(
+            "invalid_comment_parent",
+            "Exactly one of question_uuid or answer_uuid must be provided".to_owned(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Blank summaries are dropped rather than stored.
+fn normalize_edit_summary(edit_summary: Option<String>) -> Option<String> {
+    edit_summary
+        .map(|summary| summary.trim().to_owned())
+        .filter(|summary| !summary.is_empty())
+}
+
+fn authorize_deleted_posts_view(include_deleted: bool, user: Option<&AuthenticatedUser>) -> Result<(), HandlerError> {
+    if include_deleted && !can_view_deleted_posts(user) {
+        return Err(HandlerError::Forbidden(
+            "Only moderators can view deleted posts".to_owned(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// A post's revisions are visible to whoever can see the post, so those of deleted posts only to moderators.
+async fn authorize_question_history_view(
+    question_uuid: &str,
+    user: Option<&AuthenticatedUser>,
+    questions_dao: &Box<dyn QuestionsDao + Sync + Send>,
+) -> Result<(), HandlerError> {
+    let question = questions_dao
+        .get_question(question_uuid.to_owned(), false, can_view_deleted_posts(user))
+        .await
+        .map_err(|err| {
+            error!("{}", err);
+
+            HandlerError::from(err)
+        })?;
+
+    match question {
+        Some(_) => Ok(()),
+        None => Err(HandlerError::NotFound("question_not_found", "Question not found".to_owned())),
+    }
+}
+
+async fn authorize_answer_history_view(
+    answer_uuid: &str,
+    user: Option<&AuthenticatedUser>,
+    answers_dao: &Box<dyn AnswersDao + Sync + Send>,
+) -> Result<(), HandlerError> {
+    let answer = answers_dao
+        .get_answer(answer_uuid.to_owned(), can_view_deleted_posts(user))
+        .await
+        .map_err(|err| {
+            error!("{}", err);
+
+            HandlerError::from(err)
+        })?;
+
+    match answer {
+        Some(_) => Ok(()),
+        None => Err(HandlerError::NotFound("answer_not_found", "Answer not found".to_owned())),
+    }
+}
+
+pub fn page_request(limit: Option<i64>, cursor: Option<String>) -> Result<PageRequest, HandlerError> {
+    let limit = limit.unwrap_or(PageRequest::DEFAULT_LIMIT);
+
+    if !(1..=PageRequest::MAX_LIMIT).contains(&limit) {
+        return Err(HandlerError::BadRequest("invalid_limit", format!(
+            "limit must be between 1 and {}",
+            PageRequest::MAX_LIMIT
+        )));
+    }
+
+    let cursor = match cursor {
+        Some(cursor) => Some(
+            Cursor::decode(&cursor)
+                .ok_or_else(|| HandlerError::BadRequest("invalid_cursor", format!("Invalid cursor provided: {}", cursor)))?,
+        ),
+        None => None,
+    };
+
+    Ok(PageRequest { limit, cursor })
+}
+
+pub const MAX_TAGS_PER_QUESTION: usize = 5;
+pub const MAX_TAG_LENGTH: usize = 35;
+
+/// Lowercases tags, turns inner whitespace into dashes and drops duplicates, keeping the first occurrence.
+pub fn normalize_tags(tags: Vec<String>) -> Result<Vec<String>, HandlerError> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+
+    for tag in tags {
+        let tag = tag.split_whitespace().collect::<Vec<_>>().join("-").to_lowercase();
+
+        let tag_is_valid = (1..=MAX_TAG_LENGTH).contains(&tag.chars().count())
+            && tag
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "+#.-".contains(c));
+
+        if !tag_is_valid {
+            return Err(HandlerError::BadRequest("invalid_tag", format!(
+                "Invalid tag '{}': tags must be 1 to {} characters long and only contain letters, digits, '+', '#', '.' or '-'",
+                tag, MAX_TAG_LENGTH
+            )));
+        }
+
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+
+    if normalized.len() > MAX_TAGS_PER_QUESTION {
+        return Err(HandlerError::BadRequest("too_many_tags", format!(
+            "A question can have at most {} tags",
+            MAX_TAGS_PER_QUESTION
+        )));
+    }
+
+    Ok(normalized)
+}
+
+pub async fn register(
+    credentials: Credentials,
+    users_dao: &Box<dyn UsersDao + Sync + Send>,
+) -> Result<UserDetail, HandlerError> {
+    let username_is_valid = (3..=32).contains(&credentials.username.len())
+        && credentials
+            .username
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+    if !username_is_valid {
+        return Err(HandlerError::BadRequest(
+            "invalid_username",
+            "Username must be 3 to 32 characters long and only contain letters, digits, '_' or '-'".to_owned(),
+        ));
+    }
+
+    if credentials.password.chars().count() < 8 {
+        return Err(HandlerError::BadRequest(
+            "invalid_password",
+            "Password must be at least 8 characters long".to_owned(),
+        ));
+    }
+
+    let password_hash = hash_password(&credentials.password).map_err(|err| {
+        error!("{}", err);
+        HandlerError::default_internal_error()
+    })?;
+
+    let user = users_dao.create_user(credentials.username, password_hash).await;
+
+    match user {
+        Ok(user) => Ok(user),
+        Err(err) => {
+            error!("{}", err);
+
+            Err(err.into())
+        }
+    }
+}
+
+pub async fn login(
+    credentials: Credentials,
+    users_dao: &Box<dyn UsersDao + Sync + Send>,
+) -> Result<Session, HandlerError> {
+    let user_credentials = users_dao
+        .get_user_credentials(credentials.username)
+        .await
+        .map_err(|err| {
+            error!("{}", err);
+            HandlerError::from(err)
+        })?;
+
+    let user_credentials = match user_credentials {
+        Some(user_credentials) if verify_password(&credentials.password, &user_credentials.password_hash) => {
+            user_credentials
+        }
+        _ => return Err(HandlerError::Unauthorized("Invalid username or password".to_owned())),
+    };
+
+    let token = generate_session_token();
+
+    let expires_at = users_dao
+        .create_session(
+            user_credentials.user_uuid.clone(),
+            hash_session_token(&token),
+            SESSION_TTL_SECONDS,
+        )
+        .await;
+
+    match expires_at {
+        Ok(expires_at) => Ok(Session {
+            token,
+            user_uuid: user_credentials.user_uuid,
+            expires_at,
+        }),
+        Err(err) => {
+            error!("{}", err);
+            Err(err.into())
+        }
+    }
+}
+
+pub async fn logout(
+    session_token_hash: String,
+    users_dao: &Box<dyn UsersDao + Sync + Send>,
+) -> Result<(), HandlerError> {
+    let result = users_dao.delete_session(session_token_hash).await;
+
+    if let Err(err) = result {
+        error!("{}", err);
+        return Err(err.into());
+    }
+
+    Ok(())
+}
+
 pub async fn create_question(
     question: Question,
+    author_uuid: String,
     questions_dao: &Box<dyn QuestionsDao + Sync + Send>,
 ) -> Result<QuestionDetail, HandlerError> {
-    let question = questions_dao.create_question(question).await;
+    let question = question.normalize();
+    validate(&question)?;
+
+    let question = Question {
+        tags: normalize_tags(question.tags)?,
+        ..question
+    };
+
+    let question = questions_dao.create_question(question, author_uuid).await;
 
     match question {
         Ok(question) => Ok(question),
         Err(err) => {
             error!("{}", err);
-            Err(HandlerError::default_internal_error())
+            Err(err.into())
         }
     }
 }
 
 pub async fn read_questions(
+    page: PageRequest,
+    tagged: Vec<String>,
+    include_deleted: bool,
+    user: Option<&AuthenticatedUser>,
     questions_dao: &Box<dyn QuestionsDao + Sync + Send>,
-) -> Result<Vec<QuestionDetail>, HandlerError> {
-    let questions = questions_dao.get_questions().await;
+) -> Result<Page<QuestionDetail>, HandlerError> {
+    authorize_deleted_posts_view(include_deleted, user)?;
+
+    let tagged = normalize_tags(tagged)?;
+
+    let questions = questions_dao.get_questions(page, tagged, include_deleted).await;
 
     match questions {
         Ok(questions) => Ok(questions), // return questions
         Err(err) => {
             error!("{}", err);
-            Err(HandlerError::default_internal_error())
+            Err(err.into())
+        }
+    }
+}
+
+/// Returns `None` if the question does not exist or is deleted and deleted posts weren't asked for.
+pub async fn read_question(
+    question_uuid: String,
+    include_answers: bool,
+    include_deleted: bool,
+    user: Option<&AuthenticatedUser>,
+    questions_dao: &Box<dyn QuestionsDao + Sync + Send>,
+) -> Result<Option<QuestionWithAnswers>, HandlerError> {
+    authorize_deleted_posts_view(include_deleted, user)?;
+
+    let question = questions_dao.get_question(question_uuid, include_answers, include_deleted).await;
+
+    match question {
+        Ok(question) => Ok(question),
+        Err(err) => {
+            error!("{}", err);
+
+            Err(err.into())
         }
     }
 }
 
 pub async fn delete_question(
     question_uuid: QuestionId,
+    user: &AuthenticatedUser,
+    questions_dao: &Box<dyn QuestionsDao + Sync + Send>,
+) -> Result<(), HandlerError> {
+    authorize_question_change(&question_uuid.question_uuid, user, questions_dao).await?;
+
+    let result = questions_dao
+        .delete_question(question_uuid.question_uuid, user.user_uuid.clone())
+        .await;
+
+    if let Err(err) = result {
+        error!("{}", err);
+        return Err(err.into());
+    }
+
+    Ok(())
+}
+
+pub async fn undelete_question(
+    question_uuid: String,
+    user: &AuthenticatedUser,
     questions_dao: &Box<dyn QuestionsDao + Sync + Send>,
 ) -> Result<(), HandlerError> {
-    let result = questions_dao.delete_question(question_uuid.question_uuid).await;
+    let question = questions_dao
+        .get_question(question_uuid.clone(), false, true)
+        .await
+        .map_err(|err| {
+            error!("{}", err);
 
-    if result.is_err() {
-        return Err(HandlerError::default_internal_error());
+            HandlerError::from(err)
+        })?
+        .ok_or_else(|| HandlerError::NotFound("question_not_found", "Question not found".to_owned()))?
+        .question;
+
+    if !can_undelete_post(user, question.author_uuid.as_deref(), question.deleted_by.as_deref()) {
+        return Err(HandlerError::Forbidden(
+            "Only a moderator can restore this question".to_owned(),
+        ));
+    }
+
+    let result = questions_dao.undelete_question(question_uuid).await;
+
+    if let Err(err) = result {
+        error!("{}", err);
+        return Err(err.into());
     }
 
     Ok(())
@@ -60,498 +486,3244 @@ pub async fn delete_question(
 pub async fn update_question(
     updated_question: Question,
     uuid: String,
+    edit_summary: Option<String>,
+    user: &AuthenticatedUser,
     questions_dao: &Box<dyn QuestionsDao + Sync + Send>,
 ) -> Result<QuestionDetail, HandlerError> {
+    let updated_question = updated_question.normalize();
+    Validator::default()
+        .nested("updated_entity", &updated_question)
+        .optional_text("edit_summary", edit_summary.as_deref(), &EDIT_SUMMARY)
+        .finish()?;
+
+    authorize_question_change(&uuid, user, questions_dao).await?;
+
+    let updated_question = Question {
+        tags: normalize_tags(updated_question.tags)?,
+        ..updated_question
+    };
+    let edit_summary = normalize_edit_summary(edit_summary);
+
     let question = questions_dao
-        .update_question(updated_question, uuid)
+        .update_question(updated_question, uuid, user.user_uuid.clone(), edit_summary)
         .await;
 
     match question {
         Ok(question) => Ok(question),
         Err(err) => {
             error!("{}", err);
-            Err(HandlerError::default_internal_error())
+            Err(err.into())
         }
     }
 }
 
-pub async fn create_answer(
-    answer: Answer,
-    answers_dao: &Box<dyn AnswersDao + Send + Sync>,
-) -> Result<AnswerDetail, HandlerError> {
-    let answer = answers_dao.create_answer(answer).await;
+pub async fn read_question_revisions(
+    question_uuid: String,
+    user: Option<&AuthenticatedUser>,
+    questions_dao: &Box<dyn QuestionsDao + Sync + Send>,
+) -> Result<Vec<QuestionRevision>, HandlerError> {
+    authorize_question_history_view(&question_uuid, user, questions_dao).await?;
 
-    match answer {
-        Ok(answer) => Ok(answer),
+    let revisions = questions_dao.get_question_revisions(question_uuid).await;
+
+    match revisions {
+        Ok(revisions) => Ok(revisions),
         Err(err) => {
             error!("{}", err);
 
-            match err {
-                DBError::InvalidUUID(s) => Err(HandlerError::BadRequest(s)),
-                _ => Err(HandlerError::default_internal_error()), 
-            }
+            Err(err.into())
         }
     }
 }
 
-pub async fn read_answers(
-    question_uuid: QuestionId,
-    answers_dao: &Box<dyn AnswersDao + Send + Sync>,
-) -> Result<Vec<AnswerDetail>, HandlerError> {
-    let answers = answers_dao.get_answers(question_uuid.question_uuid).await;
+async fn get_question_revision(
+    question_uuid: &str,
+    revision_uuid: &str,
+    questions_dao: &Box<dyn QuestionsDao + Sync + Send>,
+) -> Result<QuestionRevision, HandlerError> {
+    let revision = questions_dao
+        .get_question_revision(question_uuid.to_owned(), revision_uuid.to_owned())
+        .await
+        .map_err(|err| {
+            error!("{}", err);
 
-    match answers {
-        Ok(answers) => Ok(answers),
-        Err(e) => {
-            error!("{}", e);
-            Err(HandlerError::default_internal_error())
-        }
-    }
+            HandlerError::from(err)
+        })?;
+
+    revision.ok_or_else(|| {
+        HandlerError::NotFound("revision_not_found", format!(
+            "Revision {} does not belong to question {}",
+            revision_uuid, question_uuid
+        ))
+    })
 }
 
-pub async fn delete_answer(
-    answer_uuid: AnswerId,
-    answers_dao: &Box<dyn AnswersDao + Send + Sync>,
-) -> Result<(), HandlerError> {
-    let result = answers_dao.delete_answer(answer_uuid.answer_uuid).await;
+pub async fn diff_question_revisions(
+    question_uuid: String,
+    from_revision_uuid: String,
+    to_revision_uuid: String,
+    user: Option<&AuthenticatedUser>,
+    questions_dao: &Box<dyn QuestionsDao + Sync + Send>,
+) -> Result<RevisionDiff, HandlerError> {
+    authorize_question_history_view(&question_uuid, user, questions_dao).await?;
+
+    let from = get_question_revision(&question_uuid, &from_revision_uuid, questions_dao).await?;
+    let to = get_question_revision(&question_uuid, &to_revision_uuid, questions_dao).await?;
+
+    Ok(RevisionDiff {
+        from_revision_uuid,
+        to_revision_uuid,
+        fields: vec![
+            diff_field("title", &from.title, &to.title),
+            diff_field("description", &from.description, &to.description),
+            diff_field("tags", &from.tags.join(" "), &to.tags.join(" ")),
+        ],
+    })
+}
 
-    if result.is_err() {
-        return Err(HandlerError::default_internal_error()); 
-    }
+/// Restores an earlier revision's content as a new edit, so the rollback itself shows up in the history.
+pub async fn rollback_question(
+    question_uuid: String,
+    revision_uuid: String,
+    user: &AuthenticatedUser,
+    questions_dao: &Box<dyn QuestionsDao + Sync + Send>,
+) -> Result<QuestionDetail, HandlerError> {
+    authorize_question_change(&question_uuid, user, questions_dao).await?;
 
-    Ok(())
-}
+    let revision = get_question_revision(&question_uuid, &revision_uuid, questions_dao).await?;
 
-// ***********************************************************
-//                           Tests 
-// ***********************************************************
+    let restored_question = Question {
+        title: revision.title,
+        description: revision.description,
+        tags: revision.tags,
+    };
 
-pub async fn update_answer(
-    updated_answer: Answer,
-    uuid: String,
-    answers_dao: &Box<dyn AnswersDao + Sync + Send>,
-) -> Result<AnswerDetail, HandlerError> {
-    let answer = answers_dao.update_answer(updated_answer, uuid).await;
+    let question = questions_dao
+        .update_question(
+            restored_question,
+            question_uuid,
+            user.user_uuid.clone(),
+            Some(format!("Rolled back to revision {}", revision_uuid)),
+        )
+        .await;
 
-    match answer {
-        Ok(answer) => Ok(answer),
+    match question {
+        Ok(question) => Ok(question),
         Err(err) => {
             error!("{}", err);
-            Err(HandlerError::default_internal_error())
+            Err(err.into())
         }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub async fn search_questions(
+    query: String,
+    limit: Option<i64>,
+    questions_dao: &Box<dyn QuestionsDao + Sync + Send>,
+) -> Result<Vec<QuestionSearchResult>, HandlerError> {
+    // Backends search for words, so a query of only punctuation would have nothing to match.
+    if !query.chars().any(char::is_alphanumeric) {
+        return Err(HandlerError::BadRequest("empty_query", "Search query must contain a word".to_owned()));
+    }
 
-    use tokio::sync::Mutex;
+    let limit = page_request(limit, None)?.limit;
 
-    struct QuestionsDaoMock {
-        create_question_response: Mutex<Option<Result<QuestionDetail, DBError>>>,
-        delete_question_response: Mutex<Option<Result<(), DBError>>>,
-        get_questions_response: Mutex<Option<Result<Vec<QuestionDetail>, DBError>>>,
-        update_question_response: Mutex<Option<Result<QuestionDetail, DBError>>>,
-    }
+    let results = questions_dao.search(query, limit).await;
 
-    impl QuestionsDaoMock {
-        pub fn new() -> Self {
-            QuestionsDaoMock {
-                create_question_response: Mutex::new(None),
-                delete_question_response: Mutex::new(None),
-                get_questions_response: Mutex::new(None),
-                update_question_response: Mutex::new(None),
-            }
-        }
-        pub fn mock_create_question(&mut self, response: Result<QuestionDetail, DBError>) {
-            self.create_question_response = Mutex::new(Some(response));
-        }
-        pub fn mock_delete_question(&mut self, response: Result<(), DBError>) {
-            self.delete_question_response = Mutex::new(Some(response));
+    match results {
+        Ok(results) => Ok(results),
+        Err(err) => {
+            error!("{}", err);
+            Err(err.into())
         }
-        pub fn mock_get_questions(&mut self, response: Result<Vec<QuestionDetail>, DBError>) {
-            self.get_questions_response = Mutex::new(Some(response));
+    }
+}
+
+pub async fn read_tags(
+    questions_dao: &Box<dyn QuestionsDao + Sync + Send>,
+) -> Result<Vec<TagDetail>, HandlerError> {
+    let tags = questions_dao.get_tags().await;
+
+    match tags {
+        Ok(tags) => Ok(tags),
+        Err(err) => {
+            error!("{}", err);
+            Err(err.into())
         }
-        pub fn mock_update_question(&mut self, response: Result<QuestionDetail, DBError>) {
-            self.update_question_response = Mutex::new(Some(response));
+    }
+}
+
+pub async fn vote_question(
+    question_uuid: String,
+    vote: Vote,
+    user: &AuthenticatedUser,
+    questions_dao: &Box<dyn QuestionsDao + Sync + Send>,
+) -> Result<Score, HandlerError> {
+    let author_uuid = get_question_author(&question_uuid, false, questions_dao).await?;
+
+    if !can_vote_on_post(user, author_uuid.as_deref()) {
+        return Err(HandlerError::Forbidden("You cannot vote on your own question".to_owned()));
+    }
+
+    let score = questions_dao
+        .vote_question(question_uuid, user.user_uuid.clone(), vote.direction.value())
+        .await;
+
+    match score {
+        Ok(score) => Ok(Score { score }),
+        Err(err) => {
+            error!("{}", err);
+
+            Err(err.into())
         }
     }
+}
 
-    #[async_trait]
-    impl QuestionsDao for QuestionsDaoMock {
-        async fn create_question(&self, _: Question) -> Result<QuestionDetail, DBError> {
-            self.create_question_response
-                .lock()
-                .await
-                .take()
-                .expect("create_question_response should not be None.")
-        }
-        async fn delete_question(&self, _: String) -> Result<(), DBError> {
-            self.delete_question_response
-                .lock()
-                .await
-                .take()
-                .expect("delete_question_response should not be None.")
+pub async fn retract_question_vote(
+    question_uuid: String,
+    user: &AuthenticatedUser,
+    questions_dao: &Box<dyn QuestionsDao + Sync + Send>,
+) -> Result<Score, HandlerError> {
+    let score = questions_dao
+        .retract_question_vote(question_uuid, user.user_uuid.clone())
+        .await;
+
+    match score {
+        Ok(score) => Ok(Score { score }),
+        Err(err) => {
+            error!("{}", err);
+
+            Err(err.into())
         }
-        async fn get_questions(&self) -> Result<Vec<QuestionDetail>, DBError> {
-            self.get_questions_response
-                .lock()
-                .await
-                .take()
-                .expect("get_questions_response should not be None.")
+    }
+}
+
+async fn authorize_answer_acceptance(
+    question_uuid: &str,
+    user: &AuthenticatedUser,
+    questions_dao: &Box<dyn QuestionsDao + Sync + Send>,
+) -> Result<(), HandlerError> {
+    let author_uuid = get_question_author(question_uuid, false, questions_dao).await?;
+
+    if !can_accept_answer(user, author_uuid.as_deref()) {
+        return Err(HandlerError::Forbidden(
+            "Only the author of the question can accept an answer".to_owned(),
+        ));
+    }
+
+    Ok(())
+}
+
+pub async fn accept_answer(
+    question_uuid: String,
+    answer_uuid: AnswerId,
+    user: &AuthenticatedUser,
+    questions_dao: &Box<dyn QuestionsDao + Sync + Send>,
+    answers_dao: &Box<dyn AnswersDao + Sync + Send>,
+) -> Result<(), HandlerError> {
+    authorize_answer_acceptance(&question_uuid, user, questions_dao).await?;
+
+    let belongs_to_question = answers_dao
+        .answer_belongs_to_question(answer_uuid.answer_uuid.clone(), question_uuid.clone())
+        .await
+        .map_err(|err| {
+            error!("{}", err);
+
+            HandlerError::from(err)
+        })?;
+
+    if !belongs_to_question {
+        return Err(HandlerError::BadRequest("answer_not_in_question", format!(
+            "Answer {} does not belong to question {}",
+            answer_uuid.answer_uuid, question_uuid
+        )));
+    }
+
+    let result = questions_dao
+        .set_accepted_answer(question_uuid, Some(answer_uuid.answer_uuid))
+        .await;
+
+    if let Err(err) = result {
+        error!("{}", err);
+        return Err(err.into());
+    }
+
+    Ok(())
+}
+
+pub async fn unaccept_answer(
+    question_uuid: String,
+    user: &AuthenticatedUser,
+    questions_dao: &Box<dyn QuestionsDao + Sync + Send>,
+) -> Result<(), HandlerError> {
+    authorize_answer_acceptance(&question_uuid, user, questions_dao).await?;
+
+    let result = questions_dao.set_accepted_answer(question_uuid, None).await;
+
+    if let Err(err) = result {
+        error!("{}", err);
+        return Err(err.into());
+    }
+
+    Ok(())
+}
+
+pub async fn create_answer(
+    answer: Answer,
+    author_uuid: String,
+    answers_dao: &Box<dyn AnswersDao + Send + Sync>,
+) -> Result<AnswerDetail, HandlerError> {
+    let answer = answer.normalize();
+    validate(&answer)?;
+
+    let answer = answers_dao.create_answer(answer, author_uuid).await;
+
+    match answer {
+        Ok(answer) => Ok(answer),
+        Err(err) => {
+            error!("{}", err);
+
+            Err(err.into())
         }
-        async fn update_question(&self, updated_question: Question, question_uuid: String) -> Result<QuestionDetail, DBError> {
-            self.update_question_response
-                .lock()
-                .await
-                .take()
-                .expect("get_questions_response should not be None.")
+    }
+}
+
+pub async fn read_answers(
+    question_uuid: QuestionId,
+    page: PageRequest,
+    include_deleted: bool,
+    user: Option<&AuthenticatedUser>,
+    answers_dao: &Box<dyn AnswersDao + Send + Sync>,
+) -> Result<Page<AnswerDetail>, HandlerError> {
+    authorize_deleted_posts_view(include_deleted, user)?;
+
+    let answers = answers_dao
+        .get_answers(question_uuid.question_uuid, page, include_deleted)
+        .await;
+
+    match answers {
+        Ok(answers) => Ok(answers),
+        Err(e) => {
+            error!("{}", e);
+            Err(e.into())
         }
     }
+}
 
-    struct AnswersDaoMock {
-        create_answer_response: Mutex<Option<Result<AnswerDetail, DBError>>>,
-        delete_answer_response: Mutex<Option<Result<(), DBError>>>,
-        get_answers_response: Mutex<Option<Result<Vec<AnswerDetail>, DBError>>>,
-        update_answer_response: Mutex<Option<Result<AnswerDetail, DBError>>>,
+pub async fn delete_answer(
+    answer_uuid: AnswerId,
+    user: &AuthenticatedUser,
+    answers_dao: &Box<dyn AnswersDao + Send + Sync>,
+) -> Result<(), HandlerError> {
+    authorize_answer_change(&answer_uuid.answer_uuid, user, answers_dao).await?;
+
+    let result = answers_dao
+        .delete_answer(answer_uuid.answer_uuid, user.user_uuid.clone())
+        .await;
+
+    if let Err(err) = result {
+        error!("{}", err);
+        return Err(err.into());
     }
 
-    impl AnswersDaoMock {
-        pub fn new() -> Self {
-            AnswersDaoMock {
-                create_answer_response: Mutex::new(None),
-                delete_answer_response: Mutex::new(None),
-                get_answers_response: Mutex::new(None),
-                update_answer_response: Mutex::new(None),
-            }
-        }
-        pub fn mock_create_answer(&mut self, response: Result<AnswerDetail, DBError>) {
-            self.create_answer_response = Mutex::new(Some(response));
+    Ok(())
+}
+
+pub async fn undelete_answer(
+    answer_uuid: String,
+    user: &AuthenticatedUser,
+    answers_dao: &Box<dyn AnswersDao + Send + Sync>,
+) -> Result<(), HandlerError> {
+    let answer = answers_dao
+        .get_answer(answer_uuid.clone(), true)
+        .await
+        .map_err(|err| {
+            error!("{}", err);
+
+            HandlerError::from(err)
+        })?
+        .ok_or_else(|| HandlerError::NotFound("answer_not_found", "Answer not found".to_owned()))?;
+
+    if !can_undelete_post(user, answer.author_uuid.as_deref(), answer.deleted_by.as_deref()) {
+        return Err(HandlerError::Forbidden(
+            "Only a moderator can restore this answer".to_owned(),
+        ));
+    }
+
+    let result = answers_dao.undelete_answer(answer_uuid).await;
+
+    if let Err(err) = result {
+        error!("{}", err);
+        return Err(err.into());
+    }
+
+    Ok(())
+}
+
+pub async fn vote_answer(
+    answer_uuid: String,
+    vote: Vote,
+    user: &AuthenticatedUser,
+    answers_dao: &Box<dyn AnswersDao + Sync + Send>,
+) -> Result<Score, HandlerError> {
+    let author_uuid = get_answer_author(&answer_uuid, false, answers_dao).await?;
+
+    if !can_vote_on_post(user, author_uuid.as_deref()) {
+        return Err(HandlerError::Forbidden("You cannot vote on your own answer".to_owned()));
+    }
+
+    let score = answers_dao
+        .vote_answer(answer_uuid, user.user_uuid.clone(), vote.direction.value())
+        .await;
+
+    match score {
+        Ok(score) => Ok(Score { score }),
+        Err(err) => {
+            error!("{}", err);
+
+            Err(err.into())
         }
-        pub fn mock_delete_answer(&mut self, response: Result<(), DBError>) {
-            self.delete_answer_response = Mutex::new(Some(response));
+    }
+}
+
+pub async fn retract_answer_vote(
+    answer_uuid: String,
+    user: &AuthenticatedUser,
+    answers_dao: &Box<dyn AnswersDao + Sync + Send>,
+) -> Result<Score, HandlerError> {
+    let score = answers_dao
+        .retract_answer_vote(answer_uuid, user.user_uuid.clone())
+        .await;
+
+    match score {
+        Ok(score) => Ok(Score { score }),
+        Err(err) => {
+            error!("{}", err);
+
+            Err(err.into())
         }
-        pub fn mock_get_answers(&mut self, response: Result<Vec<AnswerDetail>, DBError>) {
-            self.get_answers_response = Mutex::new(Some(response));
+    }
+}
+
+// ***********************************************************
+//                           Tests 
+// ***********************************************************
+
+pub async fn update_answer(
+    updated_answer: Answer,
+    uuid: String,
+    edit_summary: Option<String>,
+    user: &AuthenticatedUser,
+    answers_dao: &Box<dyn AnswersDao + Sync + Send>,
+) -> Result<AnswerDetail, HandlerError> {
+    let updated_answer = updated_answer.normalize();
+    Validator::default()
+        .nested("updated_entity", &updated_answer)
+        .optional_text("edit_summary", edit_summary.as_deref(), &EDIT_SUMMARY)
+        .finish()?;
+
+    authorize_answer_change(&uuid, user, answers_dao).await?;
+
+    let edit_summary = normalize_edit_summary(edit_summary);
+
+    let answer = answers_dao
+        .update_answer(updated_answer, uuid, user.user_uuid.clone(), edit_summary)
+        .await;
+
+    match answer {
+        Ok(answer) => Ok(answer),
+        Err(err) => {
+            error!("{}", err);
+            Err(err.into())
         }
-        pub fn mock_update_question(&mut self, response: Result<AnswerDetail, DBError>) {
-            self.update_answer_response = Mutex::new(Some(response));
+    }
+}
+
+pub async fn read_answer_revisions(
+    answer_uuid: String,
+    user: Option<&AuthenticatedUser>,
+    answers_dao: &Box<dyn AnswersDao + Sync + Send>,
+) -> Result<Vec<AnswerRevision>, HandlerError> {
+    authorize_answer_history_view(&answer_uuid, user, answers_dao).await?;
+
+    let revisions = answers_dao.get_answer_revisions(answer_uuid).await;
+
+    match revisions {
+        Ok(revisions) => Ok(revisions),
+        Err(err) => {
+            error!("{}", err);
+
+            Err(err.into())
         }
     }
+}
 
-    #[async_trait]
-    impl AnswersDao for AnswersDaoMock {
-        async fn create_answer(&self, _: Answer) -> Result<AnswerDetail, DBError> {
-            self.create_answer_response
-                .lock()
-                .await
-                .take()
-                .expect("create_answer_response should not be None.")
+async fn get_answer_revision(
+    answer_uuid: &str,
+    revision_uuid: &str,
+    answers_dao: &Box<dyn AnswersDao + Sync + Send>,
+) -> Result<AnswerRevision, HandlerError> {
+    let revision = answers_dao
+        .get_answer_revision(answer_uuid.to_owned(), revision_uuid.to_owned())
+        .await
+        .map_err(|err| {
+            error!("{}", err);
+
+            HandlerError::from(err)
+        })?;
+
+    revision.ok_or_else(|| {
+        HandlerError::NotFound("revision_not_found", format!(
+            "Revision {} does not belong to answer {}",
+            revision_uuid, answer_uuid
+        ))
+    })
+}
+
+pub async fn diff_answer_revisions(
+    answer_uuid: String,
+    from_revision_uuid: String,
+    to_revision_uuid: String,
+    user: Option<&AuthenticatedUser>,
+    answers_dao: &Box<dyn AnswersDao + Sync + Send>,
+) -> Result<RevisionDiff, HandlerError> {
+    authorize_answer_history_view(&answer_uuid, user, answers_dao).await?;
+
+    let from = get_answer_revision(&answer_uuid, &from_revision_uuid, answers_dao).await?;
+    let to = get_answer_revision(&answer_uuid, &to_revision_uuid, answers_dao).await?;
+
+    Ok(RevisionDiff {
+        from_revision_uuid,
+        to_revision_uuid,
+        fields: vec![diff_field("content", &from.content, &to.content)],
+    })
+}
+
+/// Restores an earlier revision's content as a new edit, so the rollback itself shows up in the history.
+pub async fn rollback_answer(
+    answer_uuid: String,
+    revision_uuid: String,
+    user: &AuthenticatedUser,
+    answers_dao: &Box<dyn AnswersDao + Sync + Send>,
+) -> Result<AnswerDetail, HandlerError> {
+    authorize_answer_change(&answer_uuid, user, answers_dao).await?;
+
+    let revision = get_answer_revision(&answer_uuid, &revision_uuid, answers_dao).await?;
+
+    let restored_answer = Answer {
+        question_uuid: revision.question_uuid,
+        content: revision.content,
+    };
+
+    let answer = answers_dao
+        .update_answer(
+            restored_answer,
+            answer_uuid,
+            user.user_uuid.clone(),
+            Some(format!("Rolled back to revision {}", revision_uuid)),
+        )
+        .await;
+
+    match answer {
+        Ok(answer) => Ok(answer),
+        Err(err) => {
+            error!("{}", err);
+            Err(err.into())
         }
-        async fn delete_answer(&self, _: String) -> Result<(), DBError> {
-            self.delete_answer_response
-                .lock()
-                .await
-                .take()
-                .expect("delete_answer_response should not be None.")
+    }
+}
+
+pub async fn create_comment(
+    comment: Comment,
+    author_uuid: String,
+    comments_dao: &Box<dyn CommentsDao + Sync + Send>,
+) -> Result<CommentDetail, HandlerError> {
+    check_comment_parent(&comment.parent)?;
+    let comment = comment.normalize();
+    validate(&comment)?;
+
+    let comment = comments_dao.create_comment(comment, author_uuid).await;
+
+    match comment {
+        Ok(comment) => Ok(comment),
+        Err(err) => {
+            error!("{}", err);
+
+            Err(err.into())
         }
-        async fn get_answers(&self, _: String) -> Result<Vec<AnswerDetail>, DBError> {
-            self.get_answers_response
-                .lock()
-                .await
-                .take()
-                .expect("get_answers_response should not be None.")
+    }
+}
+
+/// Comments on a deleted post are only visible to those who can see the post itself.
+pub async fn read_comments(
+    parent: CommentParent,
+    page: PageRequest,
+    user: Option<&AuthenticatedUser>,
+    comments_dao: &Box<dyn CommentsDao + Sync + Send>,
+) -> Result<Page<CommentDetail>, HandlerError> {
+    check_comment_parent(&parent)?;
+
+    let comments = comments_dao.get_comments(parent, page, can_view_deleted_posts(user)).await;
+
+    match comments {
+        Ok(comments) => Ok(comments),
+        Err(err) => {
+            error!("{}", err);
+
+            Err(err.into())
         }
-        async fn update_answer(&self, _: Answer, _: String) -> Result<AnswerDetail, DBError> {
-            self.update_answer_response
-                .lock()
-                .await
-                .take()
-                .expect("update_answer_response should not be None.")
+    }
+}
+
+pub async fn delete_comment(
+    comment_uuid: CommentId,
+    user: &AuthenticatedUser,
+    comments_dao: &Box<dyn CommentsDao + Sync + Send>,
+) -> Result<(), HandlerError> {
+    authorize_comment_change(&comment_uuid.comment_uuid, user, comments_dao).await?;
+
+    let result = comments_dao.delete_comment(comment_uuid.comment_uuid).await;
+
+    if let Err(err) = result {
+        error!("{}", err);
+        return Err(err.into());
+    }
+
+    Ok(())
+}
+
+pub async fn update_comment(
+    updated_comment: Comment,
+    uuid: String,
+    user: &AuthenticatedUser,
+    comments_dao: &Box<dyn CommentsDao + Sync + Send>,
+) -> Result<CommentDetail, HandlerError> {
+    let updated_comment = updated_comment.normalize();
+    Validator::default().nested("updated_entity", &updated_comment).finish()?;
+
+    authorize_comment_change(&uuid, user, comments_dao).await?;
+
+    let comment = comments_dao.update_comment(updated_comment, uuid).await;
+
+    match comment {
+        Ok(comment) => Ok(comment),
+        Err(err) => {
+            error!("{}", err);
+            Err(err.into())
         }
     }
+}
+
+// ---- Health ----
+
+/// Checks the DAOs that reach the database, then the migrations reported by the first one that answered;
+/// they share one database.
+pub async fn readiness(
+    questions_dao: &Box<dyn QuestionsDao + Sync + Send>,
+    answers_dao: &Box<dyn AnswersDao + Sync + Send>,
+) -> HealthReport {
+    let results = [
+        ("questions_dao", questions_dao.health_check().await),
+        ("answers_dao", answers_dao.health_check().await),
+    ];
+
+    let mut checks = vec![];
+    let mut migrations = None;
+    for (name, result) in results {
+        match result {
+            Ok(status) => {
+                checks.push(HealthCheck::new(name, HealthStatus::Up, None));
+                migrations.get_or_insert(status);
+            }
+            Err(err) => {
+                error!("{}", err);
+                checks.push(HealthCheck::new(name, HealthStatus::Down, Some(err.to_string())));
+            }
+        }
+    }
+
+    checks.push(match migrations {
+        Some(MigrationStatus::UpToDate) => HealthCheck::new("migrations", HealthStatus::Up, None),
+        Some(MigrationStatus::Pending(versions)) => HealthCheck {
+            pending_migrations: Some(versions),
+            ..HealthCheck::new("migrations", HealthStatus::Down, Some("Migrations are pending".to_owned()))
+        },
+        Some(MigrationStatus::Untracked) => HealthCheck::new(
+            "migrations",
+            HealthStatus::Unknown,
+            Some("The database doesn't record its migrations".to_owned()),
+        ),
+        None => HealthCheck::new("migrations", HealthStatus::Unknown, Some("The database is unreachable".to_owned())),
+    });
+
+    HealthReport::from_checks(checks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::models::{DiffChange, DiffOp, Role, UserCredentials, VoteDirection};
+    use sqlx::types::{
+        time::{OffsetDateTime, PrimitiveDateTime},
+        Uuid,
+    };
+    use tokio::sync::Mutex;
+
+    /// Mocked responses for methods called more than once in a test, returned in order.
+    type QueuedResponses<T> = Mutex<Option<Vec<Result<T, DBError>>>>;
+
+    struct QuestionsDaoMock {
+        create_question_response: Mutex<Option<Result<QuestionDetail, DBError>>>,
+        delete_question_response: Mutex<Option<Result<(), DBError>>>,
+        undelete_question_response: Mutex<Option<Result<(), DBError>>>,
+        get_questions_response: Mutex<Option<Result<Page<QuestionDetail>, DBError>>>,
+        get_question_response: Mutex<Option<Result<Option<QuestionWithAnswers>, DBError>>>,
+        update_question_response: Mutex<Option<Result<QuestionDetail, DBError>>>,
+        search_response: Mutex<Option<Result<Vec<QuestionSearchResult>, DBError>>>,
+        get_question_author_response: Mutex<Option<Result<Option<String>, DBError>>>,
+        vote_question_response: Mutex<Option<Result<i64, DBError>>>,
+        retract_question_vote_response: Mutex<Option<Result<i64, DBError>>>,
+        set_accepted_answer_response: Mutex<Option<Result<(), DBError>>>,
+        get_tags_response: Mutex<Option<Result<Vec<TagDetail>, DBError>>>,
+        get_question_revisions_response: Mutex<Option<Result<Vec<QuestionRevision>, DBError>>>,
+        get_question_revision_response: QueuedResponses<Option<QuestionRevision>>,
+        health_check_response: Mutex<Option<Result<MigrationStatus, DBError>>>,
+    }
+
+    impl QuestionsDaoMock {
+        pub fn new() -> Self {
+            QuestionsDaoMock {
+                create_question_response: Mutex::new(None),
+                delete_question_response: Mutex::new(None),
+                undelete_question_response: Mutex::new(None),
+                get_questions_response: Mutex::new(None),
+                get_question_response: Mutex::new(None),
+                update_question_response: Mutex::new(None),
+                search_response: Mutex::new(None),
+                get_question_author_response: Mutex::new(None),
+                vote_question_response: Mutex::new(None),
+                retract_question_vote_response: Mutex::new(None),
+                set_accepted_answer_response: Mutex::new(None),
+                get_tags_response: Mutex::new(None),
+                get_question_revisions_response: Mutex::new(None),
+                get_question_revision_response: Mutex::new(None),
+                health_check_response: Mutex::new(None),
+            }
+        }
+        pub fn mock_create_question(&mut self, response: Result<QuestionDetail, DBError>) {
+            self.create_question_response = Mutex::new(Some(response));
+        }
+        pub fn mock_delete_question(&mut self, response: Result<(), DBError>) {
+            self.delete_question_response = Mutex::new(Some(response));
+        }
+        pub fn mock_undelete_question(&mut self, response: Result<(), DBError>) {
+            self.undelete_question_response = Mutex::new(Some(response));
+        }
+        pub fn mock_get_questions(&mut self, response: Result<Page<QuestionDetail>, DBError>) {
+            self.get_questions_response = Mutex::new(Some(response));
+        }
+        pub fn mock_get_question(&mut self, response: Result<Option<QuestionWithAnswers>, DBError>) {
+            self.get_question_response = Mutex::new(Some(response));
+        }
+        pub fn mock_update_question(&mut self, response: Result<QuestionDetail, DBError>) {
+            self.update_question_response = Mutex::new(Some(response));
+        }
+        pub fn mock_search(&mut self, response: Result<Vec<QuestionSearchResult>, DBError>) {
+            self.search_response = Mutex::new(Some(response));
+        }
+        pub fn mock_get_question_author(&mut self, response: Result<Option<String>, DBError>) {
+            self.get_question_author_response = Mutex::new(Some(response));
+        }
+        pub fn mock_vote_question(&mut self, response: Result<i64, DBError>) {
+            self.vote_question_response = Mutex::new(Some(response));
+        }
+        pub fn mock_retract_question_vote(&mut self, response: Result<i64, DBError>) {
+            self.retract_question_vote_response = Mutex::new(Some(response));
+        }
+        pub fn mock_set_accepted_answer(&mut self, response: Result<(), DBError>) {
+            self.set_accepted_answer_response = Mutex::new(Some(response));
+        }
+        pub fn mock_get_tags(&mut self, response: Result<Vec<TagDetail>, DBError>) {
+            self.get_tags_response = Mutex::new(Some(response));
+        }
+        pub fn mock_get_question_revisions(&mut self, response: Result<Vec<QuestionRevision>, DBError>) {
+            self.get_question_revisions_response = Mutex::new(Some(response));
+        }
+        pub fn mock_get_question_revision(&mut self, responses: Vec<Result<Option<QuestionRevision>, DBError>>) {
+            self.get_question_revision_response = Mutex::new(Some(responses));
+        }
+        pub fn mock_health_check(&mut self, response: Result<MigrationStatus, DBError>) {
+            self.health_check_response = Mutex::new(Some(response));
+        }
+    }
+
+    #[async_trait]
+    impl QuestionsDao for QuestionsDaoMock {
+        async fn create_question(&self, _: Question, _: String) -> Result<QuestionDetail, DBError> {
+            self.create_question_response
+                .lock()
+                .await
+                .take()
+                .expect("create_question_response should not be None.")
+        }
+        async fn delete_question(&self, _: String, _: String) -> Result<(), DBError> {
+            self.delete_question_response
+                .lock()
+                .await
+                .take()
+                .expect("delete_question_response should not be None.")
+        }
+        async fn undelete_question(&self, _: String) -> Result<(), DBError> {
+            self.undelete_question_response
+                .lock()
+                .await
+                .take()
+                .expect("undelete_question_response should not be None.")
+        }
+        async fn get_questions(&self, _: PageRequest, _: Vec<String>, _: bool) -> Result<Page<QuestionDetail>, DBError> {
+            self.get_questions_response
+                .lock()
+                .await
+                .take()
+                .expect("get_questions_response should not be None.")
+        }
+        async fn get_question(&self, _: String, _: bool, _: bool) -> Result<Option<QuestionWithAnswers>, DBError> {
+            self.get_question_response
+                .lock()
+                .await
+                .take()
+                .expect("get_question_response should not be None.")
+        }
+        async fn update_question(&self, _: Question, _: String, _: String, _: Option<String>) -> Result<QuestionDetail, DBError> {
+            self.update_question_response
+                .lock()
+                .await
+                .take()
+                .expect("update_question_response should not be None.")
+        }
+        async fn search(&self, _: String, _: i64) -> Result<Vec<QuestionSearchResult>, DBError> {
+            self.search_response
+                .lock()
+                .await
+                .take()
+                .expect("search_response should not be None.")
+        }
+        async fn get_question_author(&self, _: String, _: bool) -> Result<Option<String>, DBError> {
+            self.get_question_author_response
+                .lock()
+                .await
+                .take()
+                .expect("get_question_author_response should not be None.")
+        }
+        async fn vote_question(&self, _: String, _: String, _: i16) -> Result<i64, DBError> {
+            self.vote_question_response
+                .lock()
+                .await
+                .take()
+                .expect("vote_question_response should not be None.")
+        }
+        async fn retract_question_vote(&self, _: String, _: String) -> Result<i64, DBError> {
+            self.retract_question_vote_response
+                .lock()
+                .await
+                .take()
+                .expect("retract_question_vote_response should not be None.")
+        }
+        async fn set_accepted_answer(&self, _: String, _: Option<String>) -> Result<(), DBError> {
+            self.set_accepted_answer_response
+                .lock()
+                .await
+                .take()
+                .expect("set_accepted_answer_response should not be None.")
+        }
+        async fn get_tags(&self) -> Result<Vec<TagDetail>, DBError> {
+            self.get_tags_response
+                .lock()
+                .await
+                .take()
+                .expect("get_tags_response should not be None.")
+        }
+        async fn get_question_revisions(&self, _: String) -> Result<Vec<QuestionRevision>, DBError> {
+            self.get_question_revisions_response
+                .lock()
+                .await
+                .take()
+                .expect("get_question_revisions_response should not be None.")
+        }
+        async fn get_question_revision(&self, _: String, _: String) -> Result<Option<QuestionRevision>, DBError> {
+            self.get_question_revision_response
+                .lock()
+                .await
+                .as_mut()
+                .filter(|responses| !responses.is_empty())
+                .expect("get_question_revision_response should not be None.")
+                .remove(0)
+        }
+        async fn health_check(&self) -> Result<MigrationStatus, DBError> {
+            self.health_check_response
+                .lock()
+                .await
+                .take()
+                .expect("health_check_response should not be None.")
+        }
+    }
+
+    struct AnswersDaoMock {
+        create_answer_response: Mutex<Option<Result<AnswerDetail, DBError>>>,
+        delete_answer_response: Mutex<Option<Result<(), DBError>>>,
+        undelete_answer_response: Mutex<Option<Result<(), DBError>>>,
+        get_answers_response: Mutex<Option<Result<Page<AnswerDetail>, DBError>>>,
+        update_answer_response: Mutex<Option<Result<AnswerDetail, DBError>>>,
+        get_answer_author_response: Mutex<Option<Result<Option<String>, DBError>>>,
+        get_answer_response: Mutex<Option<Result<Option<AnswerDetail>, DBError>>>,
+        vote_answer_response: Mutex<Option<Result<i64, DBError>>>,
+        retract_answer_vote_response: Mutex<Option<Result<i64, DBError>>>,
+        answer_belongs_to_question_response: Mutex<Option<Result<bool, DBError>>>,
+        get_answer_revisions_response: Mutex<Option<Result<Vec<AnswerRevision>, DBError>>>,
+        get_answer_revision_response: QueuedResponses<Option<AnswerRevision>>,
+        health_check_response: Mutex<Option<Result<MigrationStatus, DBError>>>,
+    }
+
+    impl AnswersDaoMock {
+        pub fn new() -> Self {
+            AnswersDaoMock {
+                create_answer_response: Mutex::new(None),
+                delete_answer_response: Mutex::new(None),
+                undelete_answer_response: Mutex::new(None),
+                get_answers_response: Mutex::new(None),
+                update_answer_response: Mutex::new(None),
+                get_answer_author_response: Mutex::new(None),
+                get_answer_response: Mutex::new(None),
+                vote_answer_response: Mutex::new(None),
+                retract_answer_vote_response: Mutex::new(None),
+                answer_belongs_to_question_response: Mutex::new(None),
+                get_answer_revisions_response: Mutex::new(None),
+                get_answer_revision_response: Mutex::new(None),
+                health_check_response: Mutex::new(None),
+            }
+        }
+        pub fn mock_create_answer(&mut self, response: Result<AnswerDetail, DBError>) {
+            self.create_answer_response = Mutex::new(Some(response));
+        }
+        pub fn mock_delete_answer(&mut self, response: Result<(), DBError>) {
+            self.delete_answer_response = Mutex::new(Some(response));
+        }
+        pub fn mock_undelete_answer(&mut self, response: Result<(), DBError>) {
+            self.undelete_answer_response = Mutex::new(Some(response));
+        }
+        pub fn mock_get_answers(&mut self, response: Result<Page<AnswerDetail>, DBError>) {
+            self.get_answers_response = Mutex::new(Some(response));
+        }
+        pub fn mock_update_answer(&mut self, response: Result<AnswerDetail, DBError>) {
+            self.update_answer_response = Mutex::new(Some(response));
+        }
+        pub fn mock_get_answer_author(&mut self, response: Result<Option<String>, DBError>) {
+            self.get_answer_author_response = Mutex::new(Some(response));
+        }
+        pub fn mock_get_answer(&mut self, response: Result<Option<AnswerDetail>, DBError>) {
+            self.get_answer_response = Mutex::new(Some(response));
+        }
+        pub fn mock_vote_answer(&mut self, response: Result<i64, DBError>) {
+            self.vote_answer_response = Mutex::new(Some(response));
+        }
+        pub fn mock_retract_answer_vote(&mut self, response: Result<i64, DBError>) {
+            self.retract_answer_vote_response = Mutex::new(Some(response));
+        }
+        pub fn mock_answer_belongs_to_question(&mut self, response: Result<bool, DBError>) {
+            self.answer_belongs_to_question_response = Mutex::new(Some(response));
+        }
+        pub fn mock_get_answer_revisions(&mut self, response: Result<Vec<AnswerRevision>, DBError>) {
+            self.get_answer_revisions_response = Mutex::new(Some(response));
+        }
+        pub fn mock_get_answer_revision(&mut self, responses: Vec<Result<Option<AnswerRevision>, DBError>>) {
+            self.get_answer_revision_response = Mutex::new(Some(responses));
+        }
+        pub fn mock_health_check(&mut self, response: Result<MigrationStatus, DBError>) {
+            self.health_check_response = Mutex::new(Some(response));
+        }
+    }
+
+    #[async_trait]
+    impl AnswersDao for AnswersDaoMock {
+        async fn create_answer(&self, _: Answer, _: String) -> Result<AnswerDetail, DBError> {
+            self.create_answer_response
+                .lock()
+                .await
+                .take()
+                .expect("create_answer_response should not be None.")
+        }
+        async fn delete_answer(&self, _: String, _: String) -> Result<(), DBError> {
+            self.delete_answer_response
+                .lock()
+                .await
+                .take()
+                .expect("delete_answer_response should not be None.")
+        }
+        async fn undelete_answer(&self, _: String) -> Result<(), DBError> {
+            self.undelete_answer_response
+                .lock()
+                .await
+                .take()
+                .expect("undelete_answer_response should not be None.")
+        }
+        async fn get_answers(&self, _: String, _: PageRequest, _: bool) -> Result<Page<AnswerDetail>, DBError> {
+            self.get_answers_response
+                .lock()
+                .await
+                .take()
+                .expect("get_answers_response should not be None.")
+        }
+        async fn update_answer(&self, _: Answer, _: String, _: String, _: Option<String>) -> Result<AnswerDetail, DBError> {
+            self.update_answer_response
+                .lock()
+                .await
+                .take()
+                .expect("update_answer_response should not be None.")
+        }
+        async fn get_answer(&self, _: String, _: bool) -> Result<Option<AnswerDetail>, DBError> {
+            self.get_answer_response
+                .lock()
+                .await
+                .take()
+                .expect("get_answer_response should not be None.")
+        }
+        async fn get_answer_author(&self, _: String, _: bool) -> Result<Option<String>, DBError> {
+            self.get_answer_author_response
+                .lock()
+                .await
+                .take()
+                .expect("get_answer_author_response should not be None.")
+        }
+        async fn vote_answer(&self, _: String, _: String, _: i16) -> Result<i64, DBError> {
+            self.vote_answer_response
+                .lock()
+                .await
+                .take()
+                .expect("vote_answer_response should not be None.")
+        }
+        async fn retract_answer_vote(&self, _: String, _: String) -> Result<i64, DBError> {
+            self.retract_answer_vote_response
+                .lock()
+                .await
+                .take()
+                .expect("retract_answer_vote_response should not be None.")
+        }
+        async fn answer_belongs_to_question(&self, _: String, _: String) -> Result<bool, DBError> {
+            self.answer_belongs_to_question_response
+                .lock()
+                .await
+                .take()
+                .expect("answer_belongs_to_question_response should not be None.")
+        }
+        async fn get_answer_revisions(&self, _: String) -> Result<Vec<AnswerRevision>, DBError> {
+            self.get_answer_revisions_response
+                .lock()
+                .await
+                .take()
+                .expect("get_answer_revisions_response should not be None.")
+        }
+        async fn get_answer_revision(&self, _: String, _: String) -> Result<Option<AnswerRevision>, DBError> {
+            self.get_answer_revision_response
+                .lock()
+                .await
+                .as_mut()
+                .filter(|responses| !responses.is_empty())
+                .expect("get_answer_revision_response should not be None.")
+                .remove(0)
+        }
+        async fn health_check(&self) -> Result<MigrationStatus, DBError> {
+            self.health_check_response
+                .lock()
+                .await
+                .take()
+                .expect("health_check_response should not be None.")
+        }
+    }
+
+    struct CommentsDaoMock {
+        create_comment_response: Mutex<Option<Result<CommentDetail, DBError>>>,
+        delete_comment_response: Mutex<Option<Result<(), DBError>>>,
+        get_comments_response: Mutex<Option<Result<Page<CommentDetail>, DBError>>>,
+        update_comment_response: Mutex<Option<Result<CommentDetail, DBError>>>,
+        get_comment_author_response: Mutex<Option<Result<Option<String>, DBError>>>,
+    }
+
+    impl CommentsDaoMock {
+        pub fn new() -> Self {
+            CommentsDaoMock {
+                create_comment_response: Mutex::new(None),
+                delete_comment_response: Mutex::new(None),
+                get_comments_response: Mutex::new(None),
+                update_comment_response: Mutex::new(None),
+                get_comment_author_response: Mutex::new(None),
+            }
+        }
+        pub fn mock_create_comment(&mut self, response: Result<CommentDetail, DBError>) {
+            self.create_comment_response = Mutex::new(Some(response));
+        }
+        pub fn mock_delete_comment(&mut self, response: Result<(), DBError>) {
+            self.delete_comment_response = Mutex::new(Some(response));
+        }
+        pub fn mock_get_comments(&mut self, response: Result<Page<CommentDetail>, DBError>) {
+            self.get_comments_response = Mutex::new(Some(response));
+        }
+        pub fn mock_update_comment(&mut self, response: Result<CommentDetail, DBError>) {
+            self.update_comment_response = Mutex::new(Some(response));
+        }
+        pub fn mock_get_comment_author(&mut self, response: Result<Option<String>, DBError>) {
+            self.get_comment_author_response = Mutex::new(Some(response));
+        }
+    }
+
+    #[async_trait]
+    impl CommentsDao for CommentsDaoMock {
+        async fn create_comment(&self, _: Comment, _: String) -> Result<CommentDetail, DBError> {
+            self.create_comment_response
+                .lock()
+                .await
+                .take()
+                .expect("create_comment_response should not be None.")
+        }
+        async fn delete_comment(&self, _: String) -> Result<(), DBError> {
+            self.delete_comment_response
+                .lock()
+                .await
+                .take()
+                .expect("delete_comment_response should not be None.")
+        }
+        async fn get_comments(&self, _: CommentParent, _: PageRequest, _: bool) -> Result<Page<CommentDetail>, DBError> {
+            self.get_comments_response
+                .lock()
+                .await
+                .take()
+                .expect("get_comments_response should not be None.")
+        }
+        async fn update_comment(&self, _: Comment, _: String) -> Result<CommentDetail, DBError> {
+            self.update_comment_response
+                .lock()
+                .await
+                .take()
+                .expect("update_comment_response should not be None.")
+        }
+        async fn get_comment_author(&self, _: String) -> Result<Option<String>, DBError> {
+            self.get_comment_author_response
+                .lock()
+                .await
+                .take()
+                .expect("get_comment_author_response should not be None.")
+        }
+    }
+
+    fn user(user_uuid: &str, role: Role) -> AuthenticatedUser {
+        AuthenticatedUser {
+            user_uuid: user_uuid.to_owned(),
+            role,
+            session_token_hash: "token hash".to_owned(),
+        }
+    }
+
+    fn stored_question(author_uuid: &str, deleted_by: Option<&str>) -> QuestionWithAnswers {
+        QuestionWithAnswers {
+            question: QuestionDetail {
+                question_uuid: "123".to_owned(),
+                title: "test title".to_owned(),
+                description: "test description".to_owned(),
+                author_uuid: Some(author_uuid.to_owned()),
+                accepted_answer_uuid: None,
+                tags: vec![],
+                score: 0,
+                created_at: "now".to_owned(),
+                deleted_at: deleted_by.map(|_| "now".to_owned()),
+                deleted_by: deleted_by.map(str::to_owned),
+            },
+            answers: None,
+        }
+    }
+
+    fn stored_answer(author_uuid: &str, deleted_by: Option<&str>) -> AnswerDetail {
+        AnswerDetail {
+            answer_uuid: "456".to_owned(),
+            question_uuid: "123".to_owned(),
+            content: "test content".to_owned(),
+            author_uuid: Some(author_uuid.to_owned()),
+            score: 0,
+            is_accepted: false,
+            created_at: "now".to_owned(),
+            deleted_at: deleted_by.map(|_| "now".to_owned()),
+            deleted_by: deleted_by.map(str::to_owned),
+        }
+    }
+
+    struct UsersDaoMock {
+        create_user_response: Mutex<Option<Result<UserDetail, DBError>>>,
+        get_user_credentials_response: Mutex<Option<Result<Option<UserCredentials>, DBError>>>,
+        create_session_response: Mutex<Option<Result<String, DBError>>>,
+        get_session_user_response: Mutex<Option<Result<Option<UserDetail>, DBError>>>,
+        delete_session_response: Mutex<Option<Result<(), DBError>>>,
+    }
+
+    impl UsersDaoMock {
+        pub fn new() -> Self {
+            UsersDaoMock {
+                create_user_response: Mutex::new(None),
+                get_user_credentials_response: Mutex::new(None),
+                create_session_response: Mutex::new(None),
+                get_session_user_response: Mutex::new(None),
+                delete_session_response: Mutex::new(None),
+            }
+        }
+        pub fn mock_create_user(&mut self, response: Result<UserDetail, DBError>) {
+            self.create_user_response = Mutex::new(Some(response));
+        }
+        pub fn mock_get_user_credentials(&mut self, response: Result<Option<UserCredentials>, DBError>) {
+            self.get_user_credentials_response = Mutex::new(Some(response));
+        }
+        pub fn mock_create_session(&mut self, response: Result<String, DBError>) {
+            self.create_session_response = Mutex::new(Some(response));
+        }
+        pub fn mock_delete_session(&mut self, response: Result<(), DBError>) {
+            self.delete_session_response = Mutex::new(Some(response));
+        }
+    }
+
+    #[async_trait]
+    impl UsersDao for UsersDaoMock {
+        async fn create_user(&self, _: String, _: String) -> Result<UserDetail, DBError> {
+            self.create_user_response
+                .lock()
+                .await
+                .take()
+                .expect("create_user_response should not be None.")
+        }
+        async fn get_user_credentials(&self, _: String) -> Result<Option<UserCredentials>, DBError> {
+            self.get_user_credentials_response
+                .lock()
+                .await
+                .take()
+                .expect("get_user_credentials_response should not be None.")
+        }
+        async fn create_session(&self, _: String, _: String, _: i64) -> Result<String, DBError> {
+            self.create_session_response
+                .lock()
+                .await
+                .take()
+                .expect("create_session_response should not be None.")
+        }
+        async fn get_session_user(&self, _: String) -> Result<Option<UserDetail>, DBError> {
+            self.get_session_user_response
+                .lock()
+                .await
+                .take()
+                .expect("get_session_user_response should not be None.")
+        }
+        async fn delete_session(&self, _: String) -> Result<(), DBError> {
+            self.delete_session_response
+                .lock()
+                .await
+                .take()
+                .expect("delete_session_response should not be None.")
+        }
+    }
+
+    #[tokio::test]
+    async fn register_should_return_user() {
+        let user_detail = UserDetail {
+            user_uuid: "789".to_owned(),
+            username: "test_user".to_owned(),
+            role: Role::User,
+            created_at: "now".to_owned(),
+        };
+
+        let mut users_dao = UsersDaoMock::new();
+
+        users_dao.mock_create_user(Ok(user_detail.clone()));
+
+        let users_dao: Box<dyn UsersDao + Send + Sync> = Box::new(users_dao);
+
+        let result = register(
+            Credentials {
+                username: "test_user".to_owned(),
+                password: "password123".to_owned(),
+            },
+            &users_dao,
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), user_detail);
+    }
+
+    #[tokio::test]
+    async fn register_should_reject_invalid_credentials() {
+        for (username, password) in [("ab", "password123"), ("test user", "password123"), ("test_user", "short")] {
+            let users_dao: Box<dyn UsersDao + Send + Sync> = Box::new(UsersDaoMock::new());
+
+            let result = register(
+                Credentials {
+                    username: username.to_owned(),
+                    password: password.to_owned(),
+                },
+                &users_dao,
+            )
+            .await;
+
+            assert!(
+                std::mem::discriminant(&result.unwrap_err())
+                    == std::mem::discriminant(&HandlerError::BadRequest("", "".to_owned()))
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn register_should_return_conflict_for_duplicate_username() {
+        let mut users_dao = UsersDaoMock::new();
+
+        users_dao.mock_create_user(Err(DBError::DuplicateUsername("test_user".to_owned())));
+
+        let users_dao: Box<dyn UsersDao + Send + Sync> = Box::new(users_dao);
+
+        let result = register(
+            Credentials {
+                username: "test_user".to_owned(),
+                password: "password123".to_owned(),
+            },
+            &users_dao,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::Conflict("", "".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn login_should_return_session() {
+        let mut users_dao = UsersDaoMock::new();
+
+        users_dao.mock_get_user_credentials(Ok(Some(UserCredentials {
+            user_uuid: "789".to_owned(),
+            password_hash: hash_password("password123").unwrap(),
+        })));
+        users_dao.mock_create_session(Ok("later".to_owned()));
+
+        let users_dao: Box<dyn UsersDao + Send + Sync> = Box::new(users_dao);
+
+        let result = login(
+            Credentials {
+                username: "test_user".to_owned(),
+                password: "password123".to_owned(),
+            },
+            &users_dao,
+        )
+        .await;
+
+        let session = result.unwrap();
+
+        assert_eq!(session.user_uuid, "789");
+        assert_eq!(session.expires_at, "later");
+        assert!(!session.token.is_empty());
+    }
+
+    #[tokio::test]
+    async fn login_should_reject_wrong_password() {
+        let mut users_dao = UsersDaoMock::new();
+
+        users_dao.mock_get_user_credentials(Ok(Some(UserCredentials {
+            user_uuid: "789".to_owned(),
+            password_hash: hash_password("password123").unwrap(),
+        })));
+
+        let users_dao: Box<dyn UsersDao + Send + Sync> = Box::new(users_dao);
+
+        let result = login(
+            Credentials {
+                username: "test_user".to_owned(),
+                password: "wrong password".to_owned(),
+            },
+            &users_dao,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::Unauthorized("".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn login_should_reject_unknown_user() {
+        let mut users_dao = UsersDaoMock::new();
+
+        users_dao.mock_get_user_credentials(Ok(None));
+
+        let users_dao: Box<dyn UsersDao + Send + Sync> = Box::new(users_dao);
+
+        let result = login(
+            Credentials {
+                username: "nobody".to_owned(),
+                password: "password123".to_owned(),
+            },
+            &users_dao,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::Unauthorized("".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn logout_should_succeed() {
+        let mut users_dao = UsersDaoMock::new();
+
+        users_dao.mock_delete_session(Ok(()));
+
+        let users_dao: Box<dyn UsersDao + Send + Sync> = Box::new(users_dao);
+
+        let result = logout(hash_session_token("token"), &users_dao).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn create_question_should_return_question() {
+        let question = Question {
+            title: "test title".to_owned(),
+            description: "test description".to_owned(),
+            tags: vec![],
+        };
+
+        let question_detail = QuestionDetail {
+            question_uuid: "123".to_owned(),
+            title: question.title.clone(),
+            description: question.description.clone(),
+            author_uuid: Some("789".to_owned()),
+            accepted_answer_uuid: None,
+            tags: vec![],
+            score: 0,
+            created_at: "now".to_owned(),
+            deleted_at: None,
+            deleted_by: None,
+        };
+
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_create_question(Ok(question_detail.clone()));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = create_question(question, "789".to_owned(), &questions_dao).await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), question_detail);
+    }
+
+    #[tokio::test]
+    async fn create_question_should_reject_invalid_fields_before_reaching_the_dao() {
+        let question = Question {
+            title: "  ".to_owned(),
+            description: "line\u{0}break".to_owned(),
+            tags: vec![],
+        };
+
+        // No response is mocked, the DAO panics if it gets called.
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(QuestionsDaoMock::new());
+
+        let result = create_question(question, "789".to_owned(), &questions_dao).await;
+
+        assert_eq!(
+            result,
+            Err(HandlerError::Validation(vec![
+                FieldError {
+                    field: "title".to_owned(),
+                    message: "must not be blank".to_owned(),
+                },
+                FieldError {
+                    field: "description".to_owned(),
+                    message: "must not contain control characters".to_owned(),
+                },
+            ]))
+        );
+    }
+
+    #[tokio::test]
+    async fn update_answer_should_reject_blank_content() {
+        let answer = Answer {
+            question_uuid: "123".to_owned(),
+            content: "\n\t".to_owned(),
+        };
+
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(AnswersDaoMock::new());
+
+        let result = update_answer(answer, "456".to_owned(), None, &user("789", Role::User), &answers_dao).await;
+
+        assert_eq!(
+            result,
+            Err(HandlerError::Validation(vec![FieldError {
+                field: "updated_entity.content".to_owned(),
+                message: "must not be blank".to_owned(),
+            }]))
+        );
+    }
+
+    #[tokio::test]
+    async fn create_question_should_return_error() {
+        let question = Question {
+            title: "test title".to_owned(),
+            description: "test description".to_owned(),
+            tags: vec![],
+        };
+
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_create_question(Err(DBError::Other("test".into())));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = create_question(question, "789".to_owned(), &questions_dao).await;
+
+        assert!(result.is_err());
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::InternalError("".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn read_questions_should_return_questions() {
+        let question_detail = QuestionDetail {
+            question_uuid: "123".to_owned(),
+            title: "test title".to_owned(),
+            description: "test description".to_owned(),
+            author_uuid: Some("789".to_owned()),
+            accepted_answer_uuid: None,
+            tags: vec![],
+            score: 0,
+            created_at: "now".to_owned(),
+            deleted_at: None,
+            deleted_by: None,
+        };
+
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        let page = Page {
+            items: vec![question_detail],
+            next_cursor: Some("cursor".to_owned()),
+        };
+
+        questions_dao.mock_get_questions(Ok(page.clone()));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = read_questions(PageRequest::default(), vec![], false, None, &questions_dao).await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), page);
+    }
+
+    #[tokio::test]
+    async fn read_questions_should_return_error() {
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_questions(Err(DBError::Other("test".into())));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = read_questions(PageRequest::default(), vec![], false, None, &questions_dao).await;
+
+        assert!(result.is_err());
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::InternalError("".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn read_question_should_return_question() {
+        let question = QuestionWithAnswers {
+            question: QuestionDetail {
+                question_uuid: "123".to_owned(),
+                title: "test title".to_owned(),
+                description: "test description".to_owned(),
+                author_uuid: Some("789".to_owned()),
+                accepted_answer_uuid: None,
+                tags: vec![],
+                score: 0,
+                created_at: "now".to_owned(),
+                deleted_at: None,
+                deleted_by: None,
+            },
+            answers: Some(vec![]),
+        };
+
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_question(Ok(Some(question.clone())));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = read_question("123".to_owned(), true, false, None, &questions_dao).await;
+
+        assert_eq!(result, Ok(Some(question)));
+    }
+
+    #[tokio::test]
+    async fn read_question_should_return_bad_request_for_invalid_uuid() {
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_question(Err(DBError::InvalidUUID("malformed".to_owned())));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = read_question("malformed".to_owned(), false, false, None, &questions_dao).await;
+
+        assert_eq!(result, Err(HandlerError::BadRequest("invalid_uuid", "malformed".to_owned())));
+    }
+
+    #[tokio::test]
+    async fn read_question_should_forbid_deleted_posts_to_regular_users() {
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(QuestionsDaoMock::new());
+
+        let result = read_question("123".to_owned(), false, true, Some(&user("789", Role::User)), &questions_dao).await;
+
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::Forbidden("".to_owned()))
+        );
+    }
+
+    #[test]
+    fn normalize_tags_should_lowercase_and_deduplicate() {
+        let tags = vec![
+            "Rust".to_owned(),
+            " rocket  framework ".to_owned(),
+            "rust".to_owned(),
+            "C#".to_owned(),
+        ];
+
+        let result = normalize_tags(tags);
+
+        assert_eq!(
+            result,
+            Ok(vec!["rust".to_owned(), "rocket-framework".to_owned(), "c#".to_owned()])
+        );
+    }
+
+    #[test]
+    fn normalize_tags_should_reject_invalid_tags() {
+        for tag in ["", "   ", "no/slashes", "ünïcode", &"a".repeat(MAX_TAG_LENGTH + 1)] {
+            let result = normalize_tags(vec![tag.to_owned()]);
+
+            assert!(
+                std::mem::discriminant(&result.unwrap_err())
+                    == std::mem::discriminant(&HandlerError::BadRequest("", "".to_owned()))
+            );
+        }
+    }
+
+    #[test]
+    fn normalize_tags_should_reject_too_many_tags() {
+        let tags = (0..=MAX_TAGS_PER_QUESTION).map(|i| format!("tag{}", i)).collect();
+
+        let result = normalize_tags(tags);
+
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::BadRequest("", "".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn create_question_should_reject_invalid_tags() {
+        let question = Question {
+            title: "test title".to_owned(),
+            description: "test description".to_owned(),
+            tags: vec!["not a/tag".to_owned()],
+        };
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(QuestionsDaoMock::new());
+
+        let result = create_question(question, "789".to_owned(), &questions_dao).await;
+
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::BadRequest("", "".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn read_questions_should_reject_invalid_tag_filter() {
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(QuestionsDaoMock::new());
+
+        let result = read_questions(PageRequest::default(), vec!["bad/tag".to_owned()], false, None, &questions_dao).await;
+
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::BadRequest("", "".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn read_tags_should_return_tags() {
+        let tags = vec![TagDetail {
+            name: "rust".to_owned(),
+            question_count: 2,
+        }];
+
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_tags(Ok(tags.clone()));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = read_tags(&questions_dao).await;
+
+        assert_eq!(result, Ok(tags));
+    }
+
+    #[tokio::test]
+    async fn read_tags_should_return_error() {
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_tags(Err(DBError::Other("test".into())));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = read_tags(&questions_dao).await;
+
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::InternalError("".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn search_questions_should_return_results() {
+        let search_result = QuestionSearchResult {
+            question: QuestionDetail {
+                question_uuid: "123".to_owned(),
+                title: "test title".to_owned(),
+                description: "test description".to_owned(),
+                author_uuid: Some("789".to_owned()),
+                accepted_answer_uuid: None,
+                tags: vec![],
+                score: 0,
+                created_at: "now".to_owned(),
+                deleted_at: None,
+                deleted_by: None,
+            },
+            rank: 0.5,
+            snippet: "<mark>test</mark> title".to_owned(),
+            answer_snippet: None,
+        };
+
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_search(Ok(vec![search_result.clone()]));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = search_questions("test".to_owned(), None, &questions_dao).await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), vec![search_result]);
+    }
+
+    #[tokio::test]
+    async fn search_questions_should_reject_empty_query() {
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(QuestionsDaoMock::new());
+
+        for query in ["   ", "!!!"] {
+            let result = search_questions(query.to_owned(), None, &questions_dao).await;
+
+            assert_eq!(result, Err(HandlerError::BadRequest("empty_query", "Search query must contain a word".to_owned())));
+        }
+    }
+
+    #[tokio::test]
+    async fn delete_question_should_succeed() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_owned(),
+        };
+
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_question_author(Ok(Some("789".to_owned())));
+        questions_dao.mock_delete_question(Ok(()));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = delete_question(question_id, &user("789", Role::User), &questions_dao).await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), ());
+    }
+
+    #[tokio::test]
+    async fn delete_question_should_return_error() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_owned(),
+        };
+
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_question_author(Ok(Some("789".to_owned())));
+        questions_dao.mock_delete_question(Err(DBError::Other("test".into())));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = delete_question(question_id, &user("789", Role::User), &questions_dao).await;
+
+        assert!(result.is_err());
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::InternalError("".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_question_should_return_not_found_for_missing_question() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_owned(),
+        };
+
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_question_author(Err(DBError::not_found("question_not_found", "Question not found")));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = delete_question(question_id, &user("789", Role::User), &questions_dao).await;
+
+        assert_eq!(result, Err(HandlerError::NotFound("question_not_found", "Question not found".to_owned())));
+    }
+
+    #[tokio::test]
+    async fn delete_question_should_return_unavailable_when_database_is_down() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_owned(),
+        };
+
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_question_author(Ok(Some("789".to_owned())));
+        questions_dao.mock_delete_question(Err(DBError::Unavailable { source: sqlx::Error::PoolTimedOut }));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = delete_question(question_id, &user("789", Role::User), &questions_dao).await;
+
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::Unavailable("".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn create_answer_should_return_answer() {
+        let answer = Answer {
+            question_uuid: "123".to_owned(),
+            content: "test content".to_owned(),
+        };
+
+        let answer_detail = AnswerDetail {
+            answer_uuid: "456".to_owned(),
+            question_uuid: answer.question_uuid.clone(),
+            content: answer.content.clone(),
+            author_uuid: Some("789".to_owned()),
+            score: 0,
+            is_accepted: false,
+            created_at: "now".to_owned(),
+            deleted_at: None,
+            deleted_by: None,
+        };
+
+        let mut answers_dao = AnswersDaoMock::new();
+
+        answers_dao.mock_create_answer(Ok(answer_detail.clone()));
+
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
+
+        let result = create_answer(answer, "789".to_owned(), &answers_dao).await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), answer_detail);
+    }
+
+    #[tokio::test]
+    async fn create_answer_should_return_bad_request_error() {
+        let answer = Answer {
+            question_uuid: "123".to_owned(),
+            content: "test content".to_owned(),
+        };
+
+        let mut answers_dao = AnswersDaoMock::new();
+
+        answers_dao.mock_create_answer(Err(DBError::InvalidUUID("test".to_owned())));
+
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
+
+        let result = create_answer(answer, "789".to_owned(), &answers_dao).await;
+
+        assert!(result.is_err());
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::BadRequest("", "".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn create_answer_should_return_internal_error() {
+        let answer = Answer {
+            question_uuid: "123".to_owned(),
+            content: "test content".to_owned(),
+        };
+
+        let mut answers_dao = AnswersDaoMock::new();
+
+        answers_dao.mock_create_answer(Err(DBError::Other(Box::new(std::io::Error::other(
+            "oh no!",
+        )))));
+
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
+
+        let result = create_answer(answer, "789".to_owned(), &answers_dao).await;
+
+        assert!(result.is_err());
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::InternalError("".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn read_answers_should_return_answers() {
+        let answer_detail = AnswerDetail {
+            answer_uuid: "456".to_owned(),
+            question_uuid: "123".to_owned(),
+            content: "test content".to_owned(),
+            author_uuid: Some("789".to_owned()),
+            score: 0,
+            is_accepted: false,
+            created_at: "now".to_owned(),
+            deleted_at: None,
+            deleted_by: None,
+        };
+
+        let question_id = QuestionId {
+            question_uuid: "123".to_owned(),
+        };
+
+        let mut answers_dao = AnswersDaoMock::new();
+
+        let page = Page {
+            items: vec![answer_detail],
+            next_cursor: None,
+        };
+
+        answers_dao.mock_get_answers(Ok(page.clone()));
+
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
+
+        let result = read_answers(question_id, PageRequest::default(), false, None, &answers_dao).await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), page);
+    }
+
+    #[tokio::test]
+    async fn read_answers_should_return_error() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_owned(),
+        };
+
+        let mut answers_dao = AnswersDaoMock::new();
+
+        answers_dao.mock_get_answers(Err(DBError::Other("test".into())));
+
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
+
+        let result = read_answers(question_id, PageRequest::default(), false, None, &answers_dao).await;
+
+        assert!(result.is_err());
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::InternalError("".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_answer_should_succeed() {
+        let answer_id = AnswerId {
+            answer_uuid: "123".to_owned(),
+        };
+
+        let mut answers_dao = AnswersDaoMock::new();
+
+        answers_dao.mock_get_answer_author(Ok(Some("789".to_owned())));
+        answers_dao.mock_delete_answer(Ok(()));
+
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
+
+        let result = delete_answer(answer_id, &user("789", Role::User), &answers_dao).await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), ());
+    }
+
+    #[tokio::test]
+    async fn delete_answer_should_return_error() {
+        let answer_id = AnswerId {
+            answer_uuid: "123".to_owned(),
+        };
+
+        let mut answers_dao = AnswersDaoMock::new();
+
+        answers_dao.mock_get_answer_author(Ok(Some("789".to_owned())));
+        answers_dao.mock_delete_answer(Err(DBError::Other("test".into())));
+
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
+
+        let result = delete_answer(answer_id, &user("789", Role::User), &answers_dao).await;
+
+        assert!(result.is_err());
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::InternalError("".to_owned()))
+        );
+    }
+
+    #[test]
+    fn page_request_should_use_defaults() {
+        let result = page_request(None, None);
+
+        assert_eq!(result, Ok(PageRequest::default()));
+    }
+
+    #[test]
+    fn page_request_should_decode_cursor() {
+        let created_at = OffsetDateTime::from_unix_timestamp_nanos(1_700_000_000_123_456_000).unwrap();
+        let cursor = Cursor {
+            created_at: PrimitiveDateTime::new(created_at.date(), created_at.time()),
+            uuid: Uuid::parse_str("a22abcd2-22ab-2222-a22b-2abc2a2b22cc").unwrap(),
+        };
+
+        let result = page_request(Some(5), Some(cursor.encode()));
+
+        assert_eq!(
+            result,
+            Ok(PageRequest {
+                limit: 5,
+                cursor: Some(cursor),
+            })
+        );
+    }
+
+    #[test]
+    fn page_request_should_reject_out_of_range_limit() {
+        for limit in [0, -1, PageRequest::MAX_LIMIT + 1] {
+            let result = page_request(Some(limit), None);
+
+            assert!(
+                std::mem::discriminant(&result.unwrap_err())
+                    == std::mem::discriminant(&HandlerError::BadRequest("", "".to_owned()))
+            );
+        }
+    }
+
+    #[test]
+    fn page_request_should_reject_malformed_cursor() {
+        let result = page_request(None, Some("not a cursor".to_owned()));
+
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::BadRequest("", "".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn update_question_should_return_question() {
+        let question = Question {
+            title: "new title".to_owned(),
+            description: "new description".to_owned(),
+            tags: vec![],
+        };
+
+        let question_detail = QuestionDetail {
+            question_uuid: "123".to_owned(),
+            title: question.title.clone(),
+            description: question.description.clone(),
+            author_uuid: Some("789".to_owned()),
+            accepted_answer_uuid: None,
+            tags: vec![],
+            score: 0,
+            created_at: "now".to_owned(),
+            deleted_at: None,
+            deleted_by: None,
+        };
+
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_question_author(Ok(Some("789".to_owned())));
+        questions_dao.mock_update_question(Ok(question_detail.clone()));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = update_question(question, "123".to_owned(), None, &user("789", Role::User), &questions_dao).await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), question_detail);
+    }
+
+    #[tokio::test]
+    async fn update_answer_should_return_error() {
+        let answer = Answer {
+            question_uuid: "123".to_owned(),
+            content: "new content".to_owned(),
+        };
+
+        let mut answers_dao = AnswersDaoMock::new();
+
+        answers_dao.mock_get_answer_author(Ok(Some("789".to_owned())));
+        answers_dao.mock_update_answer(Err(DBError::Other("test".into())));
+
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
+
+        let result = update_answer(answer, "456".to_owned(), None, &user("789", Role::User), &answers_dao).await;
+
+        assert!(result.is_err());
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::InternalError("".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_question_should_forbid_non_author() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_owned(),
+        };
+
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_question_author(Ok(Some("789".to_owned())));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = delete_question(question_id, &user("000", Role::User), &questions_dao).await;
+
+        assert!(result.is_err());
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::Forbidden("".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_question_should_allow_moderator() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_owned(),
+        };
+
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_question_author(Ok(Some("789".to_owned())));
+        questions_dao.mock_delete_question(Ok(()));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = delete_question(question_id, &user("000", Role::Moderator), &questions_dao).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn update_question_should_forbid_authorless_question_for_users() {
+        let question = Question {
+            title: "new title".to_owned(),
+            description: "new description".to_owned(),
+            tags: vec![],
+        };
+
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_question_author(Ok(None));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = update_question(question, "123".to_owned(), None, &user("789", Role::User), &questions_dao).await;
+
+        assert!(result.is_err());
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::Forbidden("".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn update_question_should_return_bad_request_for_malformed_uuid() {
+        let question = Question {
+            title: "new title".to_owned(),
+            description: "new description".to_owned(),
+            tags: vec![],
+        };
+
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_question_author(Err(DBError::InvalidUUID("malformed".to_owned())));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = update_question(question, "malformed".to_owned(), None, &user("789", Role::User), &questions_dao).await;
+
+        assert!(result.is_err());
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::BadRequest("", "".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn update_answer_should_forbid_non_author() {
+        let answer = Answer {
+            question_uuid: "123".to_owned(),
+            content: "new content".to_owned(),
+        };
+
+        let mut answers_dao = AnswersDaoMock::new();
+
+        answers_dao.mock_get_answer_author(Ok(Some("789".to_owned())));
+
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
+
+        let result = update_answer(answer, "456".to_owned(), None, &user("000", Role::User), &answers_dao).await;
+
+        assert!(result.is_err());
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::Forbidden("".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_answer_should_allow_admin() {
+        let answer_id = AnswerId {
+            answer_uuid: "456".to_owned(),
+        };
+
+        let mut answers_dao = AnswersDaoMock::new();
+
+        answers_dao.mock_get_answer_author(Ok(Some("789".to_owned())));
+        answers_dao.mock_delete_answer(Ok(()));
+
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
+
+        let result = delete_answer(answer_id, &user("000", Role::Admin), &answers_dao).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn vote_question_should_return_score() {
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_question_author(Ok(Some("789".to_owned())));
+        questions_dao.mock_vote_question(Ok(3));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = vote_question(
+            "123".to_owned(),
+            Vote {
+                direction: VoteDirection::Up,
+            },
+            &user("000", Role::User),
+            &questions_dao,
+        )
+        .await;
+
+        assert_eq!(result, Ok(Score { score: 3 }));
+    }
+
+    #[tokio::test]
+    async fn vote_question_should_forbid_voting_on_own_question() {
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_question_author(Ok(Some("789".to_owned())));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = vote_question(
+            "123".to_owned(),
+            Vote {
+                direction: VoteDirection::Up,
+            },
+            &user("789", Role::Admin),
+            &questions_dao,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::Forbidden("".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn retract_question_vote_should_return_score() {
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_retract_question_vote(Ok(0));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = retract_question_vote("123".to_owned(), &user("000", Role::User), &questions_dao).await;
+
+        assert_eq!(result, Ok(Score { score: 0 }));
+    }
+
+    #[tokio::test]
+    async fn vote_answer_should_return_bad_request_for_unknown_answer() {
+        let mut answers_dao = AnswersDaoMock::new();
+
+        answers_dao.mock_get_answer_author(Ok(None));
+        answers_dao.mock_vote_answer(Err(DBError::InvalidUUID("456".to_owned())));
+
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
+
+        let result = vote_answer(
+            "456".to_owned(),
+            Vote {
+                direction: VoteDirection::Down,
+            },
+            &user("000", Role::User),
+            &answers_dao,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::BadRequest("", "".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn retract_answer_vote_should_return_error() {
+        let mut answers_dao = AnswersDaoMock::new();
+
+        answers_dao.mock_retract_answer_vote(Err(DBError::Other("oh no!".into())));
+
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
+
+        let result = retract_answer_vote("456".to_owned(), &user("000", Role::User), &answers_dao).await;
+
+        assert!(result.is_err());
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::InternalError("".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn accept_answer_should_succeed() {
+        let mut questions_dao = QuestionsDaoMock::new();
+        let mut answers_dao = AnswersDaoMock::new();
+
+        questions_dao.mock_get_question_author(Ok(Some("789".to_owned())));
+        questions_dao.mock_set_accepted_answer(Ok(()));
+        answers_dao.mock_answer_belongs_to_question(Ok(true));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
+
+        let result = accept_answer(
+            "123".to_owned(),
+            AnswerId {
+                answer_uuid: "456".to_owned(),
+            },
+            &user("789", Role::User),
+            &questions_dao,
+            &answers_dao,
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn accept_answer_should_reject_answer_of_other_question() {
+        let mut questions_dao = QuestionsDaoMock::new();
+        let mut answers_dao = AnswersDaoMock::new();
+
+        questions_dao.mock_get_question_author(Ok(Some("789".to_owned())));
+        answers_dao.mock_answer_belongs_to_question(Ok(false));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
+
+        let result = accept_answer(
+            "123".to_owned(),
+            AnswerId {
+                answer_uuid: "456".to_owned(),
+            },
+            &user("789", Role::User),
+            &questions_dao,
+            &answers_dao,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::BadRequest("", "".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn accept_answer_should_forbid_moderators_who_are_not_the_author() {
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_question_author(Ok(Some("789".to_owned())));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(AnswersDaoMock::new());
+
+        let result = accept_answer(
+            "123".to_owned(),
+            AnswerId {
+                answer_uuid: "456".to_owned(),
+            },
+            &user("000", Role::Moderator),
+            &questions_dao,
+            &answers_dao,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::Forbidden("".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn unaccept_answer_should_succeed() {
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_question_author(Ok(Some("789".to_owned())));
+        questions_dao.mock_set_accepted_answer(Ok(()));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = unaccept_answer("123".to_owned(), &user("789", Role::User), &questions_dao).await;
+
+        assert!(result.is_ok());
+    }
+
+    fn comment_detail() -> CommentDetail {
+        CommentDetail {
+            comment_uuid: "321".to_owned(),
+            question_uuid: Some("123".to_owned()),
+            answer_uuid: None,
+            content: "test content".to_owned(),
+            author_uuid: Some("789".to_owned()),
+            created_at: "now".to_owned(),
+        }
+    }
+
+    #[tokio::test]
+    async fn create_comment_should_return_comment() {
+        let comment = Comment {
+            parent: CommentParent {
+                question_uuid: Some("123".to_owned()),
+                answer_uuid: None,
+            },
+            content: "test content".to_owned(),
+        };
+
+        let mut comments_dao = CommentsDaoMock::new();
+
+        comments_dao.mock_create_comment(Ok(comment_detail()));
+
+        let comments_dao: Box<dyn CommentsDao + Send + Sync> = Box::new(comments_dao);
+
+        let result = create_comment(comment, "789".to_owned(), &comments_dao).await;
+
+        assert_eq!(result, Ok(comment_detail()));
+    }
+
+    #[tokio::test]
+    async fn create_comment_should_require_exactly_one_parent() {
+        let comments_dao: Box<dyn CommentsDao + Send + Sync> = Box::new(CommentsDaoMock::new());
+
+        for parent in [
+            CommentParent::default(),
+            CommentParent {
+                question_uuid: Some("123".to_owned()),
+                answer_uuid: Some("456".to_owned()),
+            },
+        ] {
+            let comment = Comment {
+                parent,
+                content: "test content".to_owned(),
+            };
+
+            let result = create_comment(comment, "789".to_owned(), &comments_dao).await;
+
+            assert!(
+                std::mem::discriminant(&result.unwrap_err())
+                    == std::mem::discriminant(&HandlerError::BadRequest("", "".to_owned()))
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn create_comment_should_return_bad_request_for_unknown_parent() {
+        let comment = Comment {
+            parent: CommentParent {
+                question_uuid: None,
+                answer_uuid: Some("456".to_owned()),
+            },
+            content: "test content".to_owned(),
+        };
+
+        let mut comments_dao = CommentsDaoMock::new();
+
+        comments_dao.mock_create_comment(Err(DBError::InvalidUUID("456".to_owned())));
+
+        let comments_dao: Box<dyn CommentsDao + Send + Sync> = Box::new(comments_dao);
+
+        let result = create_comment(comment, "789".to_owned(), &comments_dao).await;
+
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::BadRequest("", "".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn read_comments_should_return_comments() {
+        let page = Page {
+            items: vec![comment_detail()],
+            next_cursor: None,
+        };
+
+        let mut comments_dao = CommentsDaoMock::new();
+
+        comments_dao.mock_get_comments(Ok(page.clone()));
+
+        let comments_dao: Box<dyn CommentsDao + Send + Sync> = Box::new(comments_dao);
+
+        let parent = CommentParent {
+            question_uuid: Some("123".to_owned()),
+            answer_uuid: None,
+        };
+
+        let result = read_comments(parent, PageRequest::default(), None, &comments_dao).await;
+
+        assert_eq!(result, Ok(page));
+    }
+
+    #[tokio::test]
+    async fn update_comment_should_return_comment() {
+        let mut comments_dao = CommentsDaoMock::new();
+
+        comments_dao.mock_get_comment_author(Ok(Some("789".to_owned())));
+        comments_dao.mock_update_comment(Ok(comment_detail()));
+
+        let comments_dao: Box<dyn CommentsDao + Send + Sync> = Box::new(comments_dao);
+
+        let comment = Comment {
+            parent: CommentParent::default(),
+            content: "test content".to_owned(),
+        };
+
+        let result = update_comment(comment, "321".to_owned(), &user("789", Role::User), &comments_dao).await;
+
+        assert_eq!(result, Ok(comment_detail()));
+    }
+
+    #[tokio::test]
+    async fn delete_comment_should_forbid_non_author() {
+        let mut comments_dao = CommentsDaoMock::new();
+
+        comments_dao.mock_get_comment_author(Ok(Some("789".to_owned())));
+
+        let comments_dao: Box<dyn CommentsDao + Send + Sync> = Box::new(comments_dao);
+
+        let result = delete_comment(
+            CommentId {
+                comment_uuid: "321".to_owned(),
+            },
+            &user("000", Role::User),
+            &comments_dao,
+        )
+        .await;
+
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::Forbidden("".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_comment_should_allow_moderator() {
+        let mut comments_dao = CommentsDaoMock::new();
+
+        comments_dao.mock_get_comment_author(Ok(Some("789".to_owned())));
+        comments_dao.mock_delete_comment(Ok(()));
+
+        let comments_dao: Box<dyn CommentsDao + Send + Sync> = Box::new(comments_dao);
+
+        let result = delete_comment(
+            CommentId {
+                comment_uuid: "321".to_owned(),
+            },
+            &user("000", Role::Moderator),
+            &comments_dao,
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+
+    fn question_revision(revision_uuid: &str, title: &str, tags: &[&str]) -> QuestionRevision {
+        QuestionRevision {
+            revision_uuid: revision_uuid.to_owned(),
+            question_uuid: "123".to_owned(),
+            title: title.to_owned(),
+            description: "test description".to_owned(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            editor_uuid: Some("789".to_owned()),
+            edit_summary: None,
+            created_at: "now".to_owned(),
+        }
+    }
+
+    fn answer_revision(revision_uuid: &str, content: &str) -> AnswerRevision {
+        AnswerRevision {
+            revision_uuid: revision_uuid.to_owned(),
+            answer_uuid: "456".to_owned(),
+            question_uuid: "123".to_owned(),
+            content: content.to_owned(),
+            editor_uuid: Some("789".to_owned()),
+            edit_summary: None,
+            created_at: "now".to_owned(),
+        }
+    }
+
+    #[tokio::test]
+    async fn update_question_should_reject_long_edit_summary() {
+        let question = Question {
+            title: "test title".to_owned(),
+            description: "test description".to_owned(),
+            tags: vec![],
+        };
+
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_question_author(Ok(Some("789".to_owned())));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = update_question(
+            question,
+            "123".to_owned(),
+            Some("a".repeat(EDIT_SUMMARY.max + 1)),
+            &user("789", Role::User),
+            &questions_dao,
+        )
+        .await;
+
+        assert_eq!(
+            result,
+            Err(HandlerError::Validation(vec![FieldError {
+                field: "edit_summary".to_owned(),
+                message: "must be at most 255 characters long".to_owned(),
+            }]))
+        );
+    }
+
+    #[tokio::test]
+    async fn read_question_revisions_should_return_revisions() {
+        let revisions = vec![question_revision("r1", "old title", &[])];
+
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_question(Ok(Some(stored_question("789", None))));
+        questions_dao.mock_get_question_revisions(Ok(revisions.clone()));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = read_question_revisions("123".to_owned(), None, &questions_dao).await;
+
+        assert_eq!(result, Ok(revisions));
+    }
+
+    #[tokio::test]
+    async fn read_question_revisions_should_return_not_found_for_hidden_question() {
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_question(Ok(None));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
 
-    #[tokio::test]
-    async fn create_question_should_return_question() {
-        let question = Question {
-            title: "test title".to_owned(),
-            description: "test description".to_owned(),
-        };
+        let result = read_question_revisions("123".to_owned(), Some(&user("789", Role::User)), &questions_dao).await;
 
-        let question_detail = QuestionDetail {
-            question_uuid: "123".to_owned(),
-            title: question.title.clone(),
-            description: question.description.clone(),
-            created_at: "now".to_owned(),
-        };
+        assert_eq!(result, Err(HandlerError::NotFound("question_not_found", "Question not found".to_owned())));
+    }
 
+    #[tokio::test]
+    async fn diff_question_revisions_should_return_word_changes() {
         let mut questions_dao = QuestionsDaoMock::new();
 
-        questions_dao.mock_create_question(Ok(question_detail.clone()));
+        questions_dao.mock_get_question(Ok(Some(stored_question("789", None))));
+        questions_dao.mock_get_question_revision(vec![
+            Ok(Some(question_revision("r1", "old title", &["rust"]))),
+            Ok(Some(question_revision("r2", "new title", &["rust"]))),
+        ]);
 
         let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
 
-        let result = create_question(question, &questions_dao).await;
+        let result = diff_question_revisions("123".to_owned(), "r1".to_owned(), "r2".to_owned(), None, &questions_dao)
+            .await
+            .unwrap();
 
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), question_detail);
+        let title = result.fields.iter().find(|field| field.field == "title").unwrap();
+
+        assert_eq!(
+            title.changes,
+            vec![
+                DiffChange { op: DiffOp::Delete, value: "old".to_owned() },
+                DiffChange { op: DiffOp::Insert, value: "new".to_owned() },
+                DiffChange { op: DiffOp::Equal, value: " title".to_owned() },
+            ]
+        );
+
+        let tags = result.fields.iter().find(|field| field.field == "tags").unwrap();
+
+        assert!(tags.changes.iter().all(|change| change.op == DiffOp::Equal));
     }
 
     #[tokio::test]
-    async fn create_question_should_return_error() {
-        let question = Question {
-            title: "test title".to_owned(),
-            description: "test description".to_owned(),
-        };
-
+    async fn diff_question_revisions_should_reject_unknown_revision() {
         let mut questions_dao = QuestionsDaoMock::new();
 
-        questions_dao.mock_create_question(Err(DBError::InvalidUUID("test".to_owned())));
+        questions_dao.mock_get_question(Ok(Some(stored_question("789", None))));
+        questions_dao.mock_get_question_revision(vec![Ok(None)]);
 
         let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
 
-        let result = create_question(question, &questions_dao).await;
+        let result = diff_question_revisions("123".to_owned(), "r1".to_owned(), "r2".to_owned(), None, &questions_dao).await;
 
-        assert!(result.is_err());
-        assert!(
-            std::mem::discriminant(&result.unwrap_err())
-                == std::mem::discriminant(&HandlerError::InternalError("".to_owned()))
+        assert_eq!(
+            result,
+            Err(HandlerError::NotFound("revision_not_found", "Revision r1 does not belong to question 123".to_owned()))
         );
     }
 
     #[tokio::test]
-    async fn read_questions_should_return_questions() {
+    async fn rollback_question_should_return_question() {
         let question_detail = QuestionDetail {
             question_uuid: "123".to_owned(),
-            title: "test title".to_owned(),
+            title: "old title".to_owned(),
             description: "test description".to_owned(),
+            author_uuid: Some("789".to_owned()),
+            accepted_answer_uuid: None,
+            tags: vec![],
+            score: 0,
             created_at: "now".to_owned(),
+            deleted_at: None,
+            deleted_by: None,
         };
 
         let mut questions_dao = QuestionsDaoMock::new();
 
-        questions_dao.mock_get_questions(Ok(vec![question_detail.clone()]));
+        questions_dao.mock_get_question_author(Ok(Some("789".to_owned())));
+        questions_dao.mock_get_question_revision(vec![Ok(Some(question_revision("r1", "old title", &[])))]);
+        questions_dao.mock_update_question(Ok(question_detail.clone()));
 
         let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
 
-        let result = read_questions(&questions_dao).await;
+        let result = rollback_question("123".to_owned(), "r1".to_owned(), &user("789", Role::User), &questions_dao).await;
 
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), vec![question_detail]);
+        assert_eq!(result, Ok(question_detail));
     }
 
     #[tokio::test]
-    async fn read_questions_should_return_error() {
+    async fn rollback_question_should_forbid_non_author() {
         let mut questions_dao = QuestionsDaoMock::new();
 
-        questions_dao.mock_get_questions(Err(DBError::InvalidUUID("test".to_owned())));
+        questions_dao.mock_get_question_author(Ok(Some("789".to_owned())));
 
         let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
 
-        let result = read_questions(&questions_dao).await;
+        let result = rollback_question("123".to_owned(), "r1".to_owned(), &user("000", Role::User), &questions_dao).await;
 
-        assert!(result.is_err());
         assert!(
             std::mem::discriminant(&result.unwrap_err())
-                == std::mem::discriminant(&HandlerError::InternalError("".to_owned()))
+                == std::mem::discriminant(&HandlerError::Forbidden("".to_owned()))
         );
     }
 
     #[tokio::test]
-    async fn delete_question_should_succeed() {
-        let question_id = QuestionId {
-            question_uuid: "123".to_owned(),
-        };
+    async fn read_answer_revisions_should_return_revisions() {
+        let revisions = vec![answer_revision("r1", "first answer")];
 
-        let mut questions_dao = QuestionsDaoMock::new();
+        let mut answers_dao = AnswersDaoMock::new();
 
-        questions_dao.mock_delete_question(Ok(()));
+        answers_dao.mock_get_answer(Ok(Some(stored_answer("789", None))));
+        answers_dao.mock_get_answer_revisions(Ok(revisions.clone()));
 
-        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
 
-        let result = delete_question(question_id, &questions_dao).await;
+        let result = read_answer_revisions("456".to_owned(), None, &answers_dao).await;
 
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), ());
+        assert_eq!(result, Ok(revisions));
     }
 
     #[tokio::test]
-    async fn delete_question_should_return_error() {
-        let question_id = QuestionId {
-            question_uuid: "123".to_owned(),
-        };
-
-        let mut questions_dao = QuestionsDaoMock::new();
+    async fn read_answer_revisions_should_return_error() {
+        let mut answers_dao = AnswersDaoMock::new();
 
-        questions_dao.mock_delete_question(Err(DBError::InvalidUUID("test".to_owned())));
+        answers_dao.mock_get_answer(Err(DBError::InvalidUUID("malformed".to_owned())));
 
-        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
 
-        let result = delete_question(question_id, &questions_dao).await;
+        let result = read_answer_revisions("malformed".to_owned(), None, &answers_dao).await;
 
-        assert!(result.is_err());
         assert!(
             std::mem::discriminant(&result.unwrap_err())
-                == std::mem::discriminant(&HandlerError::InternalError("".to_owned()))
+                == std::mem::discriminant(&HandlerError::BadRequest("", "".to_owned()))
         );
     }
 
     #[tokio::test]
-    async fn create_answer_should_return_answer() {
-        let answer = Answer {
-            question_uuid: "123".to_owned(),
-            content: "test content".to_owned(),
-        };
+    async fn diff_answer_revisions_should_return_changes() {
+        let mut answers_dao = AnswersDaoMock::new();
+
+        answers_dao.mock_get_answer(Ok(Some(stored_answer("789", None))));
+        answers_dao.mock_get_answer_revision(vec![
+            Ok(Some(answer_revision("r1", "first answer"))),
+            Ok(Some(answer_revision("r2", "first answer, edited"))),
+        ]);
+
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
+
+        let result = diff_answer_revisions("456".to_owned(), "r1".to_owned(), "r2".to_owned(), None, &answers_dao)
+            .await
+            .unwrap();
+
+        assert_eq!(result.fields.len(), 1);
+        assert!(result.fields[0]
+            .changes
+            .iter()
+            .any(|change| change.op == DiffOp::Insert && change.value.contains("edited")));
+    }
+
+    #[tokio::test]
+    async fn diff_answer_revisions_should_reject_unknown_revision() {
+        let mut answers_dao = AnswersDaoMock::new();
+
+        answers_dao.mock_get_answer(Ok(Some(stored_answer("789", None))));
+        answers_dao.mock_get_answer_revision(vec![Ok(Some(answer_revision("r1", "first answer"))), Ok(None)]);
+
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
+
+        let result = diff_answer_revisions("456".to_owned(), "r1".to_owned(), "r2".to_owned(), None, &answers_dao).await;
+
+        assert_eq!(
+            result,
+            Err(HandlerError::NotFound("revision_not_found", "Revision r2 does not belong to answer 456".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn diff_answer_revisions_should_return_not_found_for_hidden_answer() {
+        let mut answers_dao = AnswersDaoMock::new();
+
+        answers_dao.mock_get_answer(Ok(None));
+
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
+
+        let result = diff_answer_revisions("456".to_owned(), "r1".to_owned(), "r2".to_owned(), None, &answers_dao).await;
 
+        assert_eq!(result, Err(HandlerError::NotFound("answer_not_found", "Answer not found".to_owned())));
+    }
+
+    #[tokio::test]
+    async fn rollback_answer_should_return_answer() {
         let answer_detail = AnswerDetail {
             answer_uuid: "456".to_owned(),
-            question_uuid: answer.question_uuid.clone(),
-            content: answer.content.clone(),
+            question_uuid: "123".to_owned(),
+            content: "first answer".to_owned(),
+            author_uuid: Some("789".to_owned()),
+            score: 0,
+            is_accepted: false,
             created_at: "now".to_owned(),
+            deleted_at: None,
+            deleted_by: None,
         };
 
         let mut answers_dao = AnswersDaoMock::new();
 
-        answers_dao.mock_create_answer(Ok(answer_detail.clone()));
+        answers_dao.mock_get_answer_author(Ok(Some("789".to_owned())));
+        answers_dao.mock_get_answer_revision(vec![Ok(Some(answer_revision("r1", "first answer")))]);
+        answers_dao.mock_update_answer(Ok(answer_detail.clone()));
 
         let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
 
-        let result = create_answer(answer, &answers_dao).await;
+        let result = rollback_answer("456".to_owned(), "r1".to_owned(), &user("789", Role::User), &answers_dao).await;
 
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), answer_detail);
+        assert_eq!(result, Ok(answer_detail));
     }
 
     #[tokio::test]
-    async fn create_answer_should_return_bad_request_error() {
-        let answer = Answer {
-            question_uuid: "123".to_owned(),
-            content: "test content".to_owned(),
-        };
-
+    async fn rollback_answer_should_reject_unknown_revision() {
         let mut answers_dao = AnswersDaoMock::new();
 
-        answers_dao.mock_create_answer(Err(DBError::InvalidUUID("test".to_owned())));
+        answers_dao.mock_get_answer_author(Ok(Some("789".to_owned())));
+        answers_dao.mock_get_answer_revision(vec![Ok(None)]);
 
         let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
 
-        let result = create_answer(answer, &answers_dao).await;
+        let result = rollback_answer("456".to_owned(), "r1".to_owned(), &user("789", Role::User), &answers_dao).await;
 
-        assert!(result.is_err());
-        assert!(
-            std::mem::discriminant(&result.unwrap_err())
-                == std::mem::discriminant(&HandlerError::BadRequest("".to_owned()))
+        assert_eq!(
+            result,
+            Err(HandlerError::NotFound("revision_not_found", "Revision r1 does not belong to answer 456".to_owned()))
         );
     }
 
     #[tokio::test]
-    async fn create_answer_should_return_internal_error() {
-        let answer = Answer {
-            question_uuid: "123".to_owned(),
-            content: "test content".to_owned(),
+    async fn read_questions_should_forbid_deleted_posts_to_regular_users() {
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(QuestionsDaoMock::new());
+
+        for viewer in [None, Some(user("789", Role::User))] {
+            let result = read_questions(PageRequest::default(), vec![], true, viewer.as_ref(), &questions_dao).await;
+
+            assert!(
+                std::mem::discriminant(&result.unwrap_err())
+                    == std::mem::discriminant(&HandlerError::Forbidden("".to_owned()))
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn read_questions_should_include_deleted_posts_for_moderators() {
+        let page = Page {
+            items: vec![],
+            next_cursor: None,
         };
 
-        let mut answers_dao = AnswersDaoMock::new();
+        let mut questions_dao = QuestionsDaoMock::new();
 
-        answers_dao.mock_create_answer(Err(DBError::Other(Box::new(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            "oh no!",
-        )))));
+        questions_dao.mock_get_questions(Ok(page.clone()));
 
-        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
 
-        let result = create_answer(answer, &answers_dao).await;
+        let moderator = user("000", Role::Moderator);
+
+        let result = read_questions(PageRequest::default(), vec![], true, Some(&moderator), &questions_dao).await;
+
+        assert_eq!(result, Ok(page));
+    }
+
+    #[tokio::test]
+    async fn undelete_question_should_succeed() {
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_question(Ok(Some(stored_question("789", Some("789")))));
+        questions_dao.mock_undelete_question(Ok(()));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = undelete_question("123".to_owned(), &user("789", Role::User), &questions_dao).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn undelete_question_should_forbid_non_author() {
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_question(Ok(Some(stored_question("789", Some("789")))));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = undelete_question("123".to_owned(), &user("000", Role::User), &questions_dao).await;
 
-        assert!(result.is_err());
         assert!(
             std::mem::discriminant(&result.unwrap_err())
-                == std::mem::discriminant(&HandlerError::InternalError("".to_owned()))
+                == std::mem::discriminant(&HandlerError::Forbidden("".to_owned()))
         );
     }
 
     #[tokio::test]
-    async fn read_answers_should_return_answers() {
-        let answer_detail = AnswerDetail {
-            answer_uuid: "456".to_owned(),
-            question_uuid: "123".to_owned(),
-            content: "test content".to_owned(),
-            created_at: "now".to_owned(),
-        };
+    async fn undelete_question_should_forbid_author_if_moderator_deleted_it() {
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_question(Ok(Some(stored_question("789", Some("000")))));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = undelete_question("123".to_owned(), &user("789", Role::User), &questions_dao).await;
+
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::Forbidden("".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn undelete_question_should_return_not_found_for_missing_question() {
+        let mut questions_dao = QuestionsDaoMock::new();
+
+        questions_dao.mock_get_question(Ok(None));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+
+        let result = undelete_question("123".to_owned(), &user("789", Role::User), &questions_dao).await;
+
+        assert_eq!(result, Err(HandlerError::NotFound("question_not_found", "Question not found".to_owned())));
+    }
+
+    #[tokio::test]
+    async fn read_answers_should_forbid_deleted_posts_to_regular_users() {
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(AnswersDaoMock::new());
 
         let question_id = QuestionId {
             question_uuid: "123".to_owned(),
         };
 
+        let viewer = user("789", Role::User);
+
+        let result = read_answers(question_id, PageRequest::default(), true, Some(&viewer), &answers_dao).await;
+
+        assert!(
+            std::mem::discriminant(&result.unwrap_err())
+                == std::mem::discriminant(&HandlerError::Forbidden("".to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn undelete_answer_should_allow_moderator() {
         let mut answers_dao = AnswersDaoMock::new();
 
-        answers_dao.mock_get_answers(Ok(vec![answer_detail.clone()]));
+        answers_dao.mock_get_answer(Ok(Some(stored_answer("789", Some("789")))));
+        answers_dao.mock_undelete_answer(Ok(()));
 
         let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
 
-        let result = read_answers(question_id, &answers_dao).await;
+        let result = undelete_answer("456".to_owned(), &user("000", Role::Moderator), &answers_dao).await;
 
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), vec![answer_detail]);
     }
 
     #[tokio::test]
-    async fn read_answers_should_return_error() {
-        let question_id = QuestionId {
-            question_uuid: "123".to_owned(),
-        };
-
+    async fn undelete_answer_should_forbid_author_if_moderator_deleted_it() {
         let mut answers_dao = AnswersDaoMock::new();
 
-        answers_dao.mock_get_answers(Err(DBError::InvalidUUID("test".to_owned())));
+        answers_dao.mock_get_answer(Ok(Some(stored_answer("789", Some("000")))));
 
         let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
 
-        let result = read_answers(question_id, &answers_dao).await;
+        let result = undelete_answer("456".to_owned(), &user("789", Role::User), &answers_dao).await;
 
-        assert!(result.is_err());
         assert!(
             std::mem::discriminant(&result.unwrap_err())
-                == std::mem::discriminant(&HandlerError::InternalError("".to_owned()))
+                == std::mem::discriminant(&HandlerError::Forbidden("".to_owned()))
         );
     }
 
-    #[tokio::test]
-    async fn delete_answer_should_succeed() {
-        let answer_id = AnswerId {
-            answer_uuid: "123".to_owned(),
-        };
+    // ---- Health ----
 
+    #[tokio::test]
+    async fn readiness_should_be_up() {
+        let mut questions_dao = QuestionsDaoMock::new();
         let mut answers_dao = AnswersDaoMock::new();
 
-        answers_dao.mock_delete_answer(Ok(()));
+        questions_dao.mock_health_check(Ok(MigrationStatus::UpToDate));
+        answers_dao.mock_health_check(Ok(MigrationStatus::UpToDate));
 
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
         let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
 
-        let result = delete_answer(answer_id, &answers_dao).await;
+        let report = readiness(&questions_dao, &answers_dao).await;
 
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), ());
+        assert_eq!(report.status, HealthStatus::Up);
+        assert!(report.checks.iter().all(|check| check.status == HealthStatus::Up));
+        assert_eq!(report.checks.len(), 3);
     }
 
     #[tokio::test]
-    async fn delete_answer_should_return_error() {
-        let answer_id = AnswerId {
-            answer_uuid: "123".to_owned(),
-        };
+    async fn readiness_should_be_down_if_a_dao_fails() {
+        let mut questions_dao = QuestionsDaoMock::new();
+        let mut answers_dao = AnswersDaoMock::new();
+
+        questions_dao.mock_health_check(Ok(MigrationStatus::Untracked));
+        answers_dao.mock_health_check(Err(DBError::Unavailable { source: sqlx::Error::PoolTimedOut }));
+
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
+
+        let report = readiness(&questions_dao, &answers_dao).await;
+
+        assert_eq!(report.status, HealthStatus::Down);
+        assert_eq!(
+            report.checks,
+            vec![
+                HealthCheck::new("questions_dao", HealthStatus::Up, None),
+                HealthCheck::new("answers_dao", HealthStatus::Down, Some("Database is unavailable".to_owned())),
+                HealthCheck::new(
+                    "migrations",
+                    HealthStatus::Unknown,
+                    Some("The database doesn't record its migrations".to_owned()),
+                ),
+            ]
+        );
+    }
 
+    #[tokio::test]
+    async fn readiness_should_be_down_with_pending_migrations() {
+        let mut questions_dao = QuestionsDaoMock::new();
         let mut answers_dao = AnswersDaoMock::new();
 
-        answers_dao.mock_delete_answer(Err(DBError::InvalidUUID("test".to_owned())));
+        questions_dao.mock_health_check(Ok(MigrationStatus::Pending(vec![20230101000000])));
+        answers_dao.mock_health_check(Ok(MigrationStatus::Pending(vec![20230101000000])));
 
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(questions_dao);
         let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(answers_dao);
 
-        let result = delete_answer(answer_id, &answers_dao).await;
+        let report = readiness(&questions_dao, &answers_dao).await;
 
-        assert!(result.is_err());
-        assert!(
-            std::mem::discriminant(&result.unwrap_err())
-                == std::mem::discriminant(&HandlerError::InternalError("".to_owned()))
-        );
+        assert_eq!(report.status, HealthStatus::Down);
+        assert_eq!(report.checks[2].pending_migrations, Some(vec![20230101000000]));
+    }
+
+    /// Real in-memory DAOs holding a question with one answer, where either the question or only the answer
+    /// has been deleted by its author.
+    struct DeletedPosts {
+        questions_dao: Box<dyn QuestionsDao + Send + Sync>,
+        answers_dao: Box<dyn AnswersDao + Send + Sync>,
+        comments_dao: Box<dyn CommentsDao + Send + Sync>,
+        author: AuthenticatedUser,
+        voter: AuthenticatedUser,
+        moderator: AuthenticatedUser,
+        question_uuid: String,
+        answer_uuid: String,
+    }
+
+    async fn deleted_posts(delete_question: bool) -> DeletedPosts {
+        use crate::persistance::{
+            in_memory::{InMemoryAnswersDao, InMemoryCommentsDao, InMemoryQuestionsDao, InMemoryStore, InMemoryUsersDao},
+            users_dao::UsersDao,
+        };
+
+        let store = InMemoryStore::new();
+        let users_dao = InMemoryUsersDao::new(store.clone());
+        let questions_dao: Box<dyn QuestionsDao + Send + Sync> = Box::new(InMemoryQuestionsDao::new(store.clone()));
+        let answers_dao: Box<dyn AnswersDao + Send + Sync> = Box::new(InMemoryAnswersDao::new(store.clone()));
+        let comments_dao: Box<dyn CommentsDao + Send + Sync> = Box::new(InMemoryCommentsDao::new(store));
+
+        let author = users_dao.create_user("author".to_owned(), "hash".to_owned()).await.unwrap();
+        let author = user(&author.user_uuid, Role::User);
+        let voter = users_dao.create_user("voter".to_owned(), "hash".to_owned()).await.unwrap();
+        let voter = user(&voter.user_uuid, Role::User);
+        let moderator = users_dao.create_user("moderator".to_owned(), "hash".to_owned()).await.unwrap();
+        let moderator = user(&moderator.user_uuid, Role::Moderator);
+
+        let question = Question { title: "test title".to_owned(), description: "test description".to_owned(), tags: vec![] };
+        let question_uuid = questions_dao.create_question(question, author.user_uuid.clone()).await.unwrap().question_uuid;
+        let answer = Answer { question_uuid: question_uuid.clone(), content: "test content".to_owned() };
+        let answer_uuid = answers_dao.create_answer(answer, author.user_uuid.clone()).await.unwrap().answer_uuid;
+
+        if delete_question {
+            questions_dao.delete_question(question_uuid.clone(), author.user_uuid.clone()).await.unwrap();
+        } else {
+            answers_dao.delete_answer(answer_uuid.clone(), author.user_uuid.clone()).await.unwrap();
+        }
+
+        DeletedPosts { questions_dao, answers_dao, comments_dao, author, voter, moderator, question_uuid, answer_uuid }
+    }
+
+    fn upvote() -> Vote {
+        Vote { direction: VoteDirection::Up }
+    }
+
+    #[tokio::test]
+    async fn voting_on_deleted_posts_should_fail_with_not_found() {
+        let posts = deleted_posts(true).await;
+
+        let result = vote_question(posts.question_uuid.clone(), upvote(), &posts.voter, &posts.questions_dao).await;
+        assert_eq!(result, Err(HandlerError::NotFound("question_not_found", "Question not found".to_owned())));
+
+        let result = retract_question_vote(posts.question_uuid.clone(), &posts.voter, &posts.questions_dao).await;
+        assert_eq!(result, Err(HandlerError::NotFound("question_not_found", "Question not found".to_owned())));
+
+        // The answer itself isn't deleted, but it went away with its question.
+        let result = vote_answer(posts.answer_uuid.clone(), upvote(), &posts.voter, &posts.answers_dao).await;
+        assert_eq!(result, Err(HandlerError::NotFound("answer_not_found", "Answer not found".to_owned())));
+
+        let result = retract_answer_vote(posts.answer_uuid.clone(), &posts.voter, &posts.answers_dao).await;
+        assert_eq!(result, Err(HandlerError::NotFound("answer_not_found", "Answer not found".to_owned())));
+    }
+
+    #[tokio::test]
+    async fn editing_deleted_posts_should_only_be_allowed_for_moderators() {
+        let posts = deleted_posts(true).await;
+        let question = || Question { title: "new title".to_owned(), description: "new description".to_owned(), tags: vec![] };
+        let answer = || Answer { question_uuid: posts.question_uuid.clone(), content: "new content".to_owned() };
+
+        let result = update_question(question(), posts.question_uuid.clone(), None, &posts.author, &posts.questions_dao).await;
+        assert_eq!(result, Err(HandlerError::NotFound("question_not_found", "Question not found".to_owned())));
+
+        let result = update_answer(answer(), posts.answer_uuid.clone(), None, &posts.author, &posts.answers_dao).await;
+        assert_eq!(result, Err(HandlerError::NotFound("answer_not_found", "Answer not found".to_owned())));
+
+        let result = update_question(question(), posts.question_uuid.clone(), None, &posts.moderator, &posts.questions_dao).await;
+        assert_eq!(result.map(|question| question.title), Ok("new title".to_owned()));
+
+        let result = update_answer(answer(), posts.answer_uuid.clone(), None, &posts.moderator, &posts.answers_dao).await;
+        assert_eq!(result.map(|answer| answer.content), Ok("new content".to_owned()));
+    }
+
+    #[tokio::test]
+    async fn accepting_deleted_answers_should_fail_with_not_found() {
+        let posts = deleted_posts(false).await;
+        let answer_id = AnswerId { answer_uuid: posts.answer_uuid.clone() };
+
+        let result = accept_answer(posts.question_uuid.clone(), answer_id, &posts.author, &posts.questions_dao, &posts.answers_dao).await;
+        assert_eq!(result, Err(HandlerError::NotFound("answer_not_found", "Answer not found".to_owned())));
+
+        let posts = deleted_posts(true).await;
+        let answer_id = AnswerId { answer_uuid: posts.answer_uuid.clone() };
+
+        let result = accept_answer(posts.question_uuid.clone(), answer_id, &posts.author, &posts.questions_dao, &posts.answers_dao).await;
+        assert_eq!(result, Err(HandlerError::NotFound("question_not_found", "Question not found".to_owned())));
+    }
+
+    #[tokio::test]
+    async fn commenting_on_deleted_posts_should_fail_with_not_found() {
+        let posts = deleted_posts(false).await;
+        let parent = CommentParent { question_uuid: None, answer_uuid: Some(posts.answer_uuid.clone()) };
+        let comment = Comment { parent: parent.clone(), content: "test comment".to_owned() };
+
+        let result = create_comment(comment, posts.voter.user_uuid.clone(), &posts.comments_dao).await;
+        assert_eq!(result, Err(HandlerError::NotFound("answer_not_found", "Answer not found".to_owned())));
+
+        let result = read_comments(parent.clone(), PageRequest::default(), Some(&posts.voter), &posts.comments_dao).await;
+        assert_eq!(result, Err(HandlerError::NotFound("answer_not_found", "Answer not found".to_owned())));
+
+        let result = read_comments(parent, PageRequest::default(), Some(&posts.moderator), &posts.comments_dao).await;
+        assert_eq!(result.map(|page| page.items), Ok(vec![]));
     }
 }
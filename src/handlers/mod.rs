@@ -1,114 +1,1018 @@
-use rocket::{serde::json::Json, State};
+use rocket::{http::Status, request::FromParam, serde::json::{json, Json}, State};
+use sqlx::types::Uuid;
 
-use crate::{models::*, persistance::{questions_dao::QuestionsDao, answers_dao::AnswersDao}};
+use crate::{auth::AuthenticatedUser, models::*, persistance::{questions_dao::QuestionsDao, answers_dao::AnswersDao, comments_dao::CommentsDao, users_dao::UsersDao}};
 
+pub mod deprecated;
+mod diff;
+mod errors;
 mod handlers_inner;
+mod openapi;
+mod policy;
+mod validation;
 
-use handlers_inner::*;
+pub use errors::*;
+pub use openapi::ApiDoc;
 
-#[derive(Responder)]
-pub enum APIError {
-    #[response(status = 400)]
-    BadRequest(String),
-    #[response(status = 500)]
-    InternalError(String),
+/// A uuid path segment. Routes take it as `Result<PathUuid, APIError>` so that malformed ids are rejected
+/// with a 400 before reaching the DAOs, instead of Rocket forwarding the request.
+#[derive(Debug, PartialEq)]
+pub struct PathUuid(String);
+
+impl PathUuid {
+    pub fn into_inner(self) -> String {
+        self.0
+    }
 }
 
-impl From<HandlerError> for APIError {
-    fn from(value: HandlerError) -> Self {
-        match value {
-            HandlerError::BadRequest(s) => Self::BadRequest(s),
-            HandlerError::InternalError(s) => Self::InternalError(s),
-        }
+impl<'a> FromParam<'a> for PathUuid {
+    type Error = APIError;
+
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        Uuid::parse_str(param)
+            .map(|uuid| PathUuid(uuid.to_string()))
+            .map_err(|_| {
+                APIError::new(Status::BadRequest, "invalid_uuid", format!("Invalid UUID: {}", param))
+                    .with_details(json!({ "value": param }))
+            })
     }
 }
 
+// ---- Users ----
+
+#[utoipa::path(
+    post,
+    path = "/register",
+    tag = "users",
+    request_body = Credentials,
+    responses(
+        (status = 200, description = "The registered user", body = UserDetail),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 409, description = "Conflicts with the current state", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+)]
+#[post("/register", data = "<credentials>")]
+pub async fn register(
+    credentials: Json<Credentials>,
+    users_dao: &State<Box<dyn UsersDao + Sync + Send>>,
+) -> Result<Json<UserDetail>, APIError> {
+    let user = handlers_inner::register(credentials.0, users_dao).await
+                                        .map_err(Into::<APIError>::into)?;
+    Ok(Json(user))
+}
+
+#[utoipa::path(
+    post,
+    path = "/login",
+    tag = "users",
+    request_body = Credentials,
+    responses(
+        (status = 200, description = "A new session", body = Session),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+)]
+#[post("/login", data = "<credentials>")]
+pub async fn login(
+    credentials: Json<Credentials>,
+    users_dao: &State<Box<dyn UsersDao + Sync + Send>>,
+) -> Result<Json<Session>, APIError> {
+    let session = handlers_inner::login(credentials.0, users_dao).await
+                                        .map_err(Into::<APIError>::into)?;
+    Ok(Json(session))
+}
+
+#[utoipa::path(
+    post,
+    path = "/logout",
+    tag = "users",
+    responses(
+        (status = 200, description = "The session was ended"),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[post("/logout")]
+pub async fn logout(
+    user: AuthenticatedUser,
+    users_dao: &State<Box<dyn UsersDao + Sync + Send>>,
+) -> Result<(), APIError> {
+    handlers_inner::logout(user.session_token_hash, users_dao).await.map_err(Into::<APIError>::into)?;
+    Ok(())
+}
+
 // ---- CRUD for Questions ----
 
-#[post("/question", data = "<question>")]
+#[utoipa::path(
+    post,
+    path = "/questions",
+    tag = "questions",
+    request_body = Question,
+    responses(
+        (status = 200, description = "The created question", body = QuestionDetail),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[post("/questions", data = "<question>")]
 pub async fn create_question(
     question: Json<Question>,
+    user: AuthenticatedUser,
     questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
 ) -> Result<Json<QuestionDetail>, APIError> {
-    let question_detail = handlers_inner::create_question(question.0, questions_dao).await
-                                        .map_err(|e| Into::<APIError>::into(e))?;
+    let question_detail = handlers_inner::create_question(question.0, user.user_uuid, questions_dao).await
+                                        .map_err(Into::<APIError>::into)?;
     Ok(Json(question_detail))
-}   
+}
 
-#[get("/questions")]
+#[utoipa::path(
+    get,
+    path = "/questions",
+    tag = "questions",
+    params(
+        ("limit" = Option<i64>, Query, description = "Page size, 1 to 100, defaults to 20"),
+        ("cursor" = Option<String>, Query, description = "`next_cursor` of the previous page"),
+        ("tagged" = Option<String>, Query, description = "Comma separated tags the questions must all carry"),
+        ("include_deleted" = Option<bool>, Query, description = "Include soft deleted posts, moderators only"),
+    ),
+    responses(
+        (status = 200, description = "Newest questions first", body = Page<QuestionDetail>),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+    ),
+    security((), ("session_token" = [])),
+)]
+#[get("/questions?<limit>&<cursor>&<tagged>&<include_deleted>")]
 pub async fn read_questions(
+    limit: Option<i64>,
+    cursor: Option<String>,
+    tagged: Option<String>,
+    include_deleted: Option<bool>,
+    user: Option<AuthenticatedUser>,
     questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
-) -> Result<Json<Vec<QuestionDetail>>, APIError> { 
-    let questions = handlers_inner::read_questions(questions_dao).await
-                                        .map_err(|e| Into::<APIError>::into(e))?;
+) -> Result<Json<Page<QuestionDetail>>, APIError> {
+    let page = handlers_inner::page_request(limit, cursor)?;
+    // `?tagged=rust,sqlx` matches questions carrying all of the listed tags.
+    let tagged = tagged
+        .map(|tagged| tagged.split(',').map(str::to_owned).collect())
+        .unwrap_or_default();
+    let questions = handlers_inner::read_questions(page, tagged, include_deleted.unwrap_or(false), user.as_ref(), questions_dao).await
+                                        .map_err(Into::<APIError>::into)?;
     Ok(Json(questions))
 }
 
-#[delete("/question", data = "<question_uuid>")]
+#[utoipa::path(
+    get,
+    path = "/questions/search",
+    tag = "questions",
+    params(
+        ("q" = String, Query, description = "Full text search query, must contain a word"),
+        ("limit" = Option<i64>, Query, description = "Maximum number of results"),
+    ),
+    responses(
+        (status = 200, description = "Matching questions, best match first", body = Vec<QuestionSearchResult>),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+    ),
+)]
+#[get("/questions/search?<q>&<limit>")]
+pub async fn search_questions(
+    q: String,
+    limit: Option<i64>,
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+) -> Result<Json<Vec<QuestionSearchResult>>, APIError> {
+    let results = handlers_inner::search_questions(q, limit, questions_dao).await
+                                        .map_err(Into::<APIError>::into)?;
+    Ok(Json(results))
+}
+
+#[utoipa::path(
+    get,
+    path = "/tags",
+    tag = "questions",
+    responses(
+        (status = 200, description = "Tags with the number of questions carrying them", body = Vec<TagDetail>),
+    ),
+)]
+#[get("/tags")]
+pub async fn read_tags(
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+) -> Result<Json<Vec<TagDetail>>, APIError> {
+    let tags = handlers_inner::read_tags(questions_dao).await
+                                        .map_err(Into::<APIError>::into)?;
+    Ok(Json(tags))
+}
+
+/// `?include=answers` embeds the question's answers in the response.
+#[utoipa::path(
+    get,
+    path = "/questions/{question_uuid}",
+    tag = "questions",
+    params(
+        ("question_uuid" = String, Path, description = "Question uuid"),
+        ("include" = Option<String>, Query, description = "`answers` embeds the question's answers"),
+        ("include_deleted" = Option<bool>, Query, description = "Include soft deleted posts, moderators only"),
+    ),
+    responses(
+        (status = 200, description = "The question", body = QuestionWithAnswers),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+    security((), ("session_token" = [])),
+)]
+#[get("/questions/<question_uuid>?<include>&<include_deleted>")]
+pub async fn read_question(
+    question_uuid: Result<PathUuid, APIError>,
+    include: Option<String>,
+    include_deleted: Option<bool>,
+    user: Option<AuthenticatedUser>,
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+) -> Result<Json<QuestionWithAnswers>, APIError> {
+    let include_answers = match include.as_deref() {
+        None => false,
+        Some("answers") => true,
+        Some(include) => {
+            return Err(APIError::new(Status::BadRequest, "invalid_include", format!("Unknown include: {}", include))
+                .with_details(json!({ "allowed": ["answers"] })))
+        }
+    };
+    let question = handlers_inner::read_question(question_uuid?.into_inner(), include_answers, include_deleted.unwrap_or(false), user.as_ref(), questions_dao).await
+                                        .map_err(Into::<APIError>::into)?;
+    question
+        .map(Json)
+        .ok_or_else(|| APIError::new(Status::NotFound, "question_not_found", "Question not found"))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/questions/{question_uuid}",
+    tag = "questions",
+    params(
+        ("question_uuid" = String, Path, description = "Question uuid"),
+    ),
+    responses(
+        (status = 200, description = "The question was soft deleted"),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[delete("/questions/<question_uuid>")]
 pub async fn delete_question(
-    question_uuid: Json<QuestionId>,
+    question_uuid: Result<PathUuid, APIError>,
+    user: AuthenticatedUser,
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+) -> Result<(), APIError> {
+    let question_uuid = QuestionId { question_uuid: question_uuid?.into_inner() };
+    handlers_inner::delete_question(question_uuid, &user, questions_dao).await.map_err(Into::<APIError>::into)?;
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/questions/{question_uuid}/undelete",
+    tag = "questions",
+    params(
+        ("question_uuid" = String, Path, description = "Question uuid"),
+    ),
+    responses(
+        (status = 200, description = "The question was restored"),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[post("/questions/<question_uuid>/undelete")]
+pub async fn undelete_question(
+    question_uuid: Result<PathUuid, APIError>,
+    user: AuthenticatedUser,
     questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
 ) -> Result<(), APIError> {
-    handlers_inner::delete_question(question_uuid.0, questions_dao).await.map_err(|e| Into::<APIError>::into(e))?;
+    handlers_inner::undelete_question(question_uuid?.into_inner(), &user, questions_dao).await.map_err(Into::<APIError>::into)?;
     Ok(())
 }
 
-#[put("/question", data = "<update_request>")]
+#[utoipa::path(
+    put,
+    path = "/questions/{question_uuid}",
+    tag = "questions",
+    request_body = Edit<Question>,
+    params(
+        ("question_uuid" = String, Path, description = "Question uuid"),
+    ),
+    responses(
+        (status = 200, description = "The updated question", body = QuestionDetail),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[put("/questions/<question_uuid>", data = "<edit>")]
 pub async fn update_question(
-    update_request: Json<UpdateRequest<Question>>,
+    question_uuid: Result<PathUuid, APIError>,
+    edit: Json<Edit<Question>>,
+    user: AuthenticatedUser,
     questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
 ) -> Result<Json<QuestionDetail>, APIError> {
-    let updated_question = Question { 
-        title: update_request.updated_entity.title.to_owned(), 
-        description: update_request.updated_entity.description.to_owned() 
-    };
-    let question_detail = handlers_inner::update_question(updated_question, update_request.uuid.to_owned(), questions_dao).await
-                                        .map_err(|e| Into::<APIError>::into(e))?;
+    let question_uuid = question_uuid?.into_inner();
+    let edit = edit.0;
+    let question_detail = handlers_inner::update_question(edit.updated_entity, question_uuid, edit.edit_summary, &user, questions_dao).await
+                                        .map_err(Into::<APIError>::into)?;
+    Ok(Json(question_detail))
+}
+
+#[utoipa::path(
+    post,
+    path = "/questions/{question_uuid}/vote",
+    tag = "questions",
+    request_body = Vote,
+    params(
+        ("question_uuid" = String, Path, description = "Question uuid"),
+    ),
+    responses(
+        (status = 200, description = "The question's new score", body = Score),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[post("/questions/<question_uuid>/vote", data = "<vote>")]
+pub async fn vote_question(
+    question_uuid: Result<PathUuid, APIError>,
+    vote: Json<Vote>,
+    user: AuthenticatedUser,
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+) -> Result<Json<Score>, APIError> {
+    let score = handlers_inner::vote_question(question_uuid?.into_inner(), vote.0, &user, questions_dao).await
+                                        .map_err(Into::<APIError>::into)?;
+    Ok(Json(score))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/questions/{question_uuid}/vote",
+    tag = "questions",
+    params(
+        ("question_uuid" = String, Path, description = "Question uuid"),
+    ),
+    responses(
+        (status = 200, description = "The question's new score", body = Score),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[delete("/questions/<question_uuid>/vote")]
+pub async fn retract_question_vote(
+    question_uuid: Result<PathUuid, APIError>,
+    user: AuthenticatedUser,
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+) -> Result<Json<Score>, APIError> {
+    let score = handlers_inner::retract_question_vote(question_uuid?.into_inner(), &user, questions_dao).await
+                                        .map_err(Into::<APIError>::into)?;
+    Ok(Json(score))
+}
+
+#[utoipa::path(
+    post,
+    path = "/questions/{question_uuid}/accept",
+    tag = "questions",
+    request_body = AnswerId,
+    params(
+        ("question_uuid" = String, Path, description = "Question uuid"),
+    ),
+    responses(
+        (status = 200, description = "The answer was accepted"),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[post("/questions/<question_uuid>/accept", data = "<answer_uuid>")]
+pub async fn accept_answer(
+    question_uuid: Result<PathUuid, APIError>,
+    answer_uuid: Json<AnswerId>,
+    user: AuthenticatedUser,
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>,
+) -> Result<(), APIError> {
+    handlers_inner::accept_answer(question_uuid?.into_inner(), answer_uuid.0, &user, questions_dao, answers_dao).await
+                                        .map_err(Into::<APIError>::into)?;
+    Ok(())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/questions/{question_uuid}/accept",
+    tag = "questions",
+    params(
+        ("question_uuid" = String, Path, description = "Question uuid"),
+    ),
+    responses(
+        (status = 200, description = "The accepted answer was cleared"),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[delete("/questions/<question_uuid>/accept")]
+pub async fn unaccept_answer(
+    question_uuid: Result<PathUuid, APIError>,
+    user: AuthenticatedUser,
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+) -> Result<(), APIError> {
+    handlers_inner::unaccept_answer(question_uuid?.into_inner(), &user, questions_dao).await
+                                        .map_err(Into::<APIError>::into)?;
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/questions/{question_uuid}/revisions",
+    tag = "questions",
+    params(
+        ("question_uuid" = String, Path, description = "Question uuid"),
+    ),
+    responses(
+        (status = 200, description = "Revisions, newest first", body = Vec<QuestionRevision>),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+    security((), ("session_token" = [])),
+)]
+#[get("/questions/<question_uuid>/revisions")]
+pub async fn read_question_revisions(
+    question_uuid: Result<PathUuid, APIError>,
+    user: Option<AuthenticatedUser>,
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+) -> Result<Json<Vec<QuestionRevision>>, APIError> {
+    let revisions = handlers_inner::read_question_revisions(question_uuid?.into_inner(), user.as_ref(), questions_dao).await
+                                        .map_err(Into::<APIError>::into)?;
+    Ok(Json(revisions))
+}
+
+#[utoipa::path(
+    get,
+    path = "/questions/{question_uuid}/revisions/diff",
+    tag = "questions",
+    params(
+        ("question_uuid" = String, Path, description = "Question uuid"),
+        ("from" = String, Query, description = "Older revision uuid"),
+        ("to" = String, Query, description = "Newer revision uuid"),
+    ),
+    responses(
+        (status = 200, description = "Changes between the two revisions", body = RevisionDiff),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+    security((), ("session_token" = [])),
+)]
+#[get("/questions/<question_uuid>/revisions/diff?<from>&<to>")]
+pub async fn diff_question_revisions(
+    question_uuid: Result<PathUuid, APIError>,
+    from: String,
+    to: String,
+    user: Option<AuthenticatedUser>,
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+) -> Result<Json<RevisionDiff>, APIError> {
+    let diff = handlers_inner::diff_question_revisions(question_uuid?.into_inner(), from, to, user.as_ref(), questions_dao).await
+                                        .map_err(Into::<APIError>::into)?;
+    Ok(Json(diff))
+}
+
+#[utoipa::path(
+    post,
+    path = "/questions/{question_uuid}/revisions/{revision_uuid}/rollback",
+    tag = "questions",
+    params(
+        ("question_uuid" = String, Path, description = "Question uuid"),
+        ("revision_uuid" = String, Path, description = "Revision uuid"),
+    ),
+    responses(
+        (status = 200, description = "The question restored to the revision", body = QuestionDetail),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[post("/questions/<question_uuid>/revisions/<revision_uuid>/rollback")]
+pub async fn rollback_question(
+    question_uuid: Result<PathUuid, APIError>,
+    revision_uuid: Result<PathUuid, APIError>,
+    user: AuthenticatedUser,
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+) -> Result<Json<QuestionDetail>, APIError> {
+    let question_detail = handlers_inner::rollback_question(question_uuid?.into_inner(), revision_uuid?.into_inner(), &user, questions_dao).await
+                                        .map_err(Into::<APIError>::into)?;
     Ok(Json(question_detail))
 }
 
 // ---- CRUD for Answers ----
 
-#[post("/answer", data = "<answer>")]
+#[utoipa::path(
+    post,
+    path = "/answers",
+    tag = "answers",
+    request_body = Answer,
+    responses(
+        (status = 200, description = "The created answer", body = AnswerDetail),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[post("/answers", data = "<answer>")]
 pub async fn create_answer(
     answer: Json<Answer>,
+    user: AuthenticatedUser,
     answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>,
 ) -> Result<Json<AnswerDetail>, APIError> {
-    let answer_detail = handlers_inner::create_answer(answer.0, answers_dao).await
-                                                            .map_err(|e| Into::<APIError>::into(e))?;
+    let answer_detail = handlers_inner::create_answer(answer.0, user.user_uuid, answers_dao).await
+                                                            .map_err(Into::<APIError>::into)?;
     Ok(Json(answer_detail))
 }
 
-#[get("/answers", data = "<question_uuid>")]
+#[utoipa::path(
+    get,
+    path = "/questions/{question_uuid}/answers",
+    tag = "answers",
+    params(
+        ("question_uuid" = String, Path, description = "Question uuid"),
+        ("limit" = Option<i64>, Query, description = "Page size, 1 to 100, defaults to 20"),
+        ("cursor" = Option<String>, Query, description = "`next_cursor` of the previous page"),
+        ("include_deleted" = Option<bool>, Query, description = "Include soft deleted posts, moderators only"),
+    ),
+    responses(
+        (status = 200, description = "The question's answers", body = Page<AnswerDetail>),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+    ),
+    security((), ("session_token" = [])),
+)]
+#[get("/questions/<question_uuid>/answers?<limit>&<cursor>&<include_deleted>")]
 pub async fn read_answers(
-    question_uuid: Json<QuestionId>,
-    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>, 
-) -> Result<Json<Vec<AnswerDetail>>, APIError>  {
-    let answers = handlers_inner::read_answers(question_uuid.0, answers_dao).await
-                                                            .map_err(|e| Into::<APIError>::into(e))?;
+    question_uuid: Result<PathUuid, APIError>,
+    limit: Option<i64>,
+    cursor: Option<String>,
+    include_deleted: Option<bool>,
+    user: Option<AuthenticatedUser>,
+    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>,
+) -> Result<Json<Page<AnswerDetail>>, APIError>  {
+    let question_uuid = QuestionId { question_uuid: question_uuid?.into_inner() };
+    let page = handlers_inner::page_request(limit, cursor)?;
+    let answers = handlers_inner::read_answers(question_uuid, page, include_deleted.unwrap_or(false), user.as_ref(), answers_dao).await
+                                                            .map_err(Into::<APIError>::into)?;
     Ok(Json (answers))
 }
 
-#[delete("/answer", data = "<answer_uuid>")]
+#[utoipa::path(
+    delete,
+    path = "/answers/{answer_uuid}",
+    tag = "answers",
+    params(
+        ("answer_uuid" = String, Path, description = "Answer uuid"),
+    ),
+    responses(
+        (status = 200, description = "The answer was soft deleted"),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[delete("/answers/<answer_uuid>")]
 pub async fn delete_answer(
-    answer_uuid: Json<AnswerId>,
-    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>, 
+    answer_uuid: Result<PathUuid, APIError>,
+    user: AuthenticatedUser,
+    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>,
 ) ->  Result<(), APIError>  {
-    handlers_inner::delete_answer(answer_uuid.0, answers_dao).await
-                    .map_err(|e| Into::<APIError>::into(e))?;
+    let answer_uuid = AnswerId { answer_uuid: answer_uuid?.into_inner() };
+    handlers_inner::delete_answer(answer_uuid, &user, answers_dao).await
+                    .map_err(Into::<APIError>::into)?;
     Ok(())
 }
 
-#[put("/answer", data = "<update_request>")]
+#[utoipa::path(
+    post,
+    path = "/answers/{answer_uuid}/undelete",
+    tag = "answers",
+    params(
+        ("answer_uuid" = String, Path, description = "Answer uuid"),
+    ),
+    responses(
+        (status = 200, description = "The answer was restored"),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[post("/answers/<answer_uuid>/undelete")]
+pub async fn undelete_answer(
+    answer_uuid: Result<PathUuid, APIError>,
+    user: AuthenticatedUser,
+    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>,
+) -> Result<(), APIError> {
+    handlers_inner::undelete_answer(answer_uuid?.into_inner(), &user, answers_dao).await
+                    .map_err(Into::<APIError>::into)?;
+    Ok(())
+}
+
+#[utoipa::path(
+    put,
+    path = "/answers/{answer_uuid}",
+    tag = "answers",
+    request_body = Edit<Answer>,
+    params(
+        ("answer_uuid" = String, Path, description = "Answer uuid"),
+    ),
+    responses(
+        (status = 200, description = "The updated answer", body = AnswerDetail),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[put("/answers/<answer_uuid>", data = "<edit>")]
 pub async fn update_answer(
-    update_request: Json<UpdateRequest<Answer>>,
-    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>, 
+    answer_uuid: Result<PathUuid, APIError>,
+    edit: Json<Edit<Answer>>,
+    user: AuthenticatedUser,
+    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>,
 ) -> Result<Json<AnswerDetail>, APIError> {
-    let updated_answer = Answer { 
-        question_uuid: update_request.updated_entity.question_uuid.to_owned(), 
-        content: update_request.updated_entity.content.to_owned() 
-    };
-    let answer_detail = handlers_inner::update_answer(updated_answer, update_request.uuid.to_owned(), answers_dao).await
-                                                            .map_err(|e| Into::<APIError>::into(e))?;
+    let answer_uuid = answer_uuid?.into_inner();
+    let edit = edit.0;
+    let answer_detail = handlers_inner::update_answer(edit.updated_entity, answer_uuid, edit.edit_summary, &user, answers_dao).await
+                                                            .map_err(Into::<APIError>::into)?;
+    Ok(Json(answer_detail))
+}
+
+#[utoipa::path(
+    post,
+    path = "/answers/{answer_uuid}/vote",
+    tag = "answers",
+    request_body = Vote,
+    params(
+        ("answer_uuid" = String, Path, description = "Answer uuid"),
+    ),
+    responses(
+        (status = 200, description = "The answer's new score", body = Score),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[post("/answers/<answer_uuid>/vote", data = "<vote>")]
+pub async fn vote_answer(
+    answer_uuid: Result<PathUuid, APIError>,
+    vote: Json<Vote>,
+    user: AuthenticatedUser,
+    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>,
+) -> Result<Json<Score>, APIError> {
+    let score = handlers_inner::vote_answer(answer_uuid?.into_inner(), vote.0, &user, answers_dao).await
+                                                            .map_err(Into::<APIError>::into)?;
+    Ok(Json(score))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/answers/{answer_uuid}/vote",
+    tag = "answers",
+    params(
+        ("answer_uuid" = String, Path, description = "Answer uuid"),
+    ),
+    responses(
+        (status = 200, description = "The answer's new score", body = Score),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[delete("/answers/<answer_uuid>/vote")]
+pub async fn retract_answer_vote(
+    answer_uuid: Result<PathUuid, APIError>,
+    user: AuthenticatedUser,
+    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>,
+) -> Result<Json<Score>, APIError> {
+    let score = handlers_inner::retract_answer_vote(answer_uuid?.into_inner(), &user, answers_dao).await
+                                                            .map_err(Into::<APIError>::into)?;
+    Ok(Json(score))
+}
+
+#[utoipa::path(
+    get,
+    path = "/answers/{answer_uuid}/revisions",
+    tag = "answers",
+    params(
+        ("answer_uuid" = String, Path, description = "Answer uuid"),
+    ),
+    responses(
+        (status = 200, description = "Revisions, newest first", body = Vec<AnswerRevision>),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+    security((), ("session_token" = [])),
+)]
+#[get("/answers/<answer_uuid>/revisions")]
+pub async fn read_answer_revisions(
+    answer_uuid: Result<PathUuid, APIError>,
+    user: Option<AuthenticatedUser>,
+    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>,
+) -> Result<Json<Vec<AnswerRevision>>, APIError> {
+    let revisions = handlers_inner::read_answer_revisions(answer_uuid?.into_inner(), user.as_ref(), answers_dao).await
+                                                            .map_err(Into::<APIError>::into)?;
+    Ok(Json(revisions))
+}
+
+#[utoipa::path(
+    get,
+    path = "/answers/{answer_uuid}/revisions/diff",
+    tag = "answers",
+    params(
+        ("answer_uuid" = String, Path, description = "Answer uuid"),
+        ("from" = String, Query, description = "Older revision uuid"),
+        ("to" = String, Query, description = "Newer revision uuid"),
+    ),
+    responses(
+        (status = 200, description = "Changes between the two revisions", body = RevisionDiff),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+    security((), ("session_token" = [])),
+)]
+#[get("/answers/<answer_uuid>/revisions/diff?<from>&<to>")]
+pub async fn diff_answer_revisions(
+    answer_uuid: Result<PathUuid, APIError>,
+    from: String,
+    to: String,
+    user: Option<AuthenticatedUser>,
+    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>,
+) -> Result<Json<RevisionDiff>, APIError> {
+    let diff = handlers_inner::diff_answer_revisions(answer_uuid?.into_inner(), from, to, user.as_ref(), answers_dao).await
+                                                            .map_err(Into::<APIError>::into)?;
+    Ok(Json(diff))
+}
+
+#[utoipa::path(
+    post,
+    path = "/answers/{answer_uuid}/revisions/{revision_uuid}/rollback",
+    tag = "answers",
+    params(
+        ("answer_uuid" = String, Path, description = "Answer uuid"),
+        ("revision_uuid" = String, Path, description = "Revision uuid"),
+    ),
+    responses(
+        (status = 200, description = "The answer restored to the revision", body = AnswerDetail),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[post("/answers/<answer_uuid>/revisions/<revision_uuid>/rollback")]
+pub async fn rollback_answer(
+    answer_uuid: Result<PathUuid, APIError>,
+    revision_uuid: Result<PathUuid, APIError>,
+    user: AuthenticatedUser,
+    answers_dao: &State<Box<dyn AnswersDao + Send + Sync>>,
+) -> Result<Json<AnswerDetail>, APIError> {
+    let answer_detail = handlers_inner::rollback_answer(answer_uuid?.into_inner(), revision_uuid?.into_inner(), &user, answers_dao).await
+                                                            .map_err(Into::<APIError>::into)?;
     Ok(Json(answer_detail))
 }
+
+// ---- CRUD for Comments ----
+
+#[utoipa::path(
+    post,
+    path = "/comments",
+    tag = "comments",
+    request_body = Comment,
+    responses(
+        (status = 200, description = "The created comment", body = CommentDetail),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[post("/comments", data = "<comment>")]
+pub async fn create_comment(
+    comment: Json<Comment>,
+    user: AuthenticatedUser,
+    comments_dao: &State<Box<dyn CommentsDao + Send + Sync>>,
+) -> Result<Json<CommentDetail>, APIError> {
+    let comment_detail = handlers_inner::create_comment(comment.0, user.user_uuid, comments_dao).await
+                                                            .map_err(Into::<APIError>::into)?;
+    Ok(Json(comment_detail))
+}
+
+#[utoipa::path(
+    get,
+    path = "/questions/{question_uuid}/comments",
+    tag = "comments",
+    params(
+        ("question_uuid" = String, Path, description = "Question uuid"),
+        ("limit" = Option<i64>, Query, description = "Page size, 1 to 100, defaults to 20"),
+        ("cursor" = Option<String>, Query, description = "`next_cursor` of the previous page"),
+    ),
+    responses(
+        (status = 200, description = "The question's comments, oldest first", body = Page<CommentDetail>),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+    security((), ("session_token" = [])),
+)]
+#[get("/questions/<question_uuid>/comments?<limit>&<cursor>")]
+pub async fn read_question_comments(
+    question_uuid: Result<PathUuid, APIError>,
+    limit: Option<i64>,
+    cursor: Option<String>,
+    user: Option<AuthenticatedUser>,
+    comments_dao: &State<Box<dyn CommentsDao + Send + Sync>>,
+) -> Result<Json<Page<CommentDetail>>, APIError> {
+    let parent = CommentParent { question_uuid: Some(question_uuid?.into_inner()), answer_uuid: None };
+    let page = handlers_inner::page_request(limit, cursor)?;
+    let comments = handlers_inner::read_comments(parent, page, user.as_ref(), comments_dao).await
+                                                            .map_err(Into::<APIError>::into)?;
+    Ok(Json(comments))
+}
+
+#[utoipa::path(
+    get,
+    path = "/answers/{answer_uuid}/comments",
+    tag = "comments",
+    params(
+        ("answer_uuid" = String, Path, description = "Answer uuid"),
+        ("limit" = Option<i64>, Query, description = "Page size, 1 to 100, defaults to 20"),
+        ("cursor" = Option<String>, Query, description = "`next_cursor` of the previous page"),
+    ),
+    responses(
+        (status = 200, description = "The answer's comments, oldest first", body = Page<CommentDetail>),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+    security((), ("session_token" = [])),
+)]
+#[get("/answers/<answer_uuid>/comments?<limit>&<cursor>")]
+pub async fn read_answer_comments(
+    answer_uuid: Result<PathUuid, APIError>,
+    limit: Option<i64>,
+    cursor: Option<String>,
+    user: Option<AuthenticatedUser>,
+    comments_dao: &State<Box<dyn CommentsDao + Send + Sync>>,
+) -> Result<Json<Page<CommentDetail>>, APIError> {
+    let parent = CommentParent { question_uuid: None, answer_uuid: Some(answer_uuid?.into_inner()) };
+    let page = handlers_inner::page_request(limit, cursor)?;
+    let comments = handlers_inner::read_comments(parent, page, user.as_ref(), comments_dao).await
+                                                            .map_err(Into::<APIError>::into)?;
+    Ok(Json(comments))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/comments/{comment_uuid}",
+    tag = "comments",
+    params(
+        ("comment_uuid" = String, Path, description = "Comment uuid"),
+    ),
+    responses(
+        (status = 200, description = "The comment was deleted"),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[delete("/comments/<comment_uuid>")]
+pub async fn delete_comment(
+    comment_uuid: Result<PathUuid, APIError>,
+    user: AuthenticatedUser,
+    comments_dao: &State<Box<dyn CommentsDao + Send + Sync>>,
+) -> Result<(), APIError> {
+    let comment_uuid = CommentId { comment_uuid: comment_uuid?.into_inner() };
+    handlers_inner::delete_comment(comment_uuid, &user, comments_dao).await
+                    .map_err(Into::<APIError>::into)?;
+    Ok(())
+}
+
+#[utoipa::path(
+    put,
+    path = "/comments/{comment_uuid}",
+    tag = "comments",
+    request_body = Edit<Comment>,
+    params(
+        ("comment_uuid" = String, Path, description = "Comment uuid"),
+    ),
+    responses(
+        (status = 200, description = "The updated comment", body = CommentDetail),
+        (status = 400, description = "Malformed uuid or invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid session token", body = ErrorBody),
+        (status = 403, description = "Not allowed for this user", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 422, description = "Invalid or unparsable body", body = ErrorBody),
+    ),
+    security(("session_token" = [])),
+)]
+#[put("/comments/<comment_uuid>", data = "<edit>")]
+pub async fn update_comment(
+    comment_uuid: Result<PathUuid, APIError>,
+    edit: Json<Edit<Comment>>,
+    user: AuthenticatedUser,
+    comments_dao: &State<Box<dyn CommentsDao + Send + Sync>>,
+) -> Result<Json<CommentDetail>, APIError> {
+    let comment_detail = handlers_inner::update_comment(edit.0.updated_entity, comment_uuid?.into_inner(), &user, comments_dao).await
+                                                            .map_err(Into::<APIError>::into)?;
+    Ok(Json(comment_detail))
+}
+
+// ---- Health ----
+
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    tag = "health",
+    responses(
+        (status = 200, description = "The API is running", body = HealthReport),
+    ),
+)]
+#[get("/health/live")]
+pub async fn liveness() -> Json<HealthReport> {
+    Json(HealthReport::from_checks(vec![]))
+}
+
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    tag = "health",
+    responses(
+        (status = 200, description = "The API can serve requests", body = HealthReport),
+        (status = 503, description = "A dependency is down", body = HealthReport),
+    ),
+)]
+#[get("/health/ready")]
+pub async fn readiness(
+    questions_dao: &State<Box<dyn QuestionsDao + Sync + Send>>,
+    answers_dao: &State<Box<dyn AnswersDao + Sync + Send>>,
+) -> (Status, Json<HealthReport>) {
+    let report = handlers_inner::readiness(questions_dao, answers_dao).await;
+    let status = match report.status {
+        HealthStatus::Down => Status::ServiceUnavailable,
+        _ => Status::Ok,
+    };
+
+    (status, Json(report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_uuid_should_normalize_valid_uuids() {
+        let uuid = PathUuid::from_param("A22ABCD2-22AB-4222-A22B-2ABC2A2B22CC").unwrap();
+
+        assert_eq!(uuid.into_inner(), "a22abcd2-22ab-4222-a22b-2abc2a2b22cc");
+    }
+
+    #[test]
+    fn path_uuid_should_reject_malformed_uuids() {
+        let result = PathUuid::from_param("not-a-uuid");
+
+        assert_eq!(result.unwrap_err().body.code, "invalid_uuid");
+    }
+}
@@ -0,0 +1,161 @@
+//! OpenAPI 3 document of the routes in this module, served at `/openapi.json` next to a Swagger UI page.
+
+use utoipa::{
+    openapi::{
+        security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+        Deprecated,
+    },
+    Modify, OpenApi,
+};
+
+use super::{validation::FieldError, *};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Stack Overflow clone API"),
+    paths(
+        register,
+        login,
+        logout,
+        create_question,
+        read_questions,
+        search_questions,
+        read_tags,
+        read_question,
+        delete_question,
+        undelete_question,
+        update_question,
+        vote_question,
+        retract_question_vote,
+        accept_answer,
+        unaccept_answer,
+        read_question_revisions,
+        diff_question_revisions,
+        rollback_question,
+        create_answer,
+        read_answers,
+        delete_answer,
+        undelete_answer,
+        update_answer,
+        vote_answer,
+        retract_answer_vote,
+        read_answer_revisions,
+        diff_answer_revisions,
+        rollback_answer,
+        create_comment,
+        read_question_comments,
+        read_answer_comments,
+        delete_comment,
+        update_comment,
+        liveness,
+        readiness,
+        deprecated::create_question,
+        deprecated::delete_question,
+        deprecated::undelete_question,
+        deprecated::update_question,
+        deprecated::vote_question,
+        deprecated::retract_question_vote,
+        deprecated::accept_answer,
+        deprecated::unaccept_answer,
+        deprecated::read_question_revisions,
+        deprecated::diff_question_revisions,
+        deprecated::rollback_question,
+        deprecated::create_answer,
+        deprecated::read_answers,
+        deprecated::delete_answer,
+        deprecated::undelete_answer,
+        deprecated::update_answer,
+        deprecated::vote_answer,
+        deprecated::retract_answer_vote,
+        deprecated::read_answer_revisions,
+        deprecated::diff_answer_revisions,
+        deprecated::rollback_answer,
+        deprecated::create_comment,
+        deprecated::read_comments,
+        deprecated::delete_comment,
+        deprecated::update_comment,
+    ),
+    components(schemas(FieldError, UpdateRequest<Question>, UpdateRequest<Answer>, UpdateRequest<Comment>)),
+    modifiers(&SessionToken, &DeprecatedRoutes),
+    tags(
+        (name = "users", description = "Registration and sessions"),
+        (name = "questions"),
+        (name = "answers"),
+        (name = "comments"),
+        (name = "health", description = "Liveness and readiness probes"),
+        (name = "deprecated", description = "Routes from before uuids moved into paths, kept for existing clients"),
+    ),
+)]
+pub struct ApiDoc;
+
+/// Sessions are sent as `Authorization: Bearer <token>`, see `AuthenticatedUser`.
+struct SessionToken;
+
+impl Modify for SessionToken {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "session_token",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .description(Some("Token returned by `POST /login`"))
+                    .build(),
+            ),
+        );
+    }
+}
+
+/// `#[deprecated]` on the route functions would warn at every mount, so the spec is marked from their tag instead.
+struct DeprecatedRoutes;
+
+impl Modify for DeprecatedRoutes {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        for item in openapi.paths.paths.values_mut() {
+            let operations = [&mut item.get, &mut item.put, &mut item.post, &mut item.delete];
+            for operation in operations.into_iter().flatten() {
+                if operation.tags.iter().flatten().any(|tag| tag == "deprecated") {
+                    operation.deprecated = Some(Deprecated::True);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn openapi_should_document_routes_and_error_bodies() {
+        let openapi = ApiDoc::openapi();
+
+        let question = openapi.paths.paths.get("/questions/{question_uuid}").expect("route is documented");
+        assert!(question.get.is_some() && question.put.is_some() && question.delete.is_some());
+        assert!(question.get.as_ref().unwrap().deprecated.is_none());
+
+        let schemas = &openapi.components.as_ref().unwrap().schemas;
+        for schema in ["ErrorBody", "FieldError", "QuestionDetail", "AnswerDetail", "UpdateRequest_Question"] {
+            assert!(schemas.contains_key(schema), "missing schema {}", schema);
+        }
+        assert!(openapi.components.as_ref().unwrap().security_schemes.contains_key("session_token"));
+    }
+
+    #[test]
+    fn openapi_should_mark_old_routes_deprecated_with_unique_operation_ids() {
+        let openapi = ApiDoc::openapi();
+
+        let old_route = openapi.paths.paths.get("/question").and_then(|item| item.put.as_ref()).expect("route is documented");
+        assert!(matches!(old_route.deprecated, Some(Deprecated::True)));
+
+        let operation_ids: Vec<&String> = openapi.paths.paths.values()
+            .flat_map(|item| [&item.get, &item.put, &item.post, &item.delete])
+            .flatten()
+            .filter_map(|operation| operation.operation_id.as_ref())
+            .collect();
+        assert_eq!(operation_ids.iter().collect::<HashSet<_>>().len(), operation_ids.len());
+        assert_eq!(operation_ids.len(), 60);
+    }
+}
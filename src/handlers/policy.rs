@@ -0,0 +1,27 @@
+use crate::{auth::AuthenticatedUser, models::Role};
+
+/// Authors may edit and delete their own posts, moderators and admins may edit and delete any post.
+pub fn can_modify_post(user: &AuthenticatedUser, author_uuid: Option<&str>) -> bool {
+    matches!(user.role, Role::Moderator | Role::Admin) || author_uuid == Some(user.user_uuid.as_str())
+}
+
+/// Moderators and admins may restore any post, authors only posts they deleted themselves.
+pub fn can_undelete_post(user: &AuthenticatedUser, author_uuid: Option<&str>, deleted_by: Option<&str>) -> bool {
+    matches!(user.role, Role::Moderator | Role::Admin)
+        || (author_uuid == Some(user.user_uuid.as_str()) && deleted_by == author_uuid)
+}
+
+/// Users may vote on anybody's posts but their own.
+pub fn can_vote_on_post(user: &AuthenticatedUser, author_uuid: Option<&str>) -> bool {
+    author_uuid != Some(user.user_uuid.as_str())
+}
+
+/// Only the question's author decides which answer solved it.
+pub fn can_accept_answer(user: &AuthenticatedUser, question_author_uuid: Option<&str>) -> bool {
+    question_author_uuid == Some(user.user_uuid.as_str())
+}
+
+/// Deleted posts stay hidden from everyone but moderators and admins.
+pub fn can_view_deleted_posts(user: Option<&AuthenticatedUser>) -> bool {
+    user.is_some_and(|user| matches!(user.role, Role::Moderator | Role::Admin))
+}
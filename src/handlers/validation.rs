@@ -0,0 +1,226 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::handlers_inner::HandlerError;
+use crate::models::{Answer, Comment, Question};
+
+/// One rejected field of a request body, e.g. `{"field": "updated_entity.title", "message": "must not be blank"}`.
+#[derive(Serialize, ToSchema, Debug, PartialEq, Clone)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// Bounds for a text field. Lengths are counted in characters on the value as it will be stored, so handlers
+/// trim values with `Normalize` before checking them.
+pub struct TextRule {
+    pub min: usize,
+    pub max: usize,
+    /// Allows line breaks and tabs, every other control character is rejected either way.
+    pub multiline: bool,
+}
+
+impl TextRule {
+    const fn single_line(min: usize, max: usize) -> Self {
+        TextRule { min, max, multiline: false }
+    }
+
+    const fn multiline(min: usize, max: usize) -> Self {
+        TextRule { min, max, multiline: true }
+    }
+
+    fn check(&self, value: &str) -> Option<String> {
+        let length = value.chars().count();
+
+        if self.min > 0 && value.trim().is_empty() {
+            return Some("must not be blank".to_owned());
+        }
+        if length < self.min {
+            return Some(format!("must be at least {} characters long", self.min));
+        }
+        if length > self.max {
+            return Some(format!("must be at most {} characters long", self.max));
+        }
+        if value.chars().any(|c| c.is_control() && !(self.multiline && matches!(c, '\n' | '\r' | '\t'))) {
+            return Some("must not contain control characters".to_owned());
+        }
+
+        None
+    }
+}
+
+// The upper bounds match the column sizes in the migrations.
+pub const QUESTION_TITLE: TextRule = TextRule::single_line(1, 255);
+pub const QUESTION_DESCRIPTION: TextRule = TextRule::multiline(1, 255);
+pub const ANSWER_CONTENT: TextRule = TextRule::multiline(1, 255);
+pub const COMMENT_CONTENT: TextRule = TextRule::multiline(1, 600);
+pub const EDIT_SUMMARY: TextRule = TextRule::single_line(0, 255);
+
+/// Request bodies whose text fields are stored trimmed.
+pub trait Normalize {
+    fn normalize(self) -> Self;
+}
+
+impl Normalize for Question {
+    fn normalize(self) -> Self {
+        Question {
+            title: self.title.trim().to_owned(),
+            description: self.description.trim().to_owned(),
+            ..self
+        }
+    }
+}
+
+impl Normalize for Answer {
+    fn normalize(self) -> Self {
+        Answer { content: self.content.trim().to_owned(), ..self }
+    }
+}
+
+impl Normalize for Comment {
+    fn normalize(self) -> Self {
+        Comment { content: self.content.trim().to_owned(), ..self }
+    }
+}
+
+/// Request bodies that declare the rules for their fields.
+pub trait Validate {
+    fn validate(&self, validator: &mut Validator);
+}
+
+impl Validate for Question {
+    fn validate(&self, validator: &mut Validator) {
+        validator
+            .text("title", &self.title, &QUESTION_TITLE)
+            .text("description", &self.description, &QUESTION_DESCRIPTION);
+    }
+}
+
+impl Validate for Answer {
+    fn validate(&self, validator: &mut Validator) {
+        validator.text("content", &self.content, &ANSWER_CONTENT);
+    }
+}
+
+impl Validate for Comment {
+    fn validate(&self, validator: &mut Validator) {
+        validator.text("content", &self.content, &COMMENT_CONTENT);
+    }
+}
+
+/// Collects every failing field instead of stopping at the first one.
+#[derive(Default)]
+pub struct Validator {
+    prefix: String,
+    errors: Vec<FieldError>,
+}
+
+impl Validator {
+    pub fn text(&mut self, field: &str, value: &str, rule: &TextRule) -> &mut Self {
+        if let Some(message) = rule.check(value) {
+            self.errors.push(FieldError { field: format!("{}{}", self.prefix, field), message });
+        }
+        self
+    }
+
+    pub fn optional_text(&mut self, field: &str, value: Option<&str>, rule: &TextRule) -> &mut Self {
+        match value {
+            Some(value) => self.text(field, value, rule),
+            None => self,
+        }
+    }
+
+    /// Validates a nested body, reporting its fields as `field.inner_field`.
+    pub fn nested(&mut self, field: &str, value: &impl Validate) -> &mut Self {
+        let mut nested = Validator {
+            prefix: format!("{}{}.", self.prefix, field),
+            errors: Vec::new(),
+        };
+        value.validate(&mut nested);
+
+        self.errors.append(&mut nested.errors);
+        self
+    }
+
+    pub fn finish(&mut self) -> Result<(), HandlerError> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(HandlerError::Validation(std::mem::take(&mut self.errors)))
+        }
+    }
+}
+
+pub fn validate(value: &impl Validate) -> Result<(), HandlerError> {
+    let mut validator = Validator::default();
+    value.validate(&mut validator);
+    validator.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_rule_should_measure_stored_length() {
+        assert_eq!(QUESTION_TITLE.check("   "), Some("must not be blank".to_owned()));
+        assert_eq!(QUESTION_TITLE.check(&"a".repeat(255)), None);
+        assert_eq!(
+            QUESTION_TITLE.check(&format!("  {}  ", "a".repeat(255))),
+            Some("must be at most 255 characters long".to_owned())
+        );
+        assert_eq!(
+            QUESTION_TITLE.check(&"a".repeat(256)),
+            Some("must be at most 255 characters long".to_owned())
+        );
+    }
+
+    #[test]
+    fn normalize_should_trim_text_fields() {
+        let question = Question {
+            title: format!("  {}  ", "a".repeat(255)),
+            description: "\n description \n".to_owned(),
+            tags: vec![],
+        }
+        .normalize();
+
+        assert_eq!(question.title, "a".repeat(255));
+        assert_eq!(question.description, "description");
+        assert!(validate(&question).is_ok());
+    }
+
+    #[test]
+    fn text_rule_should_only_allow_line_breaks_in_multiline_fields() {
+        assert_eq!(QUESTION_DESCRIPTION.check("first line\n\tsecond line"), None);
+        assert!(QUESTION_TITLE.check("first line\nsecond line").is_some());
+        assert!(QUESTION_DESCRIPTION.check("bell\u{7}").is_some());
+    }
+
+    #[test]
+    fn validator_should_report_every_failing_field() {
+        let question = Question {
+            title: "".to_owned(),
+            description: "a".repeat(256),
+            tags: vec![],
+        };
+
+        let result = Validator::default()
+            .nested("updated_entity", &question)
+            .optional_text("edit_summary", Some("fine"), &EDIT_SUMMARY)
+            .finish();
+
+        assert_eq!(
+            result,
+            Err(HandlerError::Validation(vec![
+                FieldError {
+                    field: "updated_entity.title".to_owned(),
+                    message: "must not be blank".to_owned(),
+                },
+                FieldError {
+                    field: "updated_entity.description".to_owned(),
+                    message: "must be at most 255 characters long".to_owned(),
+                },
+            ]))
+        );
+    }
+}
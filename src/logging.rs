@@ -0,0 +1,174 @@
+//! JSON logging. Every line logged while a route runs carries the id of its request, and every request
+//! produces one `access` line once its response is ready.
+
+use std::io::{self, Write};
+use std::time::Instant;
+
+use log::kv::{self, Key, VisitSource};
+use log::Record;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::Header;
+use rocket::route::{Handler, Outcome};
+use rocket::serde::json::serde_json::{Map, Value};
+use rocket::{Data, Request, Response, Route};
+use sqlx::types::Uuid;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// Logs at `RUST_LOG`'s levels, one JSON object per line.
+pub fn init() {
+    env_logger::Builder::from_default_env().format(format_record).init();
+}
+
+fn format_record(buf: &mut env_logger::fmt::Formatter, record: &Record) -> io::Result<()> {
+    let mut line = Map::new();
+    line.insert("timestamp".to_owned(), buf.timestamp_millis().to_string().into());
+    line.insert("level".to_owned(), record.level().as_str().into());
+    line.insert("target".to_owned(), record.target().into());
+    line.insert("message".to_owned(), record.args().to_string().into());
+    if let Ok(request_id) = REQUEST_ID.try_with(Clone::clone) {
+        line.insert("request_id".to_owned(), request_id.into());
+    }
+    // Key-values such as `info!(status = 200; "...")` become fields of their own.
+    let _ = record.key_values().visit(&mut Fields(&mut line));
+
+    writeln!(buf, "{}", Value::Object(line))
+}
+
+struct Fields<'a>(&'a mut Map<String, Value>);
+
+impl<'kvs> VisitSource<'kvs> for Fields<'_> {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: kv::Value<'kvs>) -> Result<(), kv::Error> {
+        let value = value.to_u64().map(Value::from)
+            .or_else(|| value.to_i64().map(Value::from))
+            .or_else(|| value.to_f64().map(Value::from))
+            .or_else(|| value.to_bool().map(Value::from))
+            .unwrap_or_else(|| value.to_string().into());
+        self.0.insert(key.to_string(), value);
+        Ok(())
+    }
+}
+
+/// The id of the current request, taken from its `X-Request-Id` header when that is a sane token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestId(pub String);
+
+impl RequestId {
+    fn from_header(header: Option<&str>) -> Self {
+        let is_valid = |id: &str| {
+            (1..=128).contains(&id.len())
+                && id.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'))
+        };
+
+        match header {
+            Some(id) if is_valid(id) => RequestId(id.to_owned()),
+            _ => RequestId(Uuid::new_v4().to_string()),
+        }
+    }
+
+    pub fn of<'r>(request: &'r Request<'_>) -> &'r RequestId {
+        request.local_cache(|| RequestId::from_header(request.headers().get_one(REQUEST_ID_HEADER)))
+    }
+}
+
+/// Route handler that runs the wrapped route with its request id in scope of the logger.
+#[derive(Clone)]
+struct WithRequestId {
+    inner: Box<dyn Handler>,
+}
+
+#[rocket::async_trait]
+impl Handler for WithRequestId {
+    async fn handle<'r>(&self, request: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        let request_id = RequestId::of(request).0.clone();
+        REQUEST_ID.scope(request_id, self.inner.handle(request, data)).await
+    }
+}
+
+/// Wraps the handlers of `routes` so that everything they log carries the request id.
+pub fn with_request_id(routes: Vec<Route>) -> Vec<Route> {
+    routes
+        .into_iter()
+        .map(|mut route| {
+            route.handler = Box::new(WithRequestId { inner: route.handler });
+            route
+        })
+        .collect()
+}
+
+/// Echoes the request id in the `X-Request-Id` response header and writes the access log.
+pub struct AccessLog;
+
+#[rocket::async_trait]
+impl Fairing for AccessLog {
+    fn info(&self) -> Info {
+        Info {
+            name: "Request ids and access log",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _: &mut Data<'_>) {
+        request.local_cache(Instant::now);
+        RequestId::of(request);
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let request_id = &RequestId::of(request).0;
+        let latency_ms = request.local_cache(Instant::now).elapsed().as_secs_f64() * 1000.0;
+        // Unmatched requests have no route, their path is logged instead.
+        let route = request.route().map(|route| route.uri.to_string()).unwrap_or_else(|| request.uri().path().to_string());
+
+        response.set_header(Header::new(REQUEST_ID_HEADER, request_id.clone()));
+
+        info!(
+            target: "access",
+            method = request.method().as_str(),
+            route = route.as_str(),
+            status = response.status().code,
+            latency_ms = latency_ms,
+            request_id = request_id.as_str();
+            "{} {} {}", request.method(), route, response.status().code
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::http::Status;
+    use rocket::local::asynchronous::Client;
+
+    use super::*;
+
+    #[test]
+    fn request_id_should_only_propagate_sane_headers() {
+        assert_eq!(RequestId::from_header(Some("abc-123")), RequestId("abc-123".to_owned()));
+        assert_ne!(RequestId::from_header(Some("has spaces")).0, "has spaces");
+        assert_ne!(RequestId::from_header(Some(&"a".repeat(129))).0, "a".repeat(129));
+        assert!(Uuid::parse_str(&RequestId::from_header(None).0).is_ok());
+    }
+
+    #[get("/request-id")]
+    async fn request_id() -> String {
+        REQUEST_ID.try_with(Clone::clone).unwrap_or_default()
+    }
+
+    #[rocket::async_test]
+    async fn request_id_should_be_in_scope_and_echoed() {
+        let rocket = rocket::build().mount("/", with_request_id(routes![request_id])).attach(AccessLog);
+        let client = Client::tracked(rocket).await.unwrap();
+
+        let response = client.get("/request-id").header(Header::new(REQUEST_ID_HEADER, "abc-123")).dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.headers().get_one(REQUEST_ID_HEADER), Some("abc-123"));
+        assert_eq!(response.into_string().await.as_deref(), Some("abc-123"));
+
+        let response = client.get("/unknown").dispatch().await;
+        assert_eq!(response.status(), Status::NotFound);
+        assert!(response.headers().get_one(REQUEST_ID_HEADER).is_some());
+    }
+}
@@ -4,50 +4,202 @@ extern crate rocket;
 #[macro_use]
 extern crate log;
 
-extern crate pretty_env_logger;
-
 use dotenvy::dotenv;
+use thiserror::Error;
 
-use persistance::{questions_dao::{QuestionsDaoImpl, QuestionsDao}, answers_dao::{AnswersDaoImpl, AnswersDao}};
-use sqlx::postgres::PgPoolOptions;
+use config::{AppConfig, ConfigError};
+use migrate::MigrateCommand;
+use persistance::{
+    questions_dao::{QuestionsDaoImpl, QuestionsDao}, answers_dao::{AnswersDaoImpl, AnswersDao}, comments_dao::{CommentsDaoImpl, CommentsDao}, users_dao::{UsersDaoImpl, UsersDao},
+    in_memory::{InMemoryAnswersDao, InMemoryCommentsDao, InMemoryQuestionsDao, InMemoryStore, InMemoryUsersDao},
+    metered::Metered,
+    StorageBackend,
+};
+use sqlx::Postgres;
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
 
+mod auth;
+mod config;
 mod cors;
 mod handlers;
+mod logging;
+mod metrics;
+mod migrate;
 mod models;
 mod persistance;
+mod rate_limit;
 
 use cors::*;
 use handlers::*;
+use logging::*;
+use metrics::*;
+use rate_limit::*;
+
+#[derive(Error, Debug)]
+enum StartupError {
+    #[error("{0}")]
+    Config(#[from] ConfigError),
+    #[error("Failed to connect to the database: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("Failed to run migrations: {0}")]
+    Migrate(#[from] sqlx::migrate::MigrateError),
+    #[error("{0}")]
+    Usage(String),
+    #[error("Failed to launch: {0}")]
+    Launch(#[from] rocket::Error),
+}
 
-#[launch]
-async fn rocket() -> _ {
-    pretty_env_logger::init();
+#[rocket::main]
+async fn main() {
+    // `.env` may set `RUST_LOG`, so it has to be loaded before the logger reads it.
     dotenv().ok();
+    logging::init();
+
+    if let Err(err) = run().await {
+        error!("{}", err);
+        std::process::exit(1);
+    }
+}
 
-    let pool = PgPoolOptions::new()
-        .max_connections(5)
-        .connect(&std::env::var("DATABASE_URL").expect("DATABASE_URL must be set."))
-        .await
-        .expect("Failed to create Postgres connection pool!");
+async fn run() -> Result<(), StartupError> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = MigrateCommand::from_args(&args).map_err(StartupError::Usage)?;
 
-    let questions_dao =  QuestionsDaoImpl::new(pool.clone());
-    let answers_dao = AnswersDaoImpl::new(pool.clone());
+    let figment = config::figment();
+    let config = AppConfig::from_figment(&figment)?;
 
-    rocket::build()
+    if let Some(command) = command {
+        return migrate::run(command, &config).await;
+    }
+
+    let metrics = Metrics::new();
+
+    let rocket = rocket::custom(config.rocket_figment(figment))
         .mount(
             "/",
-            routes![
+            with_request_id(rate_limited(routes![
+                register,
+                login,
+                logout,
                 create_question,
                 read_questions,
+                read_question,
+                read_tags,
+                search_questions,
                 delete_question,
+                undelete_question,
                 update_question,
+                vote_question,
+                retract_question_vote,
+                accept_answer,
+                unaccept_answer,
+                read_question_revisions,
+                diff_question_revisions,
+                rollback_question,
                 create_answer,
                 read_answers,
                 delete_answer,
+                undelete_answer,
                 update_answer,
-            ],
+                vote_answer,
+                retract_answer_vote,
+                read_answer_revisions,
+                diff_answer_revisions,
+                rollback_answer,
+                create_comment,
+                read_question_comments,
+                read_answer_comments,
+                delete_comment,
+                update_comment,
+            ])),
         )
-        .attach(CORS)
-        .manage(Box::new(questions_dao) as Box<dyn QuestionsDao + Sync + Send>)
-        .manage(Box::new(answers_dao) as Box<dyn AnswersDao + Sync + Send>)
+        .mount(
+            "/",
+            with_request_id(rate_limited(routes![
+                deprecated::create_question,
+                deprecated::delete_question,
+                deprecated::undelete_question,
+                deprecated::update_question,
+                deprecated::vote_question,
+                deprecated::retract_question_vote,
+                deprecated::accept_answer,
+                deprecated::unaccept_answer,
+                deprecated::read_question_revisions,
+                deprecated::diff_question_revisions,
+                deprecated::rollback_question,
+                deprecated::create_answer,
+                deprecated::read_answers,
+                deprecated::delete_answer,
+                deprecated::undelete_answer,
+                deprecated::update_answer,
+                deprecated::vote_answer,
+                deprecated::retract_answer_vote,
+                deprecated::read_answer_revisions,
+                deprecated::diff_answer_revisions,
+                deprecated::rollback_answer,
+                deprecated::create_comment,
+                deprecated::read_comments,
+                deprecated::delete_comment,
+                deprecated::update_comment,
+            ])),
+        )
+        // Probes are polled constantly, so they aren't rate limited.
+        .mount("/", with_request_id(routes![liveness, readiness]))
+        .mount("/", routes![read_metrics])
+        .mount("/", SwaggerUi::new("/swagger-ui/<_..>").url("/openapi.json", ApiDoc::openapi()))
+        .register("/", catchers![default_catcher])
+        .attach(CORS::new(config.cors.clone()))
+        .attach(RateLimiter::new(config.rate_limit.clone()))
+        .attach(AccessLog)
+        .attach(RequestMetrics)
+        .manage(metrics.clone());
+
+    let rocket = match config.storage_backend {
+        StorageBackend::Postgres => {
+            let pool = config.database.options::<Postgres>().connect(config.database_url()).await?;
+            if config.migrate_on_startup {
+                persistance::MIGRATOR.run(&pool).await?;
+            }
+            metrics.watch_pool(pool.clone());
+
+            rocket
+                .manage(Box::new(Metered::new(QuestionsDaoImpl::new(pool.clone()), &metrics)) as Box<dyn QuestionsDao + Sync + Send>)
+                .manage(Box::new(Metered::new(AnswersDaoImpl::new(pool.clone()), &metrics)) as Box<dyn AnswersDao + Sync + Send>)
+                .manage(Box::new(Metered::new(CommentsDaoImpl::new(pool.clone()), &metrics)) as Box<dyn CommentsDao + Sync + Send>)
+                .manage(Box::new(Metered::new(UsersDaoImpl::new(pool), &metrics)) as Box<dyn UsersDao + Sync + Send>)
+        }
+        #[cfg(feature = "sqlite")]
+        StorageBackend::Sqlite => {
+            use persistance::sqlite::{SqliteAnswersDao, SqliteCommentsDao, SqliteQuestionsDao, SqliteUsersDao};
+            use sqlx::{sqlite::SqliteConnectOptions, Sqlite};
+
+            let options: SqliteConnectOptions = config.database_url().parse()?;
+            let pool = config.database.options::<Sqlite>().connect_with(options.create_if_missing(true)).await?;
+
+            if config.migrate_on_startup {
+                persistance::sqlite::MIGRATOR.run(&pool).await?;
+            }
+            metrics.watch_pool(pool.clone());
+
+            rocket
+                .manage(Box::new(Metered::new(SqliteQuestionsDao::new(pool.clone()), &metrics)) as Box<dyn QuestionsDao + Sync + Send>)
+                .manage(Box::new(Metered::new(SqliteAnswersDao::new(pool.clone()), &metrics)) as Box<dyn AnswersDao + Sync + Send>)
+                .manage(Box::new(Metered::new(SqliteCommentsDao::new(pool.clone()), &metrics)) as Box<dyn CommentsDao + Sync + Send>)
+                .manage(Box::new(Metered::new(SqliteUsersDao::new(pool), &metrics)) as Box<dyn UsersDao + Sync + Send>)
+        }
+        StorageBackend::InMemory => {
+            warn!("Using in-memory storage, all data will be lost on shutdown.");
+            let store = InMemoryStore::new();
+
+            rocket
+                .manage(Box::new(Metered::new(InMemoryQuestionsDao::new(store.clone()), &metrics)) as Box<dyn QuestionsDao + Sync + Send>)
+                .manage(Box::new(Metered::new(InMemoryAnswersDao::new(store.clone()), &metrics)) as Box<dyn AnswersDao + Sync + Send>)
+                .manage(Box::new(Metered::new(InMemoryCommentsDao::new(store.clone()), &metrics)) as Box<dyn CommentsDao + Sync + Send>)
+                .manage(Box::new(Metered::new(InMemoryUsersDao::new(store), &metrics)) as Box<dyn UsersDao + Sync + Send>)
+        }
+    };
+
+    rocket.manage(config).launch().await?;
+    Ok(())
 }
@@ -0,0 +1,170 @@
+//! Prometheus metrics, scraped from `GET /metrics`.
+
+use std::time::Instant;
+
+use prometheus::core::{Collector, Desc};
+use prometheus::proto::MetricFamily;
+use prometheus::{HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts, Registry, TextEncoder};
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::{ContentType, Status};
+use rocket::{Data, Request, Response, State};
+use sqlx::{Database, Pool};
+
+use crate::handlers::APIError;
+
+/// Every metric of the API. Clones share the same registry.
+#[derive(Clone)]
+pub struct Metrics {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_request_duration: HistogramVec,
+    pub dao_call_duration: HistogramVec,
+    pub dao_errors: IntCounterVec,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let http_requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "Requests by method, route and status"),
+            &["method", "route", "status"],
+        ).expect("Metric options are valid");
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new("http_request_duration_seconds", "Time from receiving a request to its response"),
+            &["method", "route"],
+        ).expect("Metric options are valid");
+        let dao_call_duration = HistogramVec::new(
+            HistogramOpts::new("dao_call_duration_seconds", "Duration of DAO calls"),
+            &["dao", "method"],
+        ).expect("Metric options are valid");
+        let dao_errors = IntCounterVec::new(
+            Opts::new("dao_errors_total", "Failed DAO calls by `DBError` kind"),
+            &["dao", "method", "error"],
+        ).expect("Metric options are valid");
+
+        for collector in [
+            Box::new(http_requests.clone()) as Box<dyn Collector>,
+            Box::new(http_request_duration.clone()),
+            Box::new(dao_call_duration.clone()),
+            Box::new(dao_errors.clone()),
+        ] {
+            registry.register(collector).expect("Metric names are unique");
+        }
+
+        Metrics { registry, http_requests, http_request_duration, dao_call_duration, dao_errors }
+    }
+
+    /// Reports the pool's size and idle connections whenever the metrics are scraped.
+    pub fn watch_pool<DB: Database>(&self, pool: Pool<DB>) {
+        let collector = PoolCollector {
+            pool,
+            size: IntGauge::new("db_pool_connections", "Open database connections").expect("Metric options are valid"),
+            idle: IntGauge::new("db_pool_idle_connections", "Idle database connections").expect("Metric options are valid"),
+        };
+
+        self.registry.register(Box::new(collector)).expect("Only one pool is watched");
+    }
+
+    fn encode(&self) -> Result<String, prometheus::Error> {
+        TextEncoder::new().encode_to_string(&self.registry.gather())
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct PoolCollector<DB: Database> {
+    pool: Pool<DB>,
+    size: IntGauge,
+    idle: IntGauge,
+}
+
+impl<DB: Database> Collector for PoolCollector<DB> {
+    fn desc(&self) -> Vec<&Desc> {
+        self.size.desc().into_iter().chain(self.idle.desc()).collect()
+    }
+
+    fn collect(&self) -> Vec<MetricFamily> {
+        self.size.set(i64::from(self.pool.size()));
+        self.idle.set(self.pool.num_idle() as i64);
+
+        self.size.collect().into_iter().chain(self.idle.collect()).collect()
+    }
+}
+
+#[get("/metrics")]
+pub fn read_metrics(metrics: &State<Metrics>) -> Result<(ContentType, String), APIError> {
+    let body = metrics.encode().map_err(|err| {
+        error!("{}", err);
+        APIError::from_status(Status::InternalServerError, "Failed to encode metrics")
+    })?;
+
+    Ok((ContentType::new("text", "plain").with_params(("version", "0.0.4")), body))
+}
+
+/// Counts and times every response by its route.
+pub struct RequestMetrics;
+
+#[rocket::async_trait]
+impl Fairing for RequestMetrics {
+    fn info(&self) -> Info {
+        Info {
+            name: "Request metrics",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _: &mut Data<'_>) {
+        request.local_cache(Instant::now);
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let Some(metrics) = request.rocket().state::<Metrics>() else {
+            return;
+        };
+
+        // Unmatched paths share one label so that scanners can't blow up the number of series.
+        let route = request.route().map(|route| route.uri.to_string()).unwrap_or_else(|| "unmatched".to_owned());
+        let method = request.method().as_str();
+        let elapsed = request.local_cache(Instant::now).elapsed();
+
+        metrics.http_requests.with_label_values(&[method, &route, &response.status().code.to_string()]).inc();
+        metrics.http_request_duration.with_label_values(&[method, &route]).observe(elapsed.as_secs_f64());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::local::asynchronous::Client;
+
+    use super::*;
+
+    #[get("/tags")]
+    fn read_tags() -> &'static str {
+        "tags"
+    }
+
+    #[rocket::async_test]
+    async fn metrics_should_count_requests_by_route() {
+        let rocket = rocket::build()
+            .mount("/", routes![read_tags, read_metrics])
+            .manage(Metrics::new())
+            .attach(RequestMetrics);
+        let client = Client::tracked(rocket).await.unwrap();
+
+        client.get("/tags").dispatch().await;
+        client.get("/unknown/1").dispatch().await;
+
+        let response = client.get("/metrics").dispatch().await;
+        assert_eq!(response.content_type(), Some(ContentType::new("text", "plain").with_params(("version", "0.0.4"))));
+
+        let body = response.into_string().await.unwrap();
+        assert!(body.contains(r#"http_requests_total{method="GET",route="/tags",status="200"} 1"#));
+        assert!(body.contains(r#"http_requests_total{method="GET",route="unmatched",status="404"} 1"#));
+        assert!(body.contains(r#"http_request_duration_seconds_count{method="GET",route="/tags"} 1"#));
+    }
+}
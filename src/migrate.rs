@@ -0,0 +1,143 @@
+//! `migrate up|down|status` subcommands, which manage the schema with the migrations bundled into the binary.
+
+use sqlx::{
+    migrate::{Migrate, MigrateError, Migrator},
+    Database, Pool, Postgres,
+};
+
+use crate::{config::AppConfig, persistance, persistance::StorageBackend, StartupError};
+
+const USAGE: &str = "Usage: rust-stackoverflow-api [migrate up|down|status]";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MigrateCommand {
+    /// Applies every pending migration.
+    Up,
+    /// Reverts the latest applied migration.
+    Down,
+    /// Lists the migrations and whether they're applied.
+    Status,
+}
+
+impl MigrateCommand {
+    /// Parses the arguments after the program name, `None` meaning that the server should start.
+    pub fn from_args(args: &[String]) -> Result<Option<Self>, String> {
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+        match args.as_slice() {
+            [] => Ok(None),
+            ["migrate", "up"] => Ok(Some(MigrateCommand::Up)),
+            ["migrate", "down"] => Ok(Some(MigrateCommand::Down)),
+            ["migrate", "status"] => Ok(Some(MigrateCommand::Status)),
+            _ => Err(USAGE.to_owned()),
+        }
+    }
+}
+
+pub async fn run(command: MigrateCommand, config: &AppConfig) -> Result<(), StartupError> {
+    match config.storage_backend {
+        StorageBackend::Postgres => {
+            let pool = config.database.options::<Postgres>().connect(config.database_url()).await?;
+            migrate(&persistance::MIGRATOR, &pool, command).await?;
+        }
+        #[cfg(feature = "sqlite")]
+        StorageBackend::Sqlite => {
+            use sqlx::{sqlite::SqliteConnectOptions, Sqlite};
+
+            let options: SqliteConnectOptions = config.database_url().parse()?;
+            let pool = config.database.options::<Sqlite>().connect_with(options.create_if_missing(true)).await?;
+            migrate(&persistance::sqlite::MIGRATOR, &pool, command).await?;
+        }
+        StorageBackend::InMemory => {
+            return Err(StartupError::Usage("The memory backend has no migrations".to_owned()));
+        }
+    }
+
+    Ok(())
+}
+
+async fn migrate<DB>(migrator: &Migrator, pool: &Pool<DB>, command: MigrateCommand) -> Result<(), MigrateError>
+where
+    DB: Database,
+    DB::Connection: Migrate,
+{
+    let applied = applied_versions(pool).await?;
+    let migrations = migrator.iter().filter(|migration| !migration.migration_type.is_down_migration());
+
+    match command {
+        MigrateCommand::Up => {
+            let pending = migrations.filter(|migration| !applied.contains(&migration.version)).count();
+            migrator.run(pool).await?;
+            println!("Applied {} migration(s)", pending);
+        }
+        MigrateCommand::Down => {
+            let Some((&latest, earlier)) = applied.split_last() else {
+                println!("No migration to revert");
+                return Ok(());
+            };
+            migrator.undo(pool, earlier.last().copied().unwrap_or(0)).await?;
+            println!("Reverted migration {}", latest);
+        }
+        MigrateCommand::Status => {
+            for migration in migrations {
+                let status = if applied.contains(&migration.version) { "applied" } else { "pending" };
+                println!("{} {:<8} {}", migration.version, status, migration.description);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Versions of the applied migrations in ascending order.
+async fn applied_versions<DB>(pool: &Pool<DB>) -> Result<Vec<i64>, MigrateError>
+where
+    DB: Database,
+    DB::Connection: Migrate,
+{
+    let mut conn = pool.acquire().await?;
+    conn.ensure_migrations_table().await?;
+
+    let mut versions: Vec<i64> = conn.list_applied_migrations().await?.into_iter().map(|migration| migration.version).collect();
+    versions.sort();
+    Ok(versions)
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::PgPool;
+
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn from_args_should_parse_subcommands() {
+        assert_eq!(MigrateCommand::from_args(&args(&[])), Ok(None));
+        assert_eq!(MigrateCommand::from_args(&args(&["migrate", "down"])), Ok(Some(MigrateCommand::Down)));
+        assert!(MigrateCommand::from_args(&args(&["migrate"])).is_err());
+        assert!(MigrateCommand::from_args(&args(&["serve"])).is_err());
+    }
+
+    #[sqlx::test(migrations = false)]
+    async fn migrate_should_apply_and_revert_migrations(pool: PgPool) -> Result<(), MigrateError> {
+        let versions: Vec<i64> = persistance::MIGRATOR
+            .iter()
+            .filter(|migration| !migration.migration_type.is_down_migration())
+            .map(|migration| migration.version)
+            .collect();
+
+        migrate(&persistance::MIGRATOR, &pool, MigrateCommand::Up).await?;
+        assert_eq!(applied_versions(&pool).await?, versions);
+
+        migrate(&persistance::MIGRATOR, &pool, MigrateCommand::Down).await?;
+        assert_eq!(applied_versions(&pool).await?, versions[..versions.len() - 1]);
+
+        migrate(&persistance::MIGRATOR, &pool, MigrateCommand::Up).await?;
+        assert_eq!(applied_versions(&pool).await?, versions);
+
+        Ok(())
+    }
+}
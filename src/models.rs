@@ -1,62 +1,507 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use thiserror::Error;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use sqlx::types::{
+    time::{OffsetDateTime, PrimitiveDateTime},
+    Uuid,
+};
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, ToSchema, Clone)]
 pub struct UpdateRequest<T> {
     pub updated_entity: T,
     pub uuid: String,
+    /// Short description of the edit, kept in the post's revision history.
+    pub edit_summary: Option<String>,
 }
-#[derive(Serialize, Deserialize)]
+
+/// Body of the `PUT` routes that take the post's uuid from the path.
+#[derive(Serialize, Deserialize, ToSchema, Clone)]
+pub struct Edit<T> {
+    pub updated_entity: T,
+    pub edit_summary: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct Question {
     pub title: String,
     pub description: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[derive(Serialize, Deserialize, ToSchema, Debug, PartialEq, Clone)]
 pub struct QuestionDetail {
     pub question_uuid: String,
     pub title: String,
     pub description: String,
+    pub author_uuid: Option<String>,
+    pub accepted_answer_uuid: Option<String>,
+    pub tags: Vec<String>,
+    pub score: i64,
     pub created_at: String,
+    pub deleted_at: Option<String>,
+    pub deleted_by: Option<String>,
 }
 
-#[derive(Serialize, Deserialize)]
+/// A single question, with its answers when they were asked for.
+#[derive(Serialize, Deserialize, ToSchema, Debug, PartialEq, Clone)]
+pub struct QuestionWithAnswers {
+    #[serde(flatten)]
+    pub question: QuestionDetail,
+    /// The accepted answer first, then the others oldest first.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answers: Option<Vec<AnswerDetail>>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct QuestionId {
     pub question_uuid: String,
 }
 
+#[derive(Serialize, Deserialize, ToSchema, Debug, PartialEq, Clone)]
+pub struct QuestionSearchResult {
+    pub question: QuestionDetail,
+    pub rank: f32,
+    /// Question title and description as escaped HTML, with matching terms wrapped in `<mark>` tags.
+    pub snippet: String,
+    /// Best matching answer content, escaped and highlighted like `snippet`, if an answer matched the query.
+    pub answer_snippet: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, PartialEq, Clone)]
+pub struct TagDetail {
+    pub name: String,
+    pub question_count: i64,
+}
+
 // ----------
 
-#[derive(Serialize, Deserialize,Clone)]
+#[derive(Serialize, Deserialize, ToSchema, Clone)]
 pub struct Answer {
     pub question_uuid: String,
     pub content: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, PartialEq)]
 pub struct AnswerDetail {
     pub answer_uuid: String,
     pub question_uuid: String,
     pub content: String,
+    pub author_uuid: Option<String>,
+    pub score: i64,
+    pub is_accepted: bool,
     pub created_at: String,
+    pub deleted_at: Option<String>,
+    pub deleted_by: Option<String>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct AnswerId {
     pub answer_uuid: String,
 }
 
 // ----------
 
+/// A question as it was before an edit, with the user who made that edit.
+#[derive(Serialize, Deserialize, ToSchema, Debug, PartialEq, Clone)]
+pub struct QuestionRevision {
+    pub revision_uuid: String,
+    pub question_uuid: String,
+    pub title: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    pub editor_uuid: Option<String>,
+    pub edit_summary: Option<String>,
+    pub created_at: String,
+}
+
+/// An answer as it was before an edit, with the user who made that edit.
+#[derive(Serialize, Deserialize, ToSchema, Debug, PartialEq, Clone)]
+pub struct AnswerRevision {
+    pub revision_uuid: String,
+    pub answer_uuid: String,
+    pub question_uuid: String,
+    pub content: String,
+    pub editor_uuid: Option<String>,
+    pub edit_summary: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffOp {
+    Equal,
+    Insert,
+    Delete,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, PartialEq, Clone)]
+pub struct DiffChange {
+    pub op: DiffOp,
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, PartialEq, Clone)]
+pub struct FieldDiff {
+    pub field: String,
+    pub changes: Vec<DiffChange>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, PartialEq, Clone)]
+pub struct RevisionDiff {
+    pub from_revision_uuid: String,
+    pub to_revision_uuid: String,
+    pub fields: Vec<FieldDiff>,
+}
+
+// ----------
+
+/// The post a comment is attached to; exactly one of the two must be set.
+#[derive(Serialize, Deserialize, ToSchema, Debug, PartialEq, Clone, Default)]
+pub struct CommentParent {
+    pub question_uuid: Option<String>,
+    pub answer_uuid: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone)]
+pub struct Comment {
+    #[serde(flatten)]
+    pub parent: CommentParent,
+    pub content: String,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, PartialEq)]
+pub struct CommentDetail {
+    pub comment_uuid: String,
+    pub question_uuid: Option<String>,
+    pub answer_uuid: Option<String>,
+    pub content: String,
+    pub author_uuid: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct CommentId {
+    pub comment_uuid: String,
+}
+
+// ----------
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum VoteDirection {
+    Up,
+    Down,
+}
+
+impl VoteDirection {
+    pub fn value(&self) -> i16 {
+        match self {
+            VoteDirection::Up => 1,
+            VoteDirection::Down => -1,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct Vote {
+    pub direction: VoteDirection,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, PartialEq, Clone)]
+pub struct Score {
+    pub score: i64,
+}
+
+// ----------
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    User,
+    Moderator,
+    Admin,
+}
+
+impl std::str::FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "user" => Ok(Role::User),
+            "moderator" => Ok(Role::Moderator),
+            "admin" => Ok(Role::Admin),
+            _ => Err(format!("Unknown role: {}", s)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, PartialEq, Clone)]
+pub struct UserDetail {
+    pub user_uuid: String,
+    pub username: String,
+    pub role: Role,
+    pub created_at: String,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct UserCredentials {
+    pub user_uuid: String,
+    pub password_hash: String,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, PartialEq, Clone)]
+pub struct Session {
+    pub token: String,
+    pub user_uuid: String,
+    pub expires_at: String,
+}
+
+// ----------
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, PartialEq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+/// Position of the last row of a page, used for keyset pagination on `(created_at, uuid)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cursor {
+    pub created_at: PrimitiveDateTime,
+    pub uuid: Uuid,
+}
+
+impl Cursor {
+    /// Cursor positioned before every row, as every row was created after the Unix epoch.
+    pub fn start() -> Self {
+        let epoch = OffsetDateTime::UNIX_EPOCH;
+
+        Cursor {
+            created_at: PrimitiveDateTime::new(epoch.date(), epoch.time()),
+            uuid: Uuid::nil(),
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        let micros = self.created_at.assume_utc().unix_timestamp_nanos() / 1_000;
+        URL_SAFE_NO_PAD.encode(format!("{}_{}", micros, self.uuid.simple()))
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let decoded = String::from_utf8(URL_SAFE_NO_PAD.decode(cursor).ok()?).ok()?;
+        let (micros, uuid) = decoded.split_once('_')?;
+
+        let micros: i128 = micros.parse().ok()?;
+        let created_at = OffsetDateTime::from_unix_timestamp_nanos(micros * 1_000).ok()?;
+
+        Some(Cursor {
+            created_at: PrimitiveDateTime::new(created_at.date(), created_at.time()),
+            uuid: Uuid::parse_str(uuid).ok()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageRequest {
+    pub limit: i64,
+    pub cursor: Option<Cursor>,
+}
+
+impl PageRequest {
+    pub const DEFAULT_LIMIT: i64 = 20;
+    pub const MAX_LIMIT: i64 = 100;
+}
+
+impl Default for PageRequest {
+    fn default() -> Self {
+        PageRequest {
+            limit: Self::DEFAULT_LIMIT,
+            cursor: None,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Up,
+    Down,
+    /// The dependency couldn't be checked, which doesn't make the API unready on its own.
+    Unknown,
+}
+
+/// Outcome of checking one dependency of the API.
+#[derive(Serialize, ToSchema, Debug, Clone, PartialEq)]
+pub struct HealthCheck {
+    pub name: String,
+    pub status: HealthStatus,
+    /// Why the dependency is down or couldn't be checked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Versions of the migrations the database hasn't applied, only set on the `migrations` check.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pending_migrations: Option<Vec<i64>>,
+}
+
+impl HealthCheck {
+    pub fn new(name: &str, status: HealthStatus, detail: Option<String>) -> Self {
+        HealthCheck { name: name.to_owned(), status, detail, pending_migrations: None }
+    }
+}
+
+#[derive(Serialize, ToSchema, Debug, Clone, PartialEq)]
+pub struct HealthReport {
+    /// `down` when any check is down, `up` otherwise.
+    pub status: HealthStatus,
+    pub checks: Vec<HealthCheck>,
+}
+
+impl HealthReport {
+    pub fn from_checks(checks: Vec<HealthCheck>) -> Self {
+        let status = if checks.iter().any(|check| check.status == HealthStatus::Down) {
+            HealthStatus::Down
+        } else {
+            HealthStatus::Up
+        };
+
+        HealthReport { status, checks }
+    }
+}
+
+/// How the database's schema compares to the migrations bundled with the binary.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MigrationStatus {
+    UpToDate,
+    /// Versions of the bundled migrations the database hasn't applied yet.
+    Pending(Vec<i64>),
+    /// The database has no `_sqlx_migrations` table, so it was migrated by other means if at all.
+    Untracked,
+}
+
+// ----------
+
 #[derive(Error, Debug)]
 pub enum DBError {
     #[error("Invalid UUID provided: {0}")]
     InvalidUUID(String),
+    #[error("Username is already taken: {0}")]
+    DuplicateUsername(String),
+    #[error("{message}")]
+    NotFound {
+        /// Domain specific code clients can tell missing resources apart by, e.g. `answer_not_found`.
+        code: &'static str,
+        message: String,
+        #[source]
+        source: Option<sqlx::Error>,
+    },
+    #[error("{message}")]
+    Conflict {
+        code: &'static str,
+        message: String,
+        #[source]
+        source: Option<sqlx::Error>,
+    },
+    #[error("Database is unavailable")]
+    Unavailable {
+        #[source]
+        source: sqlx::Error,
+    },
     #[error("Database error occurred")]
     Other(#[from] Box<dyn std::error::Error + Send + Sync>),
 }
 
+impl DBError {
+    /// Name of the variant, used as a metrics label.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            DBError::InvalidUUID(_) => "invalid_uuid",
+            DBError::DuplicateUsername(_) => "duplicate_username",
+            DBError::NotFound { .. } => "not_found",
+            DBError::Conflict { .. } => "conflict",
+            DBError::Unavailable { .. } => "unavailable",
+            DBError::Other(_) => "other",
+        }
+    }
+
+    pub fn not_found(code: &'static str, message: impl Into<String>) -> Self {
+        DBError::NotFound { code, message: message.into(), source: None }
+    }
+
+    /// Classifies a sqlx error by what the caller can do about it. Errors without a more specific meaning
+    /// become `Other` with the given message. Not found and conflict errors get the generic `not_found`
+    /// and `conflict` codes, callers expecting them build their own.
+    pub fn from_sqlx(err: sqlx::Error, message: &str) -> Self {
+        let code = err.as_database_error().and_then(|e| e.code()).map(|code| code.into_owned());
+
+        match (&err, code.as_deref()) {
+            (sqlx::Error::RowNotFound, _) => DBError::NotFound { code: "not_found", message: message.to_owned(), source: Some(err) },
+            (sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed | sqlx::Error::Io(_) | sqlx::Error::Tls(_), _) => {
+                DBError::Unavailable { source: err }
+            }
+            (_, Some(code)) if is_conflict(code) => {
+                DBError::Conflict { code: "conflict", message: message.to_owned(), source: Some(err) }
+            }
+            (_, Some(code)) if is_unavailable(code) => {
+                DBError::Unavailable { source: err }
+            }
+            _ => DBError::Other(message.into()),
+        }
+    }
+}
+
 // source: https://www.postgresql.org/docs/current/errcodes-appendix.html
 pub mod postgres_error_codes {
     pub const FOREIGN_KEY_VIOLATION: &str = "23503";
+    pub const UNIQUE_VIOLATION: &str = "23505";
+    pub const SERIALIZATION_FAILURE: &str = "40001";
+    pub const UNDEFINED_TABLE: &str = "42P01";
+    pub const DEADLOCK_DETECTED: &str = "40P01";
+    pub const TOO_MANY_CONNECTIONS: &str = "53300";
+    pub const ADMIN_SHUTDOWN: &str = "57P01";
+    pub const CRASH_SHUTDOWN: &str = "57P02";
+    pub const CANNOT_CONNECT_NOW: &str = "57P03";
+
+    /// Codes for writes that clash with the current state or a concurrent transaction.
+    pub const CONFLICT: &[&str] = &[UNIQUE_VIOLATION, SERIALIZATION_FAILURE, DEADLOCK_DETECTED];
+    /// Codes for a server that can't take the query right now but may later.
+    pub const UNAVAILABLE: &[&str] = &[TOO_MANY_CONNECTIONS, ADMIN_SHUTDOWN, CRASH_SHUTDOWN, CANNOT_CONNECT_NOW];
+}
+
+fn is_conflict(code: &str) -> bool {
+    #[cfg(feature = "sqlite")]
+    if sqlite_error_codes::CONFLICT.contains(&code) {
+        return true;
+    }
+
+    postgres_error_codes::CONFLICT.contains(&code)
+}
+
+fn is_unavailable(code: &str) -> bool {
+    #[cfg(feature = "sqlite")]
+    if sqlite_error_codes::UNAVAILABLE.contains(&code) {
+        return true;
+    }
+
+    postgres_error_codes::UNAVAILABLE.contains(&code)
+}
+
+// source: https://www.sqlite.org/rescode.html#extrc
+#[cfg(feature = "sqlite")]
+pub mod sqlite_error_codes {
+    pub const FOREIGN_KEY_VIOLATION: &str = "787";
+    pub const UNIQUE_VIOLATION: &str = "2067";
+    pub const PRIMARY_KEY_VIOLATION: &str = "1555";
+    pub const BUSY: &str = "5";
+    pub const LOCKED: &str = "6";
+
+    pub const CONFLICT: &[&str] = &[UNIQUE_VIOLATION, PRIMARY_KEY_VIOLATION];
+    pub const UNAVAILABLE: &[&str] = &[BUSY, LOCKED];
 }
@@ -1,14 +1,44 @@
 use async_trait::async_trait;
-use sqlx::PgPool;
+use sqlx::{types::Uuid, PgPool};
 
-use crate::models::{postgres_error_codes, Answer, AnswerDetail, DBError};
+use crate::models::{postgres_error_codes, Answer, AnswerDetail, AnswerRevision, Cursor, DBError, MigrationStatus, Page, PageRequest};
+
+use super::{ensure_answer_not_deleted, ensure_question_not_deleted, postgres_health_check};
 
 #[async_trait]
 pub trait AnswersDao {
-    async fn create_answer(&self, answer: Answer) -> Result<AnswerDetail, DBError>;
-    async fn delete_answer(&self, answer_uuid: String) -> Result<(), DBError>;
-    async fn get_answers(&self, question_uuid: String) -> Result<Vec<AnswerDetail>, DBError>;
-    async fn update_answer(&self, updated_answer: Answer, answer_uuid: String) -> Result<AnswerDetail, DBError>;
+    /// Deleted questions can't be answered.
+    async fn create_answer(&self, answer: Answer, author_uuid: String) -> Result<AnswerDetail, DBError>;
+    /// Soft deletes the answer; it stays hidden from listings and search until it's undeleted.
+    async fn delete_answer(&self, answer_uuid: String, deleted_by: String) -> Result<(), DBError>;
+    async fn undelete_answer(&self, answer_uuid: String) -> Result<(), DBError>;
+    /// Deleted answers, and all answers of a deleted question, are only listed when `include_deleted` is set.
+    async fn get_answers(&self, question_uuid: String, page: PageRequest, include_deleted: bool) -> Result<Page<AnswerDetail>, DBError>;
+    /// Returns `None` if the answer does not exist, or it or its question is deleted and `include_deleted` isn't set.
+    async fn get_answer(&self, answer_uuid: String, include_deleted: bool) -> Result<Option<AnswerDetail>, DBError>;
+    /// Saves the answer's current content as a revision attributed to `editor_uuid`, then applies the update.
+    async fn update_answer(
+        &self,
+        updated_answer: Answer,
+        answer_uuid: String,
+        editor_uuid: String,
+        edit_summary: Option<String>,
+    ) -> Result<AnswerDetail, DBError>;
+    /// Returns `None` if the answer has no author and `DBError::NotFound` if it does not exist, or it or its
+    /// question is deleted and `include_deleted` isn't set.
+    async fn get_answer_author(&self, answer_uuid: String, include_deleted: bool) -> Result<Option<String>, DBError>;
+    /// Records or replaces the user's vote and returns the answer's new score. Deleted answers can't be voted on.
+    async fn vote_answer(&self, answer_uuid: String, user_uuid: String, value: i16) -> Result<i64, DBError>;
+    /// Removes the user's vote, if any, and returns the answer's new score.
+    async fn retract_answer_vote(&self, answer_uuid: String, user_uuid: String) -> Result<i64, DBError>;
+    /// Fails with `DBError::NotFound` if the answer or its question is deleted.
+    async fn answer_belongs_to_question(&self, answer_uuid: String, question_uuid: String) -> Result<bool, DBError>;
+    /// Lists the answer's revisions, newest first.
+    async fn get_answer_revisions(&self, answer_uuid: String) -> Result<Vec<AnswerRevision>, DBError>;
+    /// Returns `None` if the revision does not exist or belongs to another answer.
+    async fn get_answer_revision(&self, answer_uuid: String, revision_uuid: String) -> Result<Option<AnswerRevision>, DBError>;
+    /// Pings the database and reports whether its migrations are up to date.
+    async fn health_check(&self) -> Result<MigrationStatus, DBError>;
 }
 
 pub struct AnswersDaoImpl {
@@ -19,26 +49,40 @@ impl AnswersDaoImpl {
     pub fn new(db: PgPool) -> Self {
         Self { db }
     }
+
+    async fn get_score(&self, answer_uuid: Uuid) -> Result<i64, DBError> {
+        let record = sqlx::query!(
+            r#"SELECT COALESCE(SUM(value), 0) AS "score!" FROM votes WHERE answer_uuid = $1"#,
+            answer_uuid
+        ).fetch_one(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting answer score"))?;
+
+        Ok(record.score)
+    }
 }
 
 #[async_trait]
 impl AnswersDao for AnswersDaoImpl {
-    async fn create_answer(&self, answer: Answer) -> Result<AnswerDetail, DBError> {
+    async fn create_answer(&self, answer: Answer, author_uuid: String) -> Result<AnswerDetail, DBError> {
 
         let uuid = sqlx::types::Uuid::parse_str(&answer.question_uuid)
             .map_err(|_| DBError::InvalidUUID(answer.question_uuid.clone()))?;
+        let author_uuid = sqlx::types::Uuid::parse_str(&author_uuid)
+            .map_err(|_| DBError::InvalidUUID(author_uuid.clone()))?;
+
+        ensure_question_not_deleted(&self.db, uuid).await?;
 
         let record = sqlx::query!(
-            "INSERT INTO answers ( question_uuid, content )
-            VALUES ( $1, $2 )
-            RETURNING *",
+            "INSERT INTO answers ( question_uuid, content, author_uuid )
+            VALUES ( $1, $2, $3 )
+            RETURNING answer_uuid, question_uuid, content, author_uuid, created_at",
             uuid,
-            answer.content
+            answer.content,
+            author_uuid
         ).fetch_one(&self.db).await.map_err(|e| {
             if e.as_database_error().map(|e| e.code().expect("Error reading &dyn DatabaseError code").to_string()) == Some(postgres_error_codes::FOREIGN_KEY_VIOLATION.to_string()) {
                 DBError::InvalidUUID(answer.question_uuid.clone())
             } else {
-                DBError::Other("Error creating answer".into())
+                DBError::from_sqlx(e, "Error creating answer")
             }
         })?;
 
@@ -46,52 +90,300 @@ impl AnswersDao for AnswersDaoImpl {
             answer_uuid: record.answer_uuid.to_string(),
             question_uuid: record.question_uuid.to_string(),
             content: record.content,
+            author_uuid: record.author_uuid.map(|uuid| uuid.to_string()),
+            score: 0,
+            is_accepted: false,
             created_at: record.created_at.to_string(),
+            deleted_at: None,
+            deleted_by: None,
         })
     }
 
-    async fn delete_answer(&self, answer_uuid: String) -> Result<(), DBError> {
+    async fn delete_answer(&self, answer_uuid: String, deleted_by: String) -> Result<(), DBError> {
         let uuid = sqlx::types::Uuid::parse_str(&answer_uuid) 
                         .map_err(|_| DBError::InvalidUUID(answer_uuid.clone()))?;
+        let deleted_by = Uuid::parse_str(&deleted_by).map_err(|_| DBError::InvalidUUID(deleted_by.clone()))?;
 
-        sqlx::query!("DELETE FROM answers WHERE answer_uuid = $1", uuid)
-            .execute(&self.db).await.map_err(|_| DBError::Other("Error deleting answer".into()))?;
+        let deleted = sqlx::query!(
+            "UPDATE answers SET deleted_at = CURRENT_TIMESTAMP, deleted_by = $2
+            WHERE answer_uuid = $1 AND deleted_at IS NULL",
+            uuid,
+            deleted_by
+        ).execute(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error deleting answer"))?;
+
+        if deleted.rows_affected() == 0 {
+            return Err(DBError::not_found("answer_not_found", "Answer not found"));
+        }
 
         Ok(())
     }
 
-    async fn get_answers(&self, question_uuid: String) -> Result<Vec<AnswerDetail>, DBError> {
+    async fn undelete_answer(&self, answer_uuid: String) -> Result<(), DBError> {
+        let uuid = Uuid::parse_str(&answer_uuid).map_err(|_| DBError::InvalidUUID(answer_uuid.clone()))?;
+
+        let undeleted = sqlx::query!("UPDATE answers SET deleted_at = NULL, deleted_by = NULL WHERE answer_uuid = $1", uuid)
+            .execute(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error undeleting answer"))?;
+
+        if undeleted.rows_affected() == 0 {
+            return Err(DBError::not_found("answer_not_found", "Answer not found"));
+        }
+
+        Ok(())
+    }
+
+    async fn get_answers(&self, question_uuid: String, page: PageRequest, include_deleted: bool) -> Result<Page<AnswerDetail>, DBError> {
         let uuid = sqlx::types::Uuid::parse_str(&question_uuid).map_err(|_| DBError::InvalidUUID(question_uuid.clone()))?;
 
-        let records = sqlx::query!("SELECT * FROM answers WHERE question_uuid = $1", uuid)
-            .fetch_all(&self.db).await.map_err(|_| DBError::Other("Error getting answers".into()))?;
+        // The accepted answer is pinned to the top of the first page and left out of the keyset order,
+        // the remaining answers follow oldest first. One extra row is fetched to know whether another page exists.
+        let mut records = sqlx::query!(
+            r#"SELECT answers.answer_uuid, answers.question_uuid, answers.content, answers.author_uuid, answers.created_at,
+                answers.deleted_at, answers.deleted_by,
+                (SELECT COALESCE(SUM(value), 0) FROM votes WHERE votes.answer_uuid = answers.answer_uuid) AS "score!",
+                answers.answer_uuid IS NOT DISTINCT FROM questions.accepted_answer_uuid AS "is_accepted!"
+            FROM answers
+            JOIN questions ON questions.question_uuid = answers.question_uuid
+            WHERE answers.question_uuid = $1
+                AND ($5 OR (answers.deleted_at IS NULL AND questions.deleted_at IS NULL))
+                AND CASE WHEN answers.answer_uuid IS NOT DISTINCT FROM questions.accepted_answer_uuid
+                    THEN $2::timestamp IS NULL
+                    ELSE $2::timestamp IS NULL OR (answers.created_at, answers.answer_uuid) > ($2, $3::uuid)
+                END
+            ORDER BY "is_accepted!" DESC, answers.created_at, answers.answer_uuid
+            LIMIT $4"#,
+            uuid,
+            page.cursor.map(|cursor| cursor.created_at),
+            page.cursor.map(|cursor| cursor.uuid),
+            page.limit + 1,
+            include_deleted
+        ).fetch_all(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting answers"))?;
+
+        let next_cursor = if records.len() as i64 > page.limit {
+            records.truncate(page.limit as usize);
+            records.last().map(|record| {
+                if record.is_accepted {
+                    Cursor::start()
+                } else {
+                    Cursor { created_at: record.created_at, uuid: record.answer_uuid }
+                }.encode()
+            })
+        } else {
+            None
+        };
 
         let answers = records.iter().map(|record| {
             AnswerDetail {
                 answer_uuid: record.answer_uuid.to_string(),
                 question_uuid: record.question_uuid.to_string(),
                 content: record.content.clone(),
+                author_uuid: record.author_uuid.map(|uuid| uuid.to_string()),
+                score: record.score,
+                is_accepted: record.is_accepted,
                 created_at: record.created_at.to_string(),
+                deleted_at: record.deleted_at.map(|deleted_at| deleted_at.to_string()),
+                deleted_by: record.deleted_by.map(|uuid| uuid.to_string()),
             }
         }).collect();
 
-        Ok(answers)
+        Ok(Page { items: answers, next_cursor })
+    }
+
+    async fn get_answer(&self, answer_uuid: String, include_deleted: bool) -> Result<Option<AnswerDetail>, DBError> {
+        let uuid = sqlx::types::Uuid::parse_str(&answer_uuid).map_err(|_| DBError::InvalidUUID(answer_uuid.clone()))?;
+
+        let record = sqlx::query!(
+            r#"SELECT answers.answer_uuid, answers.question_uuid, answers.content, answers.author_uuid, answers.created_at,
+                answers.deleted_at, answers.deleted_by,
+                (SELECT COALESCE(SUM(value), 0) FROM votes WHERE votes.answer_uuid = answers.answer_uuid) AS "score!",
+                answers.answer_uuid IS NOT DISTINCT FROM questions.accepted_answer_uuid AS "is_accepted!"
+            FROM answers
+            JOIN questions ON questions.question_uuid = answers.question_uuid
+            WHERE answers.answer_uuid = $1
+                AND ($2 OR (answers.deleted_at IS NULL AND questions.deleted_at IS NULL))"#,
+            uuid,
+            include_deleted
+        ).fetch_optional(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting answer"))?;
+
+        Ok(record.map(|record| AnswerDetail {
+            answer_uuid: record.answer_uuid.to_string(),
+            question_uuid: record.question_uuid.to_string(),
+            content: record.content,
+            author_uuid: record.author_uuid.map(|uuid| uuid.to_string()),
+            score: record.score,
+            is_accepted: record.is_accepted,
+            created_at: record.created_at.to_string(),
+            deleted_at: record.deleted_at.map(|deleted_at| deleted_at.to_string()),
+            deleted_by: record.deleted_by.map(|uuid| uuid.to_string()),
+        }))
     }
 
-    async fn update_answer(&self, updated_answer: Answer, answer_uuid: String) -> Result<AnswerDetail, DBError> {
+    async fn update_answer(
+        &self,
+        updated_answer: Answer,
+        answer_uuid: String,
+        editor_uuid: String,
+        edit_summary: Option<String>,
+    ) -> Result<AnswerDetail, DBError> {
         let uuid = sqlx::types::Uuid::parse_str(&answer_uuid).map_err(|_| DBError::InvalidUUID(answer_uuid.clone()))?;
+        let editor_uuid = Uuid::parse_str(&editor_uuid).map_err(|_| DBError::InvalidUUID(editor_uuid.clone()))?;
+
+        let mut tx = self.db.begin().await.map_err(|e| DBError::from_sqlx(e, "Error updating answer"))?;
+
+        sqlx::query!(
+            "INSERT INTO answer_revisions ( answer_uuid, content, editor_uuid, edit_summary )
+            SELECT answer_uuid, content, $2, $3 FROM answers WHERE answer_uuid = $1",
+            uuid,
+            editor_uuid,
+            edit_summary
+        ).execute(&mut tx).await.map_err(|e| DBError::from_sqlx(e, "Error saving answer revision"))?;
 
         let record = sqlx::query!(
-            "UPDATE answers SET content = $1 WHERE answer_uuid = $2 RETURNING *",
+            r#"UPDATE answers SET content = $1 WHERE answer_uuid = $2
+            RETURNING answer_uuid, question_uuid, content, author_uuid, created_at, deleted_at, deleted_by,
+                (SELECT COALESCE(SUM(value), 0) FROM votes WHERE votes.answer_uuid = answers.answer_uuid) AS "score!",
+                EXISTS(SELECT 1 FROM questions WHERE questions.accepted_answer_uuid = answers.answer_uuid) AS "is_accepted!""#,
             updated_answer.content,
             uuid
-        ).fetch_one(&self.db).await.map_err(|_| DBError::Other("Error updating answer".into()))?;
+        ).fetch_optional(&mut tx).await.map_err(|e| DBError::from_sqlx(e, "Error updating answer"))?
+        .ok_or_else(|| DBError::not_found("answer_not_found", "Answer not found"))?;
+
+        tx.commit().await.map_err(|e| DBError::from_sqlx(e, "Error updating answer"))?;
 
         Ok(AnswerDetail {
             answer_uuid: record.answer_uuid.to_string(),
             question_uuid: record.question_uuid.to_string(),
             content: record.content,
+            author_uuid: record.author_uuid.map(|uuid| uuid.to_string()),
+            score: record.score,
+            is_accepted: record.is_accepted,
             created_at: record.created_at.to_string(),
+            deleted_at: record.deleted_at.map(|deleted_at| deleted_at.to_string()),
+            deleted_by: record.deleted_by.map(|uuid| uuid.to_string()),
         })
     }
+
+    async fn get_answer_author(&self, answer_uuid: String, include_deleted: bool) -> Result<Option<String>, DBError> {
+        let uuid = sqlx::types::Uuid::parse_str(&answer_uuid).map_err(|_| DBError::InvalidUUID(answer_uuid.clone()))?;
+
+        let record = sqlx::query!(
+            "SELECT answers.author_uuid FROM answers
+            JOIN questions ON questions.question_uuid = answers.question_uuid
+            WHERE answers.answer_uuid = $1 AND ($2 OR (answers.deleted_at IS NULL AND questions.deleted_at IS NULL))",
+            uuid,
+            include_deleted
+        )
+            .fetch_optional(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting answer author"))?
+            .ok_or_else(|| DBError::not_found("answer_not_found", "Answer not found"))?;
+
+        Ok(record.author_uuid.map(|uuid| uuid.to_string()))
+    }
+
+    async fn vote_answer(&self, answer_uuid: String, user_uuid: String, value: i16) -> Result<i64, DBError> {
+        let uuid = Uuid::parse_str(&answer_uuid).map_err(|_| DBError::InvalidUUID(answer_uuid.clone()))?;
+        let user_uuid = Uuid::parse_str(&user_uuid).map_err(|_| DBError::InvalidUUID(user_uuid.clone()))?;
+
+        ensure_answer_not_deleted(&self.db, uuid).await?;
+
+        sqlx::query!(
+            "INSERT INTO votes ( user_uuid, answer_uuid, value )
+            VALUES ( $1, $2, $3 )
+            ON CONFLICT ( user_uuid, answer_uuid ) WHERE answer_uuid IS NOT NULL
+            DO UPDATE SET value = EXCLUDED.value, created_at = CURRENT_TIMESTAMP",
+            user_uuid,
+            uuid,
+            value
+        ).execute(&self.db).await.map_err(|e| {
+            if e.as_database_error().map(|e| e.code().expect("Error reading &dyn DatabaseError code").to_string()) == Some(postgres_error_codes::FOREIGN_KEY_VIOLATION.to_string()) {
+                DBError::InvalidUUID(answer_uuid.clone())
+            } else {
+                DBError::from_sqlx(e, "Error voting on answer")
+            }
+        })?;
+
+        self.get_score(uuid).await
+    }
+
+    async fn retract_answer_vote(&self, answer_uuid: String, user_uuid: String) -> Result<i64, DBError> {
+        let uuid = Uuid::parse_str(&answer_uuid).map_err(|_| DBError::InvalidUUID(answer_uuid.clone()))?;
+        let user_uuid = Uuid::parse_str(&user_uuid).map_err(|_| DBError::InvalidUUID(user_uuid.clone()))?;
+
+        ensure_answer_not_deleted(&self.db, uuid).await?;
+
+        sqlx::query!("DELETE FROM votes WHERE user_uuid = $1 AND answer_uuid = $2", user_uuid, uuid)
+            .execute(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error retracting answer vote"))?;
+
+        self.get_score(uuid).await
+    }
+
+    async fn answer_belongs_to_question(&self, answer_uuid: String, question_uuid: String) -> Result<bool, DBError> {
+        let uuid = Uuid::parse_str(&answer_uuid).map_err(|_| DBError::InvalidUUID(answer_uuid.clone()))?;
+        let question_uuid = Uuid::parse_str(&question_uuid).map_err(|_| DBError::InvalidUUID(question_uuid.clone()))?;
+
+        ensure_answer_not_deleted(&self.db, uuid).await?;
+
+        let record = sqlx::query!(
+            r#"SELECT EXISTS(SELECT 1 FROM answers WHERE answer_uuid = $1 AND question_uuid = $2) AS "belongs!""#,
+            uuid,
+            question_uuid
+        ).fetch_one(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error checking answer"))?;
+
+        Ok(record.belongs)
+    }
+
+    async fn get_answer_revisions(&self, answer_uuid: String) -> Result<Vec<AnswerRevision>, DBError> {
+        let uuid = Uuid::parse_str(&answer_uuid).map_err(|_| DBError::InvalidUUID(answer_uuid.clone()))?;
+
+        let records = sqlx::query!(
+            "SELECT answer_revisions.revision_uuid, answer_revisions.answer_uuid, answers.question_uuid,
+                answer_revisions.content, answer_revisions.editor_uuid, answer_revisions.edit_summary, answer_revisions.created_at
+            FROM answer_revisions
+            JOIN answers ON answers.answer_uuid = answer_revisions.answer_uuid
+            WHERE answer_revisions.answer_uuid = $1
+            ORDER BY answer_revisions.created_at DESC, answer_revisions.revision_uuid DESC",
+            uuid
+        ).fetch_all(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting answer revisions"))?;
+
+        let revisions = records.into_iter().map(|record| {
+            AnswerRevision {
+                revision_uuid: record.revision_uuid.to_string(),
+                answer_uuid: record.answer_uuid.to_string(),
+                question_uuid: record.question_uuid.to_string(),
+                content: record.content,
+                editor_uuid: record.editor_uuid.map(|uuid| uuid.to_string()),
+                edit_summary: record.edit_summary,
+                created_at: record.created_at.to_string(),
+            }
+        }).collect();
+
+        Ok(revisions)
+    }
+
+    async fn get_answer_revision(&self, answer_uuid: String, revision_uuid: String) -> Result<Option<AnswerRevision>, DBError> {
+        let uuid = Uuid::parse_str(&answer_uuid).map_err(|_| DBError::InvalidUUID(answer_uuid.clone()))?;
+        let revision_uuid = Uuid::parse_str(&revision_uuid).map_err(|_| DBError::InvalidUUID(revision_uuid.clone()))?;
+
+        let record = sqlx::query!(
+            "SELECT answer_revisions.revision_uuid, answer_revisions.answer_uuid, answers.question_uuid,
+                answer_revisions.content, answer_revisions.editor_uuid, answer_revisions.edit_summary, answer_revisions.created_at
+            FROM answer_revisions
+            JOIN answers ON answers.answer_uuid = answer_revisions.answer_uuid
+            WHERE answer_revisions.revision_uuid = $1 AND answer_revisions.answer_uuid = $2",
+            revision_uuid,
+            uuid
+        ).fetch_optional(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting answer revision"))?;
+
+        Ok(record.map(|record| AnswerRevision {
+            revision_uuid: record.revision_uuid.to_string(),
+            answer_uuid: record.answer_uuid.to_string(),
+            question_uuid: record.question_uuid.to_string(),
+            content: record.content,
+            editor_uuid: record.editor_uuid.map(|uuid| uuid.to_string()),
+            edit_summary: record.edit_summary,
+            created_at: record.created_at.to_string(),
+        }))
+    }
+
+    async fn health_check(&self) -> Result<MigrationStatus, DBError> {
+        postgres_health_check(&self.db).await
+    }
 }
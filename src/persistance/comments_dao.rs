@@ -0,0 +1,168 @@
+use async_trait::async_trait;
+use sqlx::{types::Uuid, PgPool};
+
+use super::{ensure_answer_not_deleted, ensure_question_not_deleted};
+use crate::models::{postgres_error_codes, Comment, CommentDetail, CommentParent, Cursor, DBError, Page, PageRequest};
+
+#[async_trait]
+pub trait CommentsDao {
+    /// Deleted posts can't be commented on.
+    async fn create_comment(&self, comment: Comment, author_uuid: String) -> Result<CommentDetail, DBError>;
+    async fn delete_comment(&self, comment_uuid: String) -> Result<(), DBError>;
+    /// Fails with `DBError::NotFound` if the parent post is deleted and `include_deleted` isn't set.
+    async fn get_comments(&self, parent: CommentParent, page: PageRequest, include_deleted: bool) -> Result<Page<CommentDetail>, DBError>;
+    /// Only the content of a comment can change, it stays attached to the same post.
+    async fn update_comment(&self, updated_comment: Comment, comment_uuid: String) -> Result<CommentDetail, DBError>;
+    /// Returns `None` if the comment has no author and `DBError::NotFound` if it does not exist.
+    async fn get_comment_author(&self, comment_uuid: String) -> Result<Option<String>, DBError>;
+}
+
+pub struct CommentsDaoImpl {
+    db: PgPool,
+}
+
+impl CommentsDaoImpl {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+fn parse_optional_uuid(uuid: Option<String>) -> Result<Option<Uuid>, DBError> {
+    uuid.map(|uuid| Uuid::parse_str(&uuid).map_err(|_| DBError::InvalidUUID(uuid.clone())))
+        .transpose()
+}
+
+async fn ensure_parent_not_deleted(db: &PgPool, question_uuid: Option<Uuid>, answer_uuid: Option<Uuid>) -> Result<(), DBError> {
+    if let Some(question_uuid) = question_uuid {
+        ensure_question_not_deleted(db, question_uuid).await?;
+    }
+    if let Some(answer_uuid) = answer_uuid {
+        ensure_answer_not_deleted(db, answer_uuid).await?;
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl CommentsDao for CommentsDaoImpl {
+    async fn create_comment(&self, comment: Comment, author_uuid: String) -> Result<CommentDetail, DBError> {
+        let question_uuid = parse_optional_uuid(comment.parent.question_uuid.clone())?;
+        let answer_uuid = parse_optional_uuid(comment.parent.answer_uuid.clone())?;
+        let author_uuid = Uuid::parse_str(&author_uuid).map_err(|_| DBError::InvalidUUID(author_uuid.clone()))?;
+        let parent_uuid = comment.parent.question_uuid.or(comment.parent.answer_uuid).unwrap_or_default();
+
+        ensure_parent_not_deleted(&self.db, question_uuid, answer_uuid).await?;
+
+        let record = sqlx::query!(
+            "INSERT INTO comments ( question_uuid, answer_uuid, content, author_uuid )
+            VALUES ( $1, $2, $3, $4 )
+            RETURNING comment_uuid, question_uuid, answer_uuid, content, author_uuid, created_at",
+            question_uuid,
+            answer_uuid,
+            comment.content,
+            author_uuid
+        ).fetch_one(&self.db).await.map_err(|e| {
+            if e.as_database_error().map(|e| e.code().expect("Error reading &dyn DatabaseError code").to_string()) == Some(postgres_error_codes::FOREIGN_KEY_VIOLATION.to_string()) {
+                DBError::InvalidUUID(parent_uuid.clone())
+            } else {
+                DBError::from_sqlx(e, "Error creating comment")
+            }
+        })?;
+
+        Ok(CommentDetail {
+            comment_uuid: record.comment_uuid.to_string(),
+            question_uuid: record.question_uuid.map(|uuid| uuid.to_string()),
+            answer_uuid: record.answer_uuid.map(|uuid| uuid.to_string()),
+            content: record.content,
+            author_uuid: record.author_uuid.map(|uuid| uuid.to_string()),
+            created_at: record.created_at.to_string(),
+        })
+    }
+
+    async fn delete_comment(&self, comment_uuid: String) -> Result<(), DBError> {
+        let uuid = Uuid::parse_str(&comment_uuid).map_err(|_| DBError::InvalidUUID(comment_uuid.clone()))?;
+
+        let deleted = sqlx::query!("DELETE FROM comments WHERE comment_uuid = $1", uuid)
+            .execute(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error deleting comment"))?;
+
+        if deleted.rows_affected() == 0 {
+            return Err(DBError::not_found("comment_not_found", "Comment not found"));
+        }
+
+        Ok(())
+    }
+
+    async fn get_comments(&self, parent: CommentParent, page: PageRequest, include_deleted: bool) -> Result<Page<CommentDetail>, DBError> {
+        let question_uuid = parse_optional_uuid(parent.question_uuid)?;
+        let answer_uuid = parse_optional_uuid(parent.answer_uuid)?;
+
+        if !include_deleted {
+            ensure_parent_not_deleted(&self.db, question_uuid, answer_uuid).await?;
+        }
+
+        // Oldest comments first; one extra row is fetched to know whether another page exists.
+        let mut records = sqlx::query!(
+            "SELECT comment_uuid, question_uuid, answer_uuid, content, author_uuid, created_at
+            FROM comments
+            WHERE question_uuid IS NOT DISTINCT FROM $1 AND answer_uuid IS NOT DISTINCT FROM $2
+                AND ($3::timestamp IS NULL OR (created_at, comment_uuid) > ($3, $4::uuid))
+            ORDER BY created_at, comment_uuid
+            LIMIT $5",
+            question_uuid,
+            answer_uuid,
+            page.cursor.map(|cursor| cursor.created_at),
+            page.cursor.map(|cursor| cursor.uuid),
+            page.limit + 1
+        ).fetch_all(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting comments"))?;
+
+        let next_cursor = if records.len() as i64 > page.limit {
+            records.truncate(page.limit as usize);
+            records.last().map(|record| Cursor { created_at: record.created_at, uuid: record.comment_uuid }.encode())
+        } else {
+            None
+        };
+
+        let comments = records.into_iter().map(|record| {
+            CommentDetail {
+                comment_uuid: record.comment_uuid.to_string(),
+                question_uuid: record.question_uuid.map(|uuid| uuid.to_string()),
+                answer_uuid: record.answer_uuid.map(|uuid| uuid.to_string()),
+                content: record.content,
+                author_uuid: record.author_uuid.map(|uuid| uuid.to_string()),
+                created_at: record.created_at.to_string(),
+            }
+        }).collect();
+
+        Ok(Page { items: comments, next_cursor })
+    }
+
+    async fn update_comment(&self, updated_comment: Comment, comment_uuid: String) -> Result<CommentDetail, DBError> {
+        let uuid = Uuid::parse_str(&comment_uuid).map_err(|_| DBError::InvalidUUID(comment_uuid.clone()))?;
+
+        let record = sqlx::query!(
+            "UPDATE comments SET content = $1 WHERE comment_uuid = $2
+            RETURNING comment_uuid, question_uuid, answer_uuid, content, author_uuid, created_at",
+            updated_comment.content,
+            uuid
+        ).fetch_optional(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error updating comment"))?
+        .ok_or_else(|| DBError::not_found("comment_not_found", "Comment not found"))?;
+
+        Ok(CommentDetail {
+            comment_uuid: record.comment_uuid.to_string(),
+            question_uuid: record.question_uuid.map(|uuid| uuid.to_string()),
+            answer_uuid: record.answer_uuid.map(|uuid| uuid.to_string()),
+            content: record.content,
+            author_uuid: record.author_uuid.map(|uuid| uuid.to_string()),
+            created_at: record.created_at.to_string(),
+        })
+    }
+
+    async fn get_comment_author(&self, comment_uuid: String) -> Result<Option<String>, DBError> {
+        let uuid = Uuid::parse_str(&comment_uuid).map_err(|_| DBError::InvalidUUID(comment_uuid.clone()))?;
+
+        let record = sqlx::query!("SELECT author_uuid FROM comments WHERE comment_uuid = $1", uuid)
+            .fetch_optional(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting comment author"))?
+            .ok_or_else(|| DBError::not_found("comment_not_found", "Comment not found"))?;
+
+        Ok(record.author_uuid.map(|uuid| uuid.to_string()))
+    }
+}
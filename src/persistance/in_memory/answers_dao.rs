@@ -0,0 +1,245 @@
+use std::cmp::Reverse;
+
+use async_trait::async_trait;
+use sqlx::types::Uuid;
+
+use crate::{
+    models::{Answer, AnswerDetail, AnswerRevision, Cursor, DBError, MigrationStatus, Page, PageRequest},
+    persistance::answers_dao::AnswersDao,
+};
+
+use super::{into_page, paginate, parse_uuid, AnswerRevisionRow, AnswerRow, Database, InMemoryStore, Post};
+
+pub struct InMemoryAnswersDao {
+    store: InMemoryStore,
+}
+
+impl InMemoryAnswersDao {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+fn to_revision(db: &Database, revision: &AnswerRevisionRow) -> Option<AnswerRevision> {
+    let answer = db.answers.get(&revision.answer_uuid)?;
+
+    Some(AnswerRevision {
+        revision_uuid: revision.revision_uuid.to_string(),
+        answer_uuid: revision.answer_uuid.to_string(),
+        question_uuid: answer.question_uuid.to_string(),
+        content: revision.content.clone(),
+        editor_uuid: revision.editor_uuid.map(|uuid| uuid.to_string()),
+        edit_summary: revision.edit_summary.clone(),
+        created_at: revision.created_at.to_string(),
+    })
+}
+
+fn vote(db: &mut Database, answer_uuid: Uuid, user_uuid: Uuid, value: Option<i16>) -> i64 {
+    let key = (user_uuid, Post::Answer(answer_uuid));
+    match value {
+        Some(value) => db.votes.insert(key, value),
+        None => db.votes.remove(&key),
+    };
+    db.score(Post::Answer(answer_uuid))
+}
+
+#[async_trait]
+impl AnswersDao for InMemoryAnswersDao {
+    async fn create_answer(&self, answer: Answer, author_uuid: String) -> Result<AnswerDetail, DBError> {
+        let uuid = parse_uuid(&answer.question_uuid)?;
+        let author_uuid = parse_uuid(&author_uuid)?;
+
+        let mut db = self.store.write();
+        if !db.questions.contains_key(&uuid) || !db.users.contains_key(&author_uuid) {
+            return Err(DBError::InvalidUUID(answer.question_uuid));
+        }
+        db.ensure_question_not_deleted(&uuid)?;
+
+        let row = AnswerRow {
+            answer_uuid: Uuid::new_v4(),
+            question_uuid: uuid,
+            content: answer.content,
+            author_uuid: Some(author_uuid),
+            created_at: db.now(),
+            deleted_at: None,
+            deleted_by: None,
+        };
+        let answer = db.answer_detail(&row);
+        db.answers.insert(row.answer_uuid, row);
+
+        Ok(answer)
+    }
+
+    async fn delete_answer(&self, answer_uuid: String, deleted_by: String) -> Result<(), DBError> {
+        let uuid = parse_uuid(&answer_uuid)?;
+        let deleted_by = parse_uuid(&deleted_by)?;
+
+        let mut db = self.store.write();
+        let now = db.now();
+        let answer = db.answers.get_mut(&uuid)
+            .filter(|answer| answer.deleted_at.is_none())
+            .ok_or_else(|| DBError::not_found("answer_not_found", "Answer not found"))?;
+        answer.deleted_at = Some(now);
+        answer.deleted_by = Some(deleted_by);
+
+        Ok(())
+    }
+
+    async fn undelete_answer(&self, answer_uuid: String) -> Result<(), DBError> {
+        let uuid = parse_uuid(&answer_uuid)?;
+
+        let mut db = self.store.write();
+        let answer = db.answers.get_mut(&uuid).ok_or_else(|| DBError::not_found("answer_not_found", "Answer not found"))?;
+        answer.deleted_at = None;
+        answer.deleted_by = None;
+
+        Ok(())
+    }
+
+    async fn get_answers(&self, question_uuid: String, page: PageRequest, include_deleted: bool) -> Result<Page<AnswerDetail>, DBError> {
+        let uuid = parse_uuid(&question_uuid)?;
+
+        let db = self.store.read();
+        let Some(question) = db.questions.get(&uuid) else {
+            return Ok(Page { items: vec![], next_cursor: None });
+        };
+
+        // Same ordering as Postgres: the accepted answer is pinned to the top of the first page,
+        // the remaining answers follow oldest first.
+        let mut answers: Vec<(bool, &AnswerRow)> = db.answers.values()
+            .filter(|answer| answer.question_uuid == uuid)
+            .filter(|answer| include_deleted || (answer.deleted_at.is_none() && question.deleted_at.is_none()))
+            .map(|answer| (db.is_accepted(answer), answer))
+            .filter(|(is_accepted, answer)| match page.cursor {
+                None => true,
+                Some(_) if *is_accepted => false,
+                Some(cursor) => (answer.created_at, answer.answer_uuid) > (cursor.created_at, cursor.uuid),
+            })
+            .collect();
+        answers.sort_by(|(a_accepted, a), (b_accepted, b)| {
+            b_accepted.cmp(a_accepted).then((a.created_at, a.answer_uuid).cmp(&(b.created_at, b.answer_uuid)))
+        });
+
+        let (answers, next_cursor) = paginate(answers, &page, |(is_accepted, answer)| {
+            if *is_accepted {
+                Cursor::start()
+            } else {
+                Cursor { created_at: answer.created_at, uuid: answer.answer_uuid }
+            }
+        });
+
+        Ok(into_page(answers, next_cursor, |(_, answer)| db.answer_detail(answer)))
+    }
+
+    async fn get_answer(&self, answer_uuid: String, include_deleted: bool) -> Result<Option<AnswerDetail>, DBError> {
+        let uuid = parse_uuid(&answer_uuid)?;
+
+        let db = self.store.read();
+        let answer = db.answers.get(&uuid).filter(|answer| include_deleted || !db.is_answer_deleted(answer));
+
+        Ok(answer.map(|answer| db.answer_detail(answer)))
+    }
+
+    async fn update_answer(
+        &self,
+        updated_answer: Answer,
+        answer_uuid: String,
+        editor_uuid: String,
+        edit_summary: Option<String>,
+    ) -> Result<AnswerDetail, DBError> {
+        let uuid = parse_uuid(&answer_uuid)?;
+        let editor_uuid = parse_uuid(&editor_uuid)?;
+
+        let mut db = self.store.write();
+        if !db.users.contains_key(&editor_uuid) {
+            return Err(DBError::Other("Error saving answer revision".into()));
+        }
+        let Some(current) = db.answers.get(&uuid).cloned() else {
+            return Err(DBError::not_found("answer_not_found", "Answer not found"));
+        };
+
+        let revision = AnswerRevisionRow {
+            revision_uuid: Uuid::new_v4(),
+            answer_uuid: uuid,
+            content: current.content,
+            editor_uuid: Some(editor_uuid),
+            edit_summary,
+            created_at: db.now(),
+        };
+        db.answer_revisions.push(revision);
+
+        let answer = db.answers.get_mut(&uuid).expect("Answer was just read");
+        answer.content = updated_answer.content;
+        let answer = answer.clone();
+
+        Ok(db.answer_detail(&answer))
+    }
+
+    async fn get_answer_author(&self, answer_uuid: String, include_deleted: bool) -> Result<Option<String>, DBError> {
+        let uuid = parse_uuid(&answer_uuid)?;
+
+        let db = self.store.read();
+        let answer = db.answers.get(&uuid)
+            .filter(|answer| include_deleted || !db.is_answer_deleted(answer))
+            .ok_or_else(|| DBError::not_found("answer_not_found", "Answer not found"))?;
+        Ok(answer.author_uuid.map(|uuid| uuid.to_string()))
+    }
+
+    async fn vote_answer(&self, answer_uuid: String, user_uuid: String, value: i16) -> Result<i64, DBError> {
+        let uuid = parse_uuid(&answer_uuid)?;
+        let user_uuid = parse_uuid(&user_uuid)?;
+
+        let mut db = self.store.write();
+        if !db.answers.contains_key(&uuid) || !db.users.contains_key(&user_uuid) {
+            return Err(DBError::InvalidUUID(answer_uuid));
+        }
+        db.ensure_answer_not_deleted(&uuid)?;
+
+        Ok(vote(&mut db, uuid, user_uuid, Some(value)))
+    }
+
+    async fn retract_answer_vote(&self, answer_uuid: String, user_uuid: String) -> Result<i64, DBError> {
+        let uuid = parse_uuid(&answer_uuid)?;
+        let user_uuid = parse_uuid(&user_uuid)?;
+
+        let mut db = self.store.write();
+        db.ensure_answer_not_deleted(&uuid)?;
+
+        Ok(vote(&mut db, uuid, user_uuid, None))
+    }
+
+    async fn answer_belongs_to_question(&self, answer_uuid: String, question_uuid: String) -> Result<bool, DBError> {
+        let uuid = parse_uuid(&answer_uuid)?;
+        let question_uuid = parse_uuid(&question_uuid)?;
+
+        let db = self.store.read();
+        db.ensure_answer_not_deleted(&uuid)?;
+        Ok(db.answers.get(&uuid).is_some_and(|answer| answer.question_uuid == question_uuid))
+    }
+
+    async fn get_answer_revisions(&self, answer_uuid: String) -> Result<Vec<AnswerRevision>, DBError> {
+        let uuid = parse_uuid(&answer_uuid)?;
+
+        let db = self.store.read();
+        let mut revisions: Vec<&AnswerRevisionRow> = db.answer_revisions.iter()
+            .filter(|revision| revision.answer_uuid == uuid)
+            .collect();
+        revisions.sort_by_key(|revision| Reverse((revision.created_at, revision.revision_uuid)));
+
+        Ok(revisions.into_iter().filter_map(|revision| to_revision(&db, revision)).collect())
+    }
+
+    async fn get_answer_revision(&self, answer_uuid: String, revision_uuid: String) -> Result<Option<AnswerRevision>, DBError> {
+        let uuid = parse_uuid(&answer_uuid)?;
+        let revision_uuid = parse_uuid(&revision_uuid)?;
+
+        let db = self.store.read();
+        Ok(db.answer_revisions.iter()
+            .find(|revision| revision.revision_uuid == revision_uuid && revision.answer_uuid == uuid)
+            .and_then(|revision| to_revision(&db, revision)))
+    }
+
+    async fn health_check(&self) -> Result<MigrationStatus, DBError> {
+        Ok(MigrationStatus::UpToDate)
+    }
+}
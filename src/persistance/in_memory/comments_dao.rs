@@ -0,0 +1,129 @@
+use async_trait::async_trait;
+use sqlx::types::Uuid;
+
+use crate::{
+    models::{Comment, CommentDetail, CommentParent, Cursor, DBError, Page, PageRequest},
+    persistance::comments_dao::CommentsDao,
+};
+
+use super::{into_page, paginate, parse_uuid, CommentRow, Database, InMemoryStore};
+
+pub struct InMemoryCommentsDao {
+    store: InMemoryStore,
+}
+
+impl InMemoryCommentsDao {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+fn parse_optional_uuid(uuid: Option<String>) -> Result<Option<Uuid>, DBError> {
+    uuid.map(|uuid| parse_uuid(&uuid)).transpose()
+}
+
+fn ensure_parent_not_deleted(db: &Database, question_uuid: Option<Uuid>, answer_uuid: Option<Uuid>) -> Result<(), DBError> {
+    if let Some(question_uuid) = question_uuid {
+        db.ensure_question_not_deleted(&question_uuid)?;
+    }
+    if let Some(answer_uuid) = answer_uuid {
+        db.ensure_answer_not_deleted(&answer_uuid)?;
+    }
+    Ok(())
+}
+
+fn to_detail(comment: &CommentRow) -> CommentDetail {
+    CommentDetail {
+        comment_uuid: comment.comment_uuid.to_string(),
+        question_uuid: comment.question_uuid.map(|uuid| uuid.to_string()),
+        answer_uuid: comment.answer_uuid.map(|uuid| uuid.to_string()),
+        content: comment.content.clone(),
+        author_uuid: comment.author_uuid.map(|uuid| uuid.to_string()),
+        created_at: comment.created_at.to_string(),
+    }
+}
+
+#[async_trait]
+impl CommentsDao for InMemoryCommentsDao {
+    async fn create_comment(&self, comment: Comment, author_uuid: String) -> Result<CommentDetail, DBError> {
+        let question_uuid = parse_optional_uuid(comment.parent.question_uuid.clone())?;
+        let answer_uuid = parse_optional_uuid(comment.parent.answer_uuid.clone())?;
+        let author_uuid = parse_uuid(&author_uuid)?;
+        let parent_uuid = comment.parent.question_uuid.or(comment.parent.answer_uuid).unwrap_or_default();
+
+        let mut db = self.store.write();
+
+        // A comment belongs to exactly one post, like the `comments` table's check constraint requires.
+        if question_uuid.is_some() == answer_uuid.is_some() {
+            return Err(DBError::Other("Error creating comment".into()));
+        }
+
+        let parent_exists = question_uuid.is_none_or(|uuid| db.questions.contains_key(&uuid))
+            && answer_uuid.is_none_or(|uuid| db.answers.contains_key(&uuid));
+        if !parent_exists || !db.users.contains_key(&author_uuid) {
+            return Err(DBError::InvalidUUID(parent_uuid));
+        }
+        ensure_parent_not_deleted(&db, question_uuid, answer_uuid)?;
+
+        let row = CommentRow {
+            comment_uuid: Uuid::new_v4(),
+            question_uuid,
+            answer_uuid,
+            content: comment.content,
+            author_uuid: Some(author_uuid),
+            created_at: db.now(),
+        };
+        let comment = to_detail(&row);
+        db.comments.insert(row.comment_uuid, row);
+
+        Ok(comment)
+    }
+
+    async fn delete_comment(&self, comment_uuid: String) -> Result<(), DBError> {
+        let uuid = parse_uuid(&comment_uuid)?;
+
+        self.store.write().comments.remove(&uuid).ok_or_else(|| DBError::not_found("comment_not_found", "Comment not found"))?;
+
+        Ok(())
+    }
+
+    async fn get_comments(&self, parent: CommentParent, page: PageRequest, include_deleted: bool) -> Result<Page<CommentDetail>, DBError> {
+        let question_uuid = parse_optional_uuid(parent.question_uuid)?;
+        let answer_uuid = parse_optional_uuid(parent.answer_uuid)?;
+
+        let db = self.store.read();
+        if !include_deleted {
+            ensure_parent_not_deleted(&db, question_uuid, answer_uuid)?;
+        }
+        let mut comments: Vec<&CommentRow> = db.comments.values()
+            .filter(|comment| comment.question_uuid == question_uuid && comment.answer_uuid == answer_uuid)
+            .filter(|comment| page.cursor.is_none_or(|cursor| (comment.created_at, comment.comment_uuid) > (cursor.created_at, cursor.uuid)))
+            .collect();
+        comments.sort_by_key(|comment| (comment.created_at, comment.comment_uuid));
+
+        let (comments, next_cursor) = paginate(comments, &page, |comment| Cursor {
+            created_at: comment.created_at,
+            uuid: comment.comment_uuid,
+        });
+
+        Ok(into_page(comments, next_cursor, |comment| to_detail(comment)))
+    }
+
+    async fn update_comment(&self, updated_comment: Comment, comment_uuid: String) -> Result<CommentDetail, DBError> {
+        let uuid = parse_uuid(&comment_uuid)?;
+
+        let mut db = self.store.write();
+        let comment = db.comments.get_mut(&uuid).ok_or_else(|| DBError::not_found("comment_not_found", "Comment not found"))?;
+        comment.content = updated_comment.content;
+
+        Ok(to_detail(comment))
+    }
+
+    async fn get_comment_author(&self, comment_uuid: String) -> Result<Option<String>, DBError> {
+        let uuid = parse_uuid(&comment_uuid)?;
+
+        let db = self.store.read();
+        let comment = db.comments.get(&uuid).ok_or_else(|| DBError::not_found("comment_not_found", "Comment not found"))?;
+        Ok(comment.author_uuid.map(|uuid| uuid.to_string()))
+    }
+}
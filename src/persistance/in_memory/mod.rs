@@ -0,0 +1,241 @@
+//! DAOs keeping everything in process memory, for running the API locally or in demos without Postgres.
+//! They follow the Postgres implementations' validation rules, but all data is lost on restart.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    time::Duration,
+};
+
+use sqlx::types::{
+    time::{OffsetDateTime, PrimitiveDateTime},
+    Uuid,
+};
+
+use crate::models::{AnswerDetail, Cursor, DBError, Page, PageRequest, QuestionDetail, Role};
+
+mod answers_dao;
+mod comments_dao;
+mod questions_dao;
+mod users_dao;
+
+pub use answers_dao::InMemoryAnswersDao;
+pub use comments_dao::InMemoryCommentsDao;
+pub use questions_dao::InMemoryQuestionsDao;
+pub use users_dao::InMemoryUsersDao;
+
+/// Shared storage behind the in-memory DAOs; clones refer to the same data.
+#[derive(Clone, Default)]
+pub struct InMemoryStore {
+    db: Arc<RwLock<Database>>,
+}
+
+impl InMemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, Database> {
+        self.db.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Database> {
+        self.db.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[derive(Default)]
+struct Database {
+    questions: HashMap<Uuid, QuestionRow>,
+    answers: HashMap<Uuid, AnswerRow>,
+    question_revisions: Vec<QuestionRevisionRow>,
+    answer_revisions: Vec<AnswerRevisionRow>,
+    votes: HashMap<(Uuid, Post), i16>,
+    comments: HashMap<Uuid, CommentRow>,
+    users: HashMap<Uuid, UserRow>,
+    sessions: HashMap<String, SessionRow>,
+    last_timestamp: Option<PrimitiveDateTime>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Post {
+    Question(Uuid),
+    Answer(Uuid),
+}
+
+#[derive(Clone)]
+struct QuestionRow {
+    question_uuid: Uuid,
+    title: String,
+    description: String,
+    tags: Vec<String>,
+    author_uuid: Option<Uuid>,
+    accepted_answer_uuid: Option<Uuid>,
+    created_at: PrimitiveDateTime,
+    deleted_at: Option<PrimitiveDateTime>,
+    deleted_by: Option<Uuid>,
+}
+
+#[derive(Clone)]
+struct AnswerRow {
+    answer_uuid: Uuid,
+    question_uuid: Uuid,
+    content: String,
+    author_uuid: Option<Uuid>,
+    created_at: PrimitiveDateTime,
+    deleted_at: Option<PrimitiveDateTime>,
+    deleted_by: Option<Uuid>,
+}
+
+#[derive(Clone)]
+struct QuestionRevisionRow {
+    revision_uuid: Uuid,
+    question_uuid: Uuid,
+    title: String,
+    description: String,
+    tags: Vec<String>,
+    editor_uuid: Option<Uuid>,
+    edit_summary: Option<String>,
+    created_at: PrimitiveDateTime,
+}
+
+#[derive(Clone)]
+struct AnswerRevisionRow {
+    revision_uuid: Uuid,
+    answer_uuid: Uuid,
+    content: String,
+    editor_uuid: Option<Uuid>,
+    edit_summary: Option<String>,
+    created_at: PrimitiveDateTime,
+}
+
+#[derive(Clone)]
+struct CommentRow {
+    comment_uuid: Uuid,
+    question_uuid: Option<Uuid>,
+    answer_uuid: Option<Uuid>,
+    content: String,
+    author_uuid: Option<Uuid>,
+    created_at: PrimitiveDateTime,
+}
+
+#[derive(Clone)]
+struct UserRow {
+    user_uuid: Uuid,
+    username: String,
+    password_hash: String,
+    role: Role,
+    created_at: PrimitiveDateTime,
+}
+
+#[derive(Clone)]
+struct SessionRow {
+    user_uuid: Uuid,
+    expires_at: PrimitiveDateTime,
+}
+
+impl Database {
+    /// Current UTC time at microsecond precision, like a Postgres `TIMESTAMP`. Every call returns a later
+    /// timestamp than the previous one so that rows keep their insertion order.
+    fn now(&mut self) -> PrimitiveDateTime {
+        let now = OffsetDateTime::now_utc();
+        let now = PrimitiveDateTime::new(now.date(), now.time())
+            .replace_nanosecond(now.microsecond() * 1_000)
+            .expect("Microseconds are always a valid nanosecond value");
+
+        let now = match self.last_timestamp {
+            Some(last) if now <= last => last + Duration::from_micros(1),
+            _ => now,
+        };
+
+        self.last_timestamp = Some(now);
+        now
+    }
+
+    fn score(&self, post: Post) -> i64 {
+        self.votes
+            .iter()
+            .filter(|((_, voted_post), _)| *voted_post == post)
+            .map(|(_, value)| *value as i64)
+            .sum()
+    }
+
+    fn question_detail(&self, question: &QuestionRow) -> QuestionDetail {
+        QuestionDetail {
+            question_uuid: question.question_uuid.to_string(),
+            title: question.title.clone(),
+            description: question.description.clone(),
+            author_uuid: question.author_uuid.map(|uuid| uuid.to_string()),
+            accepted_answer_uuid: question.accepted_answer_uuid.map(|uuid| uuid.to_string()),
+            tags: question.tags.clone(),
+            score: self.score(Post::Question(question.question_uuid)),
+            created_at: question.created_at.to_string(),
+            deleted_at: question.deleted_at.map(|deleted_at| deleted_at.to_string()),
+            deleted_by: question.deleted_by.map(|uuid| uuid.to_string()),
+        }
+    }
+
+    fn is_accepted(&self, answer: &AnswerRow) -> bool {
+        self.questions
+            .get(&answer.question_uuid)
+            .is_some_and(|question| question.accepted_answer_uuid == Some(answer.answer_uuid))
+    }
+
+    /// An answer also counts as deleted when its question is.
+    fn is_answer_deleted(&self, answer: &AnswerRow) -> bool {
+        answer.deleted_at.is_some()
+            || self.questions.get(&answer.question_uuid).is_some_and(|question| question.deleted_at.is_some())
+    }
+
+    /// Fails with `NotFound` if the question exists but is deleted. Missing questions are left to the caller.
+    fn ensure_question_not_deleted(&self, question_uuid: &Uuid) -> Result<(), DBError> {
+        if self.questions.get(question_uuid).is_some_and(|question| question.deleted_at.is_some()) {
+            return Err(DBError::not_found("question_not_found", "Question not found"));
+        }
+        Ok(())
+    }
+
+    fn ensure_answer_not_deleted(&self, answer_uuid: &Uuid) -> Result<(), DBError> {
+        if self.answers.get(answer_uuid).is_some_and(|answer| self.is_answer_deleted(answer)) {
+            return Err(DBError::not_found("answer_not_found", "Answer not found"));
+        }
+        Ok(())
+    }
+
+    fn answer_detail(&self, answer: &AnswerRow) -> AnswerDetail {
+        AnswerDetail {
+            answer_uuid: answer.answer_uuid.to_string(),
+            question_uuid: answer.question_uuid.to_string(),
+            content: answer.content.clone(),
+            author_uuid: answer.author_uuid.map(|uuid| uuid.to_string()),
+            score: self.score(Post::Answer(answer.answer_uuid)),
+            is_accepted: self.is_accepted(answer),
+            created_at: answer.created_at.to_string(),
+            deleted_at: answer.deleted_at.map(|deleted_at| deleted_at.to_string()),
+            deleted_by: answer.deleted_by.map(|uuid| uuid.to_string()),
+        }
+    }
+}
+
+fn parse_uuid(uuid: &str) -> Result<Uuid, DBError> {
+    Uuid::parse_str(uuid).map_err(|_| DBError::InvalidUUID(uuid.to_owned()))
+}
+
+/// Cuts `rows`, already sorted and past the cursor, down to one page.
+fn paginate<T>(mut rows: Vec<T>, page: &PageRequest, cursor: impl Fn(&T) -> Cursor) -> (Vec<T>, Option<String>) {
+    let next_cursor = if rows.len() as i64 > page.limit {
+        rows.truncate(page.limit as usize);
+        rows.last().map(|row| cursor(row).encode())
+    } else {
+        None
+    };
+
+    (rows, next_cursor)
+}
+
+fn into_page<T, U>(rows: Vec<T>, next_cursor: Option<String>, detail: impl Fn(&T) -> U) -> Page<U> {
+    Page {
+        items: rows.iter().map(detail).collect(),
+        next_cursor,
+    }
+}
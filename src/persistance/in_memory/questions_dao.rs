@@ -0,0 +1,300 @@
+use std::{cmp::Reverse, collections::BTreeMap};
+
+use async_trait::async_trait;
+use sqlx::types::Uuid;
+
+use crate::{
+    models::{
+        Cursor, DBError, MigrationStatus, Page, PageRequest, Question, QuestionDetail, QuestionRevision, QuestionSearchResult,
+        QuestionWithAnswers, TagDetail,
+    },
+    persistance::{questions_dao::QuestionsDao, text_search},
+};
+
+use super::{into_page, paginate, parse_uuid, AnswerRow, Database, InMemoryStore, Post, QuestionRevisionRow, QuestionRow};
+
+pub struct InMemoryQuestionsDao {
+    store: InMemoryStore,
+}
+
+impl InMemoryQuestionsDao {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+fn normalize_tags(mut tags: Vec<String>) -> Vec<String> {
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+fn to_revision(revision: &QuestionRevisionRow) -> QuestionRevision {
+    QuestionRevision {
+        revision_uuid: revision.revision_uuid.to_string(),
+        question_uuid: revision.question_uuid.to_string(),
+        title: revision.title.clone(),
+        description: revision.description.clone(),
+        tags: revision.tags.clone(),
+        editor_uuid: revision.editor_uuid.map(|uuid| uuid.to_string()),
+        edit_summary: revision.edit_summary.clone(),
+        created_at: revision.created_at.to_string(),
+    }
+}
+
+fn vote(db: &mut Database, question_uuid: Uuid, user_uuid: Uuid, value: Option<i16>) -> i64 {
+    let key = (user_uuid, Post::Question(question_uuid));
+    match value {
+        Some(value) => db.votes.insert(key, value),
+        None => db.votes.remove(&key),
+    };
+    db.score(Post::Question(question_uuid))
+}
+
+#[async_trait]
+impl QuestionsDao for InMemoryQuestionsDao {
+    async fn create_question(&self, question: Question, author_uuid: String) -> Result<QuestionDetail, DBError> {
+        let author_uuid = parse_uuid(&author_uuid)?;
+
+        let mut db = self.store.write();
+        if !db.users.contains_key(&author_uuid) {
+            return Err(DBError::Other("Error creating question".into()));
+        }
+
+        let row = QuestionRow {
+            question_uuid: Uuid::new_v4(),
+            title: question.title,
+            description: question.description,
+            tags: normalize_tags(question.tags),
+            author_uuid: Some(author_uuid),
+            accepted_answer_uuid: None,
+            created_at: db.now(),
+            deleted_at: None,
+            deleted_by: None,
+        };
+        let question = db.question_detail(&row);
+        db.questions.insert(row.question_uuid, row);
+
+        Ok(question)
+    }
+
+    async fn delete_question(&self, question_uuid: String, deleted_by: String) -> Result<(), DBError> {
+        let uuid = parse_uuid(&question_uuid)?;
+        let deleted_by = parse_uuid(&deleted_by)?;
+
+        let mut db = self.store.write();
+        let now = db.now();
+        let question = db.questions.get_mut(&uuid)
+            .filter(|question| question.deleted_at.is_none())
+            .ok_or_else(|| DBError::not_found("question_not_found", "Question not found"))?;
+        question.deleted_at = Some(now);
+        question.deleted_by = Some(deleted_by);
+
+        Ok(())
+    }
+
+    async fn undelete_question(&self, question_uuid: String) -> Result<(), DBError> {
+        let uuid = parse_uuid(&question_uuid)?;
+
+        let mut db = self.store.write();
+        let question = db.questions.get_mut(&uuid).ok_or_else(|| DBError::not_found("question_not_found", "Question not found"))?;
+        question.deleted_at = None;
+        question.deleted_by = None;
+
+        Ok(())
+    }
+
+    async fn get_questions(&self, page: PageRequest, tagged: Vec<String>, include_deleted: bool) -> Result<Page<QuestionDetail>, DBError> {
+        let db = self.store.read();
+
+        let mut questions: Vec<&QuestionRow> = db.questions.values()
+            .filter(|question| page.cursor.is_none_or(|cursor| (question.created_at, question.question_uuid) < (cursor.created_at, cursor.uuid)))
+            .filter(|question| include_deleted || question.deleted_at.is_none())
+            .filter(|question| tagged.iter().all(|tag| question.tags.contains(tag)))
+            .collect();
+        questions.sort_by_key(|question| Reverse((question.created_at, question.question_uuid)));
+
+        let (questions, next_cursor) = paginate(questions, &page, |question| Cursor {
+            created_at: question.created_at,
+            uuid: question.question_uuid,
+        });
+
+        Ok(into_page(questions, next_cursor, |question| db.question_detail(question)))
+    }
+
+    async fn get_question(&self, question_uuid: String, include_answers: bool, include_deleted: bool) -> Result<Option<QuestionWithAnswers>, DBError> {
+        let uuid = parse_uuid(&question_uuid)?;
+
+        let db = self.store.read();
+        let Some(question) = db.questions.get(&uuid).filter(|question| include_deleted || question.deleted_at.is_none()) else {
+            return Ok(None);
+        };
+
+        let answers = include_answers.then(|| {
+            let mut answers: Vec<&AnswerRow> = db.answers.values()
+                .filter(|answer| answer.question_uuid == uuid)
+                .filter(|answer| include_deleted || answer.deleted_at.is_none())
+                .collect();
+            answers.sort_by_key(|answer| (Reverse(db.is_accepted(answer)), answer.created_at, answer.answer_uuid));
+
+            answers.into_iter().map(|answer| db.answer_detail(answer)).collect()
+        });
+
+        Ok(Some(QuestionWithAnswers { question: db.question_detail(question), answers }))
+    }
+
+    async fn update_question(
+        &self,
+        updated_question: Question,
+        question_uuid: String,
+        editor_uuid: String,
+        edit_summary: Option<String>,
+    ) -> Result<QuestionDetail, DBError> {
+        let uuid = parse_uuid(&question_uuid)?;
+        let editor_uuid = parse_uuid(&editor_uuid)?;
+
+        let mut db = self.store.write();
+        if !db.users.contains_key(&editor_uuid) {
+            return Err(DBError::Other("Error saving question revision".into()));
+        }
+        let Some(current) = db.questions.get(&uuid).cloned() else {
+            return Err(DBError::not_found("question_not_found", "Question not found"));
+        };
+
+        let revision = QuestionRevisionRow {
+            revision_uuid: Uuid::new_v4(),
+            question_uuid: uuid,
+            title: current.title,
+            description: current.description,
+            tags: current.tags,
+            editor_uuid: Some(editor_uuid),
+            edit_summary,
+            created_at: db.now(),
+        };
+        db.question_revisions.push(revision);
+
+        let question = db.questions.get_mut(&uuid).expect("Question was just read");
+        question.title = updated_question.title;
+        question.description = updated_question.description;
+        question.tags = normalize_tags(updated_question.tags);
+        let question = question.clone();
+
+        Ok(db.question_detail(&question))
+    }
+
+    async fn search(&self, query: String, limit: i64) -> Result<Vec<QuestionSearchResult>, DBError> {
+        let terms = text_search::terms(&query);
+        if terms.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let db = self.store.read();
+
+        let mut results: Vec<(QuestionSearchResult, &QuestionRow)> = db.questions.values()
+            .filter(|question| question.deleted_at.is_none())
+            .filter_map(|question| {
+                let answers = db.answers.values()
+                    .filter(|answer| answer.question_uuid == question.question_uuid && answer.deleted_at.is_none())
+                    .map(|answer| answer.content.as_str());
+
+                text_search::search_result(db.question_detail(question), answers, &terms).map(|result| (result, question))
+            })
+            .collect();
+        results.sort_by(|(a, a_question), (b, b_question)| {
+            b.rank.total_cmp(&a.rank).then(b_question.created_at.cmp(&a_question.created_at))
+        });
+
+        Ok(results.into_iter().take(limit.max(0) as usize).map(|(result, _)| result).collect())
+    }
+
+    async fn get_question_author(&self, question_uuid: String, include_deleted: bool) -> Result<Option<String>, DBError> {
+        let uuid = parse_uuid(&question_uuid)?;
+
+        let db = self.store.read();
+        let question = db.questions.get(&uuid)
+            .filter(|question| include_deleted || question.deleted_at.is_none())
+            .ok_or_else(|| DBError::not_found("question_not_found", "Question not found"))?;
+        Ok(question.author_uuid.map(|uuid| uuid.to_string()))
+    }
+
+    async fn vote_question(&self, question_uuid: String, user_uuid: String, value: i16) -> Result<i64, DBError> {
+        let uuid = parse_uuid(&question_uuid)?;
+        let user_uuid = parse_uuid(&user_uuid)?;
+
+        let mut db = self.store.write();
+        if !db.questions.contains_key(&uuid) || !db.users.contains_key(&user_uuid) {
+            return Err(DBError::InvalidUUID(question_uuid));
+        }
+        db.ensure_question_not_deleted(&uuid)?;
+
+        Ok(vote(&mut db, uuid, user_uuid, Some(value)))
+    }
+
+    async fn retract_question_vote(&self, question_uuid: String, user_uuid: String) -> Result<i64, DBError> {
+        let uuid = parse_uuid(&question_uuid)?;
+        let user_uuid = parse_uuid(&user_uuid)?;
+
+        let mut db = self.store.write();
+        db.ensure_question_not_deleted(&uuid)?;
+
+        Ok(vote(&mut db, uuid, user_uuid, None))
+    }
+
+    async fn set_accepted_answer(&self, question_uuid: String, answer_uuid: Option<String>) -> Result<(), DBError> {
+        let uuid = parse_uuid(&question_uuid)?;
+        let answer_uuid = answer_uuid.map(|answer_uuid| parse_uuid(&answer_uuid)).transpose()?;
+
+        let mut db = self.store.write();
+        if answer_uuid.is_some_and(|answer_uuid| !db.answers.contains_key(&answer_uuid)) {
+            return Err(DBError::Other("Error setting accepted answer".into()));
+        }
+        let question = db.questions.get_mut(&uuid).ok_or_else(|| DBError::not_found("question_not_found", "Question not found"))?;
+        question.accepted_answer_uuid = answer_uuid;
+
+        Ok(())
+    }
+
+    async fn get_tags(&self) -> Result<Vec<TagDetail>, DBError> {
+        let db = self.store.read();
+
+        let mut counts: BTreeMap<&str, i64> = BTreeMap::new();
+        for question in db.questions.values().filter(|question| question.deleted_at.is_none()) {
+            for tag in &question.tags {
+                *counts.entry(tag).or_default() += 1;
+            }
+        }
+
+        let mut tags: Vec<TagDetail> = counts.into_iter()
+            .map(|(name, question_count)| TagDetail { name: name.to_owned(), question_count })
+            .collect();
+        tags.sort_by(|a, b| b.question_count.cmp(&a.question_count).then_with(|| a.name.cmp(&b.name)));
+
+        Ok(tags)
+    }
+
+    async fn get_question_revisions(&self, question_uuid: String) -> Result<Vec<QuestionRevision>, DBError> {
+        let uuid = parse_uuid(&question_uuid)?;
+
+        let db = self.store.read();
+        let mut revisions: Vec<&QuestionRevisionRow> = db.question_revisions.iter()
+            .filter(|revision| revision.question_uuid == uuid)
+            .collect();
+        revisions.sort_by_key(|revision| Reverse((revision.created_at, revision.revision_uuid)));
+
+        Ok(revisions.into_iter().map(to_revision).collect())
+    }
+
+    async fn get_question_revision(&self, question_uuid: String, revision_uuid: String) -> Result<Option<QuestionRevision>, DBError> {
+        let uuid = parse_uuid(&question_uuid)?;
+        let revision_uuid = parse_uuid(&revision_uuid)?;
+
+        let db = self.store.read();
+        Ok(db.question_revisions.iter()
+            .find(|revision| revision.revision_uuid == revision_uuid && revision.question_uuid == uuid)
+            .map(to_revision))
+    }
+
+    async fn health_check(&self) -> Result<MigrationStatus, DBError> {
+        Ok(MigrationStatus::UpToDate)
+    }
+}
@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use sqlx::types::Uuid;
+
+use crate::{
+    models::{DBError, Role, UserCredentials, UserDetail},
+    persistance::users_dao::UsersDao,
+};
+
+use super::{parse_uuid, InMemoryStore, SessionRow, UserRow};
+
+pub struct InMemoryUsersDao {
+    store: InMemoryStore,
+}
+
+impl InMemoryUsersDao {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+fn to_detail(user: &UserRow) -> UserDetail {
+    UserDetail {
+        user_uuid: user.user_uuid.to_string(),
+        username: user.username.clone(),
+        role: user.role,
+        created_at: user.created_at.to_string(),
+    }
+}
+
+#[async_trait]
+impl UsersDao for InMemoryUsersDao {
+    async fn create_user(&self, username: String, password_hash: String) -> Result<UserDetail, DBError> {
+        let mut db = self.store.write();
+        if db.users.values().any(|user| user.username == username) {
+            return Err(DBError::DuplicateUsername(username));
+        }
+
+        let user = UserRow {
+            user_uuid: Uuid::new_v4(),
+            username,
+            password_hash,
+            role: Role::User,
+            created_at: db.now(),
+        };
+        let detail = to_detail(&user);
+        db.users.insert(user.user_uuid, user);
+
+        Ok(detail)
+    }
+
+    async fn get_user_credentials(&self, username: String) -> Result<Option<UserCredentials>, DBError> {
+        let db = self.store.read();
+
+        Ok(db.users.values().find(|user| user.username == username).map(|user| UserCredentials {
+            user_uuid: user.user_uuid.to_string(),
+            password_hash: user.password_hash.clone(),
+        }))
+    }
+
+    async fn create_session(&self, user_uuid: String, token_hash: String, ttl_seconds: i64) -> Result<String, DBError> {
+        let uuid = parse_uuid(&user_uuid)?;
+
+        let mut db = self.store.write();
+        if !db.users.contains_key(&uuid) {
+            return Err(DBError::Other("Error creating session".into()));
+        }
+        if db.sessions.contains_key(&token_hash) {
+            return Err(DBError::Conflict { code: "conflict", message: "Error creating session".to_owned(), source: None });
+        }
+
+        let expires_at = db.now() + Duration::from_secs(ttl_seconds.max(0) as u64);
+        db.sessions.insert(token_hash, SessionRow { user_uuid: uuid, expires_at });
+
+        Ok(expires_at.to_string())
+    }
+
+    async fn get_session_user(&self, token_hash: String) -> Result<Option<UserDetail>, DBError> {
+        let mut db = self.store.write();
+        let now = db.now();
+
+        Ok(db.sessions.get(&token_hash)
+            .filter(|session| session.expires_at > now)
+            .and_then(|session| db.users.get(&session.user_uuid))
+            .map(to_detail))
+    }
+
+    async fn delete_session(&self, token_hash: String) -> Result<(), DBError> {
+        self.store.write().sessions.remove(&token_hash);
+
+        Ok(())
+    }
+}
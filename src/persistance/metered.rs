@@ -0,0 +1,123 @@
+use std::future::Future;
+
+use async_trait::async_trait;
+use prometheus::{HistogramVec, IntCounterVec};
+
+use crate::{
+    metrics::Metrics,
+    models::*,
+    persistance::{answers_dao::AnswersDao, comments_dao::CommentsDao, questions_dao::QuestionsDao, users_dao::UsersDao},
+};
+
+/// Decorates a DAO with the duration of its calls and the kinds of errors they fail with.
+pub struct Metered<D> {
+    inner: D,
+    call_duration: HistogramVec,
+    errors: IntCounterVec,
+}
+
+impl<D> Metered<D> {
+    pub fn new(inner: D, metrics: &Metrics) -> Self {
+        Metered {
+            inner,
+            call_duration: metrics.dao_call_duration.clone(),
+            errors: metrics.dao_errors.clone(),
+        }
+    }
+
+    async fn observe<T>(&self, dao: &str, method: &str, call: impl Future<Output = Result<T, DBError>>) -> Result<T, DBError> {
+        let timer = self.call_duration.with_label_values(&[dao, method]).start_timer();
+        let result = call.await;
+        timer.observe_duration();
+
+        if let Err(err) = &result {
+            self.errors.with_label_values(&[dao, method, err.kind()]).inc();
+        }
+
+        result
+    }
+}
+
+/// Implements `$dao` for `Metered<D>` by forwarding every listed method to the inner DAO.
+macro_rules! metered_dao {
+    ($dao:ident, $label:literal, { $( fn $method:ident(&self $(, $arg:ident: $ty:ty)*) -> $ret:ty; )* }) => {
+        #[async_trait]
+        impl<D: $dao + Send + Sync> $dao for Metered<D> {
+            $(
+                async fn $method(&self $(, $arg: $ty)*) -> Result<$ret, DBError> {
+                    self.observe($label, stringify!($method), self.inner.$method($($arg),*)).await
+                }
+            )*
+        }
+    };
+}
+
+metered_dao!(QuestionsDao, "questions", {
+    fn create_question(&self, question: Question, author_uuid: String) -> QuestionDetail;
+    fn delete_question(&self, question_uuid: String, deleted_by: String) -> ();
+    fn undelete_question(&self, question_uuid: String) -> ();
+    fn get_questions(&self, page: PageRequest, tagged: Vec<String>, include_deleted: bool) -> Page<QuestionDetail>;
+    fn get_question(&self, question_uuid: String, include_answers: bool, include_deleted: bool) -> Option<QuestionWithAnswers>;
+    fn update_question(&self, updated_question: Question, question_uuid: String, editor_uuid: String, edit_summary: Option<String>) -> QuestionDetail;
+    fn search(&self, query: String, limit: i64) -> Vec<QuestionSearchResult>;
+    fn get_question_author(&self, question_uuid: String, include_deleted: bool) -> Option<String>;
+    fn vote_question(&self, question_uuid: String, user_uuid: String, value: i16) -> i64;
+    fn retract_question_vote(&self, question_uuid: String, user_uuid: String) -> i64;
+    fn set_accepted_answer(&self, question_uuid: String, answer_uuid: Option<String>) -> ();
+    fn get_tags(&self) -> Vec<TagDetail>;
+    fn get_question_revisions(&self, question_uuid: String) -> Vec<QuestionRevision>;
+    fn get_question_revision(&self, question_uuid: String, revision_uuid: String) -> Option<QuestionRevision>;
+    fn health_check(&self) -> MigrationStatus;
+});
+
+metered_dao!(AnswersDao, "answers", {
+    fn create_answer(&self, answer: Answer, author_uuid: String) -> AnswerDetail;
+    fn delete_answer(&self, answer_uuid: String, deleted_by: String) -> ();
+    fn undelete_answer(&self, answer_uuid: String) -> ();
+    fn get_answer(&self, answer_uuid: String, include_deleted: bool) -> Option<AnswerDetail>;
+    fn get_answers(&self, question_uuid: String, page: PageRequest, include_deleted: bool) -> Page<AnswerDetail>;
+    fn update_answer(&self, updated_answer: Answer, answer_uuid: String, editor_uuid: String, edit_summary: Option<String>) -> AnswerDetail;
+    fn get_answer_author(&self, answer_uuid: String, include_deleted: bool) -> Option<String>;
+    fn vote_answer(&self, answer_uuid: String, user_uuid: String, value: i16) -> i64;
+    fn retract_answer_vote(&self, answer_uuid: String, user_uuid: String) -> i64;
+    fn answer_belongs_to_question(&self, answer_uuid: String, question_uuid: String) -> bool;
+    fn get_answer_revisions(&self, answer_uuid: String) -> Vec<AnswerRevision>;
+    fn get_answer_revision(&self, answer_uuid: String, revision_uuid: String) -> Option<AnswerRevision>;
+    fn health_check(&self) -> MigrationStatus;
+});
+
+metered_dao!(CommentsDao, "comments", {
+    fn create_comment(&self, comment: Comment, author_uuid: String) -> CommentDetail;
+    fn delete_comment(&self, comment_uuid: String) -> ();
+    fn get_comments(&self, parent: CommentParent, page: PageRequest, include_deleted: bool) -> Page<CommentDetail>;
+    fn update_comment(&self, updated_comment: Comment, comment_uuid: String) -> CommentDetail;
+    fn get_comment_author(&self, comment_uuid: String) -> Option<String>;
+});
+
+metered_dao!(UsersDao, "users", {
+    fn create_user(&self, username: String, password_hash: String) -> UserDetail;
+    fn get_user_credentials(&self, username: String) -> Option<UserCredentials>;
+    fn create_session(&self, user_uuid: String, token_hash: String, ttl_seconds: i64) -> String;
+    fn get_session_user(&self, token_hash: String) -> Option<UserDetail>;
+    fn delete_session(&self, token_hash: String) -> ();
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistance::in_memory::{InMemoryQuestionsDao, InMemoryStore};
+
+    #[tokio::test]
+    async fn metered_dao_should_time_calls_and_count_errors_by_kind() {
+        let metrics = Metrics::new();
+        let dao = Metered::new(InMemoryQuestionsDao::new(InMemoryStore::new()), &metrics);
+
+        dao.get_tags().await.unwrap();
+        let result = dao.undelete_question("4a4fd4a7-2e74-4bd0-8e2b-e8b3c4a0d1a4".to_owned()).await;
+
+        assert!(matches!(result, Err(DBError::NotFound { .. })));
+        assert_eq!(metrics.dao_call_duration.with_label_values(&["questions", "get_tags"]).get_sample_count(), 1);
+        assert_eq!(metrics.dao_errors.with_label_values(&["questions", "undelete_question", "not_found"]).get(), 1);
+        assert_eq!(metrics.dao_errors.with_label_values(&["questions", "get_tags", "not_found"]).get(), 0);
+    }
+}
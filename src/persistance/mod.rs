@@ -1,5 +1,96 @@
+use serde::Deserialize;
+use sqlx::{migrate::Migrator, types::Uuid, PgPool};
+
+use crate::models::{postgres_error_codes, DBError, MigrationStatus};
+
 pub mod answers_dao;
+pub mod comments_dao;
+pub mod in_memory;
+pub mod metered;
 pub mod questions_dao;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod users_dao;
+
+mod text_search;
 
 #[cfg(test)]
 mod tests;
+
+/// Where the DAOs keep their data, picked at startup from the `storage_backend` setting.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    #[default]
+    Postgres,
+    #[cfg(feature = "sqlite")]
+    Sqlite,
+    #[serde(rename = "memory")]
+    InMemory,
+}
+
+/// Migrations of the Postgres schema, bundled into the binary.
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// Compares the versions `_sqlx_migrations` records as applied with the migrations of `migrator`.
+fn migration_status(migrator: &Migrator, applied: &[i64]) -> MigrationStatus {
+    let pending: Vec<i64> = migrator
+        .iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+        .map(|migration| migration.version)
+        .filter(|version| !applied.contains(version))
+        .collect();
+
+    if pending.is_empty() {
+        MigrationStatus::UpToDate
+    } else {
+        MigrationStatus::Pending(pending)
+    }
+}
+
+/// Fails with `NotFound` if the question exists but is deleted. Missing questions are left to the caller, whose
+/// foreign keys and lookups already report them.
+async fn ensure_question_not_deleted(db: &PgPool, question_uuid: Uuid) -> Result<(), DBError> {
+    let deleted = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM questions WHERE question_uuid = $1 AND deleted_at IS NOT NULL) AS "deleted!""#,
+        question_uuid
+    ).fetch_one(db).await.map_err(|e| DBError::from_sqlx(e, "Error checking question"))?;
+
+    if deleted {
+        return Err(DBError::not_found("question_not_found", "Question not found"));
+    }
+
+    Ok(())
+}
+
+/// Like `ensure_question_not_deleted`, an answer also counts as deleted when its question is.
+async fn ensure_answer_not_deleted(db: &PgPool, answer_uuid: Uuid) -> Result<(), DBError> {
+    let deleted = sqlx::query_scalar!(
+        r#"SELECT EXISTS(
+            SELECT 1 FROM answers
+            JOIN questions ON questions.question_uuid = answers.question_uuid
+            WHERE answers.answer_uuid = $1 AND (answers.deleted_at IS NOT NULL OR questions.deleted_at IS NOT NULL)
+        ) AS "deleted!""#,
+        answer_uuid
+    ).fetch_one(db).await.map_err(|e| DBError::from_sqlx(e, "Error checking answer"))?;
+
+    if deleted {
+        return Err(DBError::not_found("answer_not_found", "Answer not found"));
+    }
+
+    Ok(())
+}
+
+/// Pings the Postgres database and reports the status of its migrations.
+async fn postgres_health_check(db: &PgPool) -> Result<MigrationStatus, DBError> {
+    sqlx::query("SELECT 1").execute(db).await.map_err(|e| DBError::from_sqlx(e, "Error pinging database"))?;
+
+    let applied = sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success").fetch_all(db).await;
+    match applied {
+        Ok(applied) => Ok(migration_status(&MIGRATOR, &applied)),
+        Err(e) if e.as_database_error().and_then(|e| e.code()).as_deref() == Some(postgres_error_codes::UNDEFINED_TABLE) => {
+            Ok(MigrationStatus::Untracked)
+        }
+        Err(e) => Err(DBError::from_sqlx(e, "Error reading applied migrations")),
+    }
+}
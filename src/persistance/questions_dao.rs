@@ -1,14 +1,51 @@
 use async_trait::async_trait;
-use sqlx::PgPool;
+use sqlx::{types::Uuid, PgPool, Postgres, Transaction};
 
-use crate::models::{DBError, Question, QuestionDetail};
+use crate::models::{
+    postgres_error_codes, AnswerDetail, Cursor, DBError, MigrationStatus, Page, PageRequest, Question, QuestionDetail,
+    QuestionRevision, QuestionSearchResult, QuestionWithAnswers, TagDetail,
+};
+
+use super::{ensure_question_not_deleted, postgres_health_check, text_search};
 
 #[async_trait]
 pub trait QuestionsDao {
-    async fn create_question(&self, question: Question) -> Result<QuestionDetail, DBError>;
-    async fn delete_question(&self, question_uuid: String) -> Result<(), DBError>;
-    async fn get_questions(&self) -> Result<Vec<QuestionDetail>, DBError>;
-    async fn update_question(&self, updated_question: Question, question_uuid: String) -> Result<QuestionDetail, DBError>;
+    async fn create_question(&self, question: Question, author_uuid: String) -> Result<QuestionDetail, DBError>;
+    /// Soft deletes the question; it stays hidden from listings and search until it's undeleted.
+    async fn delete_question(&self, question_uuid: String, deleted_by: String) -> Result<(), DBError>;
+    async fn undelete_question(&self, question_uuid: String) -> Result<(), DBError>;
+    /// Lists questions carrying every tag in `tagged`, or all questions when `tagged` is empty.
+    /// Deleted questions are only listed when `include_deleted` is set.
+    async fn get_questions(&self, page: PageRequest, tagged: Vec<String>, include_deleted: bool) -> Result<Page<QuestionDetail>, DBError>;
+    /// Returns `None` if the question does not exist, or is deleted and `include_deleted` isn't set.
+    /// With `include_answers` its answers are loaded in the same query, deleted ones only with `include_deleted`.
+    async fn get_question(&self, question_uuid: String, include_answers: bool, include_deleted: bool) -> Result<Option<QuestionWithAnswers>, DBError>;
+    /// Saves the question's current content as a revision attributed to `editor_uuid`, then applies the update.
+    async fn update_question(
+        &self,
+        updated_question: Question,
+        question_uuid: String,
+        editor_uuid: String,
+        edit_summary: Option<String>,
+    ) -> Result<QuestionDetail, DBError>;
+    async fn search(&self, query: String, limit: i64) -> Result<Vec<QuestionSearchResult>, DBError>;
+    /// Returns `None` if the question has no author and `DBError::NotFound` if it does not exist, or is deleted
+    /// and `include_deleted` isn't set.
+    async fn get_question_author(&self, question_uuid: String, include_deleted: bool) -> Result<Option<String>, DBError>;
+    /// Records or replaces the user's vote and returns the question's new score. Deleted questions can't be voted on.
+    async fn vote_question(&self, question_uuid: String, user_uuid: String, value: i16) -> Result<i64, DBError>;
+    /// Removes the user's vote, if any, and returns the question's new score.
+    async fn retract_question_vote(&self, question_uuid: String, user_uuid: String) -> Result<i64, DBError>;
+    /// Marks the answer as the question's accepted answer, or clears it when `answer_uuid` is `None`.
+    async fn set_accepted_answer(&self, question_uuid: String, answer_uuid: Option<String>) -> Result<(), DBError>;
+    /// Lists tags in use, most used first.
+    async fn get_tags(&self) -> Result<Vec<TagDetail>, DBError>;
+    /// Lists the question's revisions, newest first.
+    async fn get_question_revisions(&self, question_uuid: String) -> Result<Vec<QuestionRevision>, DBError>;
+    /// Returns `None` if the revision does not exist or belongs to another question.
+    async fn get_question_revision(&self, question_uuid: String, revision_uuid: String) -> Result<Option<QuestionRevision>, DBError>;
+    /// Pings the database and reports whether its migrations are up to date.
+    async fn health_check(&self) -> Result<MigrationStatus, DBError>;
 }
 
 pub struct QuestionsDaoImpl {
@@ -19,66 +56,483 @@ impl QuestionsDaoImpl {
     pub fn new(db: PgPool) -> Self {
         Self { db }
     }
+
+    async fn get_score(&self, question_uuid: Uuid) -> Result<i64, DBError> {
+        let record = sqlx::query!(
+            r#"SELECT COALESCE(SUM(value), 0) AS "score!" FROM votes WHERE question_uuid = $1"#,
+            question_uuid
+        ).fetch_one(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting question score"))?;
+
+        Ok(record.score)
+    }
+}
+
+/// Replaces the question's tags, creating any tag that doesn't exist yet.
+async fn replace_tags(tx: &mut Transaction<'_, Postgres>, question_uuid: Uuid, tags: &[String]) -> Result<(), sqlx::Error> {
+    sqlx::query!("DELETE FROM question_tags WHERE question_uuid = $1", question_uuid)
+        .execute(&mut *tx).await?;
+
+    sqlx::query!(
+        "INSERT INTO tags ( name ) SELECT UNNEST($1::text[]) ON CONFLICT ( name ) DO NOTHING",
+        tags
+    ).execute(&mut *tx).await?;
+
+    sqlx::query!(
+        "INSERT INTO question_tags ( question_uuid, tag_uuid )
+        SELECT $1, tag_uuid FROM tags WHERE name = ANY($2::text[])",
+        question_uuid,
+        tags
+    ).execute(&mut *tx).await?;
+
+    Ok(())
 }
 
 #[async_trait]
 impl QuestionsDao for QuestionsDaoImpl {
-    async fn create_question(&self, question: Question) -> Result<QuestionDetail, DBError> {
+    async fn create_question(&self, question: Question, author_uuid: String) -> Result<QuestionDetail, DBError> {
+        let author_uuid = sqlx::types::Uuid::parse_str(&author_uuid).map_err(|_| DBError::InvalidUUID(author_uuid.clone()))?;
+
+        let mut tx = self.db.begin().await.map_err(|e| DBError::from_sqlx(e, "Error creating question"))?;
+
         let record = sqlx::query!(
-            "INSERT INTO questions ( title, description )
-            VALUES ( $1, $2 )
-            RETURNING *",
+            "INSERT INTO questions ( title, description, author_uuid )
+            VALUES ( $1, $2, $3 )
+            RETURNING question_uuid, title, description, author_uuid, accepted_answer_uuid, created_at",
             question.title,
-            question.description
-        ).fetch_one(&self.db).await.map_err(|_| DBError::Other("Error creating question".into()))?;
+            question.description,
+            author_uuid
+        ).fetch_one(&mut tx).await.map_err(|e| DBError::from_sqlx(e, "Error creating question"))?;
+
+        replace_tags(&mut tx, record.question_uuid, &question.tags).await
+            .map_err(|e| DBError::from_sqlx(e, "Error tagging question"))?;
+
+        tx.commit().await.map_err(|e| DBError::from_sqlx(e, "Error creating question"))?;
+
+        let mut tags = question.tags;
+        tags.sort();
+        tags.dedup();
 
         Ok(QuestionDetail {
             question_uuid: record.question_uuid.to_string(),
             title: record.title,
             description: record.description,
+            author_uuid: record.author_uuid.map(|uuid| uuid.to_string()),
+            accepted_answer_uuid: record.accepted_answer_uuid.map(|uuid| uuid.to_string()),
+            tags,
+            score: 0,
             created_at: record.created_at.to_string(),
+            deleted_at: None,
+            deleted_by: None,
         })
     }
 
-    async fn delete_question(&self, question_uuid: String) -> Result<(), DBError> {
+    async fn delete_question(&self, question_uuid: String, deleted_by: String) -> Result<(), DBError> {
         let uuid = sqlx::types::Uuid::parse_str(&question_uuid).map_err(|_| DBError::InvalidUUID(question_uuid.clone()))?;
+        let deleted_by = Uuid::parse_str(&deleted_by).map_err(|_| DBError::InvalidUUID(deleted_by.clone()))?;
+
+        let deleted = sqlx::query!(
+            "UPDATE questions SET deleted_at = CURRENT_TIMESTAMP, deleted_by = $2
+            WHERE question_uuid = $1 AND deleted_at IS NULL",
+            uuid,
+            deleted_by
+        ).execute(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error deleting question"))?;
+
+        if deleted.rows_affected() == 0 {
+            return Err(DBError::not_found("question_not_found", "Question not found"));
+        }
+
+        Ok(())
+    }
+
+    async fn undelete_question(&self, question_uuid: String) -> Result<(), DBError> {
+        let uuid = Uuid::parse_str(&question_uuid).map_err(|_| DBError::InvalidUUID(question_uuid.clone()))?;
+
+        let undeleted = sqlx::query!("UPDATE questions SET deleted_at = NULL, deleted_by = NULL WHERE question_uuid = $1", uuid)
+            .execute(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error undeleting question"))?;
+
+        if undeleted.rows_affected() == 0 {
+            return Err(DBError::not_found("question_not_found", "Question not found"));
+        }
 
-        sqlx::query!("DELETE FROM questions WHERE question_uuid = $1", uuid)
-            .execute(&self.db).await.map_err(|_| DBError::Other("Error deleting question".into()))?;
         Ok(())
     }
 
-    async fn get_questions(&self) -> Result<Vec<QuestionDetail>, DBError> {
-        let records = sqlx::query!("SELECT * FROM questions")
-            .fetch_all(&self.db).await.map_err(|_| DBError::Other("Error getting questions".into()))?;
+    async fn get_questions(&self, page: PageRequest, tagged: Vec<String>, include_deleted: bool) -> Result<Page<QuestionDetail>, DBError> {
+        // Newest questions first; one extra row is fetched to know whether another page exists.
+        let mut records = sqlx::query!(
+            r#"SELECT question_uuid, title, description, author_uuid, accepted_answer_uuid, created_at, deleted_at, deleted_by,
+                ARRAY(
+                    SELECT tags.name FROM question_tags JOIN tags ON tags.tag_uuid = question_tags.tag_uuid
+                    WHERE question_tags.question_uuid = questions.question_uuid ORDER BY tags.name
+                ) AS "tags!",
+                (SELECT COALESCE(SUM(value), 0) FROM votes WHERE votes.question_uuid = questions.question_uuid) AS "score!"
+            FROM questions
+            WHERE ($1::timestamp IS NULL OR (created_at, question_uuid) < ($1, $2::uuid))
+                AND ($5 OR deleted_at IS NULL)
+                AND (cardinality($4::text[]) = 0 OR question_uuid IN (
+                    SELECT question_tags.question_uuid FROM question_tags
+                    JOIN tags ON tags.tag_uuid = question_tags.tag_uuid
+                    WHERE tags.name = ANY($4)
+                    GROUP BY question_tags.question_uuid
+                    HAVING COUNT(*) = cardinality($4)
+                ))
+            ORDER BY created_at DESC, question_uuid DESC
+            LIMIT $3"#,
+            page.cursor.map(|cursor| cursor.created_at),
+            page.cursor.map(|cursor| cursor.uuid),
+            page.limit + 1,
+            &tagged,
+            include_deleted
+        ).fetch_all(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting questions"))?;
+
+        let next_cursor = if records.len() as i64 > page.limit {
+            records.truncate(page.limit as usize);
+            records.last().map(|record| Cursor { created_at: record.created_at, uuid: record.question_uuid }.encode())
+        } else {
+            None
+        };
 
         let questions = records.iter().map(|record| {
             QuestionDetail {
                 question_uuid: record.question_uuid.to_string(),
                 title: record.title.clone(),
                 description: record.description.clone(),
+                author_uuid: record.author_uuid.map(|uuid| uuid.to_string()),
+                accepted_answer_uuid: record.accepted_answer_uuid.map(|uuid| uuid.to_string()),
+                tags: record.tags.clone(),
+                score: record.score,
                 created_at: record.created_at.to_string(),
+                deleted_at: record.deleted_at.map(|deleted_at| deleted_at.to_string()),
+                deleted_by: record.deleted_by.map(|uuid| uuid.to_string()),
             }
         }).collect();
 
-        Ok(questions)
+        Ok(Page { items: questions, next_cursor })
     }
 
-    async fn update_question(&self, updated_question: Question, question_uuid: String) -> Result<QuestionDetail, DBError> {
+    async fn get_question(&self, question_uuid: String, include_answers: bool, include_deleted: bool) -> Result<Option<QuestionWithAnswers>, DBError> {
+        let uuid = Uuid::parse_str(&question_uuid).map_err(|_| DBError::InvalidUUID(question_uuid.clone()))?;
+
+        // One row per answer, or a single row with null answer columns when there are none or they weren't asked for.
+        let records = sqlx::query!(
+            r#"SELECT questions.question_uuid, questions.title, questions.description, questions.author_uuid,
+                questions.accepted_answer_uuid, questions.created_at, questions.deleted_at, questions.deleted_by,
+                ARRAY(
+                    SELECT tags.name FROM question_tags JOIN tags ON tags.tag_uuid = question_tags.tag_uuid
+                    WHERE question_tags.question_uuid = questions.question_uuid ORDER BY tags.name
+                ) AS "tags!",
+                (SELECT COALESCE(SUM(value), 0) FROM votes WHERE votes.question_uuid = questions.question_uuid) AS "score!",
+                answers.answer_uuid AS "answer_uuid?", answers.content AS "answer_content?",
+                answers.author_uuid AS "answer_author_uuid?", answers.created_at AS "answer_created_at?",
+                answers.deleted_at AS "answer_deleted_at?", answers.deleted_by AS "answer_deleted_by?",
+                (SELECT COALESCE(SUM(value), 0) FROM votes WHERE votes.answer_uuid = answers.answer_uuid) AS "answer_score!"
+            FROM questions
+            LEFT JOIN answers ON answers.question_uuid = questions.question_uuid
+                AND $2 AND ($3 OR answers.deleted_at IS NULL)
+            WHERE questions.question_uuid = $1 AND ($3 OR questions.deleted_at IS NULL)
+            ORDER BY (answers.answer_uuid = questions.accepted_answer_uuid) IS TRUE DESC, answers.created_at, answers.answer_uuid"#,
+            uuid,
+            include_answers,
+            include_deleted
+        ).fetch_all(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting question"))?;
+
+        let Some(record) = records.first() else {
+            return Ok(None);
+        };
+
+        let question = QuestionDetail {
+            question_uuid: record.question_uuid.to_string(),
+            title: record.title.clone(),
+            description: record.description.clone(),
+            author_uuid: record.author_uuid.map(|uuid| uuid.to_string()),
+            accepted_answer_uuid: record.accepted_answer_uuid.map(|uuid| uuid.to_string()),
+            tags: record.tags.clone(),
+            score: record.score,
+            created_at: record.created_at.to_string(),
+            deleted_at: record.deleted_at.map(|deleted_at| deleted_at.to_string()),
+            deleted_by: record.deleted_by.map(|uuid| uuid.to_string()),
+        };
+
+        let answers = include_answers.then(|| {
+            records.iter().filter_map(|record| {
+                let (answer_uuid, content, created_at) = (record.answer_uuid?, record.answer_content.clone()?, record.answer_created_at?);
+
+                Some(AnswerDetail {
+                    answer_uuid: answer_uuid.to_string(),
+                    question_uuid: record.question_uuid.to_string(),
+                    content,
+                    author_uuid: record.answer_author_uuid.map(|uuid| uuid.to_string()),
+                    score: record.answer_score,
+                    is_accepted: record.accepted_answer_uuid == Some(answer_uuid),
+                    created_at: created_at.to_string(),
+                    deleted_at: record.answer_deleted_at.map(|deleted_at| deleted_at.to_string()),
+                    deleted_by: record.answer_deleted_by.map(|uuid| uuid.to_string()),
+                })
+            }).collect()
+        });
+
+        Ok(Some(QuestionWithAnswers { question, answers }))
+    }
+
+    async fn update_question(
+        &self,
+        updated_question: Question,
+        question_uuid: String,
+        editor_uuid: String,
+        edit_summary: Option<String>,
+    ) -> Result<QuestionDetail, DBError> {
         let uuid = sqlx::types::Uuid::parse_str(&question_uuid).map_err(|_| DBError::InvalidUUID(question_uuid.clone()))?;
+        let editor_uuid = Uuid::parse_str(&editor_uuid).map_err(|_| DBError::InvalidUUID(editor_uuid.clone()))?;
+
+        let mut tx = self.db.begin().await.map_err(|e| DBError::from_sqlx(e, "Error updating question"))?;
+
+        sqlx::query!(
+            "INSERT INTO question_revisions ( question_uuid, title, description, tags, editor_uuid, edit_summary )
+            SELECT question_uuid, title, description,
+                ARRAY(
+                    SELECT tags.name FROM question_tags JOIN tags ON tags.tag_uuid = question_tags.tag_uuid
+                    WHERE question_tags.question_uuid = questions.question_uuid ORDER BY tags.name
+                ),
+                $2, $3
+            FROM questions WHERE question_uuid = $1",
+            uuid,
+            editor_uuid,
+            edit_summary
+        ).execute(&mut tx).await.map_err(|e| DBError::from_sqlx(e, "Error saving question revision"))?;
+
+        replace_tags(&mut tx, uuid, &updated_question.tags).await
+            .map_err(|e| DBError::from_sqlx(e, "Error tagging question"))?;
 
         let record = sqlx::query!(
-            "UPDATE questions SET title = $1, description = $2 WHERE question_uuid = $3 RETURNING *",
+            r#"UPDATE questions SET title = $1, description = $2 WHERE question_uuid = $3
+            RETURNING question_uuid, title, description, author_uuid, accepted_answer_uuid, created_at, deleted_at, deleted_by,
+                ARRAY(
+                    SELECT tags.name FROM question_tags JOIN tags ON tags.tag_uuid = question_tags.tag_uuid
+                    WHERE question_tags.question_uuid = questions.question_uuid ORDER BY tags.name
+                ) AS "tags!",
+                (SELECT COALESCE(SUM(value), 0) FROM votes WHERE votes.question_uuid = questions.question_uuid) AS "score!""#,
             updated_question.title,
             updated_question.description,
             uuid
-        ).fetch_one(&self.db).await.map_err(|_| DBError::Other("Error updating question".into()))?;
+        ).fetch_optional(&mut tx).await.map_err(|e| DBError::from_sqlx(e, "Error updating question"))?
+        .ok_or_else(|| DBError::not_found("question_not_found", "Question not found"))?;
+
+        tx.commit().await.map_err(|e| DBError::from_sqlx(e, "Error updating question"))?;
 
         Ok(QuestionDetail {
             question_uuid: record.question_uuid.to_string(),
             title: record.title,
             description: record.description,
+            author_uuid: record.author_uuid.map(|uuid| uuid.to_string()),
+            accepted_answer_uuid: record.accepted_answer_uuid.map(|uuid| uuid.to_string()),
+            tags: record.tags,
+            score: record.score,
             created_at: record.created_at.to_string(),
+            deleted_at: record.deleted_at.map(|deleted_at| deleted_at.to_string()),
+            deleted_by: record.deleted_by.map(|uuid| uuid.to_string()),
         })
     }
+
+    async fn search(&self, query: String, limit: i64) -> Result<Vec<QuestionSearchResult>, DBError> {
+        // A question matches on its own title/description or through any of its answers.
+        // Answer matches count for half as much as direct matches.
+        let records = sqlx::query!(
+            r#"WITH query AS (
+                SELECT websearch_to_tsquery('english', $1) AS tsquery, $3 || $4 AS marks,
+                    'StartSel=' || $3 || ', StopSel=' || $4 || ', MaxFragments=2' AS options
+            ),
+            answer_matches AS (
+                SELECT DISTINCT ON (answers.question_uuid)
+                    answers.question_uuid, answers.content, ts_rank(answers.search_vector, query.tsquery) AS rank
+                FROM answers, query
+                WHERE answers.search_vector @@ query.tsquery AND answers.deleted_at IS NULL
+                ORDER BY answers.question_uuid, rank DESC
+            )
+            SELECT
+                questions.question_uuid, questions.title, questions.description, questions.author_uuid,
+                questions.accepted_answer_uuid, questions.created_at, questions.deleted_at, questions.deleted_by,
+                ARRAY(
+                    SELECT tags.name FROM question_tags JOIN tags ON tags.tag_uuid = question_tags.tag_uuid
+                    WHERE question_tags.question_uuid = questions.question_uuid ORDER BY tags.name
+                ) AS "tags!",
+                (SELECT COALESCE(SUM(value), 0) FROM votes WHERE votes.question_uuid = questions.question_uuid) AS "score!",
+                (ts_rank(questions.search_vector, query.tsquery) + COALESCE(answer_matches.rank * 0.5, 0))::real AS "rank!",
+                ts_headline('english', translate(questions.title || ' ' || questions.description, query.marks, ''),
+                    query.tsquery, query.options) AS "snippet!",
+                ts_headline('english', translate(answer_matches.content, query.marks, ''),
+                    query.tsquery, query.options) AS "answer_snippet?"
+            FROM questions
+            CROSS JOIN query
+            LEFT JOIN answer_matches ON answer_matches.question_uuid = questions.question_uuid
+            WHERE questions.deleted_at IS NULL
+                AND (questions.search_vector @@ query.tsquery OR answer_matches.question_uuid IS NOT NULL)
+            ORDER BY "rank!" DESC, questions.created_at DESC
+            LIMIT $2"#,
+            query,
+            limit,
+            text_search::MARK_START.to_string(),
+            text_search::MARK_END.to_string()
+        ).fetch_all(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error searching questions"))?;
+
+        let results = records.into_iter().map(|record| {
+            QuestionSearchResult {
+                question: QuestionDetail {
+                    question_uuid: record.question_uuid.to_string(),
+                    title: record.title,
+                    description: record.description,
+                    author_uuid: record.author_uuid.map(|uuid| uuid.to_string()),
+                    accepted_answer_uuid: record.accepted_answer_uuid.map(|uuid| uuid.to_string()),
+                    tags: record.tags,
+                    score: record.score,
+                    created_at: record.created_at.to_string(),
+                    deleted_at: record.deleted_at.map(|deleted_at| deleted_at.to_string()),
+                    deleted_by: record.deleted_by.map(|uuid| uuid.to_string()),
+                },
+                rank: record.rank,
+                snippet: text_search::escape_snippet(&record.snippet),
+                answer_snippet: record.answer_snippet.as_deref().map(text_search::escape_snippet),
+            }
+        }).collect();
+
+        Ok(results)
+    }
+
+    async fn get_question_author(&self, question_uuid: String, include_deleted: bool) -> Result<Option<String>, DBError> {
+        let uuid = sqlx::types::Uuid::parse_str(&question_uuid).map_err(|_| DBError::InvalidUUID(question_uuid.clone()))?;
+
+        let record = sqlx::query!(
+            "SELECT author_uuid FROM questions WHERE question_uuid = $1 AND ($2 OR deleted_at IS NULL)",
+            uuid,
+            include_deleted
+        )
+            .fetch_optional(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting question author"))?
+            .ok_or_else(|| DBError::not_found("question_not_found", "Question not found"))?;
+
+        Ok(record.author_uuid.map(|uuid| uuid.to_string()))
+    }
+
+    async fn vote_question(&self, question_uuid: String, user_uuid: String, value: i16) -> Result<i64, DBError> {
+        let uuid = Uuid::parse_str(&question_uuid).map_err(|_| DBError::InvalidUUID(question_uuid.clone()))?;
+        let user_uuid = Uuid::parse_str(&user_uuid).map_err(|_| DBError::InvalidUUID(user_uuid.clone()))?;
+
+        ensure_question_not_deleted(&self.db, uuid).await?;
+
+        sqlx::query!(
+            "INSERT INTO votes ( user_uuid, question_uuid, value )
+            VALUES ( $1, $2, $3 )
+            ON CONFLICT ( user_uuid, question_uuid ) WHERE question_uuid IS NOT NULL
+            DO UPDATE SET value = EXCLUDED.value, created_at = CURRENT_TIMESTAMP",
+            user_uuid,
+            uuid,
+            value
+        ).execute(&self.db).await.map_err(|e| {
+            if e.as_database_error().map(|e| e.code().expect("Error reading &dyn DatabaseError code").to_string()) == Some(postgres_error_codes::FOREIGN_KEY_VIOLATION.to_string()) {
+                DBError::InvalidUUID(question_uuid.clone())
+            } else {
+                DBError::from_sqlx(e, "Error voting on question")
+            }
+        })?;
+
+        self.get_score(uuid).await
+    }
+
+    async fn retract_question_vote(&self, question_uuid: String, user_uuid: String) -> Result<i64, DBError> {
+        let uuid = Uuid::parse_str(&question_uuid).map_err(|_| DBError::InvalidUUID(question_uuid.clone()))?;
+        let user_uuid = Uuid::parse_str(&user_uuid).map_err(|_| DBError::InvalidUUID(user_uuid.clone()))?;
+
+        ensure_question_not_deleted(&self.db, uuid).await?;
+
+        sqlx::query!("DELETE FROM votes WHERE user_uuid = $1 AND question_uuid = $2", user_uuid, uuid)
+            .execute(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error retracting question vote"))?;
+
+        self.get_score(uuid).await
+    }
+
+    async fn set_accepted_answer(&self, question_uuid: String, answer_uuid: Option<String>) -> Result<(), DBError> {
+        let uuid = Uuid::parse_str(&question_uuid).map_err(|_| DBError::InvalidUUID(question_uuid.clone()))?;
+        let answer_uuid = answer_uuid
+            .map(|answer_uuid| Uuid::parse_str(&answer_uuid).map_err(|_| DBError::InvalidUUID(answer_uuid.clone())))
+            .transpose()?;
+
+        let updated = sqlx::query!(
+            "UPDATE questions SET accepted_answer_uuid = $1 WHERE question_uuid = $2",
+            answer_uuid,
+            uuid
+        ).execute(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error setting accepted answer"))?;
+
+        if updated.rows_affected() == 0 {
+            return Err(DBError::not_found("question_not_found", "Question not found"));
+        }
+
+        Ok(())
+    }
+
+    async fn get_tags(&self) -> Result<Vec<TagDetail>, DBError> {
+        let records = sqlx::query!(
+            r#"SELECT tags.name, COUNT(*) AS "question_count!"
+            FROM tags
+            JOIN question_tags ON question_tags.tag_uuid = tags.tag_uuid
+            JOIN questions ON questions.question_uuid = question_tags.question_uuid
+            WHERE questions.deleted_at IS NULL
+            GROUP BY tags.name
+            ORDER BY "question_count!" DESC, tags.name"#
+        ).fetch_all(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting tags"))?;
+
+        Ok(records.into_iter().map(|record| TagDetail { name: record.name, question_count: record.question_count }).collect())
+    }
+
+    async fn get_question_revisions(&self, question_uuid: String) -> Result<Vec<QuestionRevision>, DBError> {
+        let uuid = Uuid::parse_str(&question_uuid).map_err(|_| DBError::InvalidUUID(question_uuid.clone()))?;
+
+        let records = sqlx::query!(
+            "SELECT revision_uuid, question_uuid, title, description, tags, editor_uuid, edit_summary, created_at
+            FROM question_revisions
+            WHERE question_uuid = $1
+            ORDER BY created_at DESC, revision_uuid DESC",
+            uuid
+        ).fetch_all(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting question revisions"))?;
+
+        let revisions = records.into_iter().map(|record| {
+            QuestionRevision {
+                revision_uuid: record.revision_uuid.to_string(),
+                question_uuid: record.question_uuid.to_string(),
+                title: record.title,
+                description: record.description,
+                tags: record.tags,
+                editor_uuid: record.editor_uuid.map(|uuid| uuid.to_string()),
+                edit_summary: record.edit_summary,
+                created_at: record.created_at.to_string(),
+            }
+        }).collect();
+
+        Ok(revisions)
+    }
+
+    async fn get_question_revision(&self, question_uuid: String, revision_uuid: String) -> Result<Option<QuestionRevision>, DBError> {
+        let uuid = Uuid::parse_str(&question_uuid).map_err(|_| DBError::InvalidUUID(question_uuid.clone()))?;
+        let revision_uuid = Uuid::parse_str(&revision_uuid).map_err(|_| DBError::InvalidUUID(revision_uuid.clone()))?;
+
+        let record = sqlx::query!(
+            "SELECT revision_uuid, question_uuid, title, description, tags, editor_uuid, edit_summary, created_at
+            FROM question_revisions
+            WHERE revision_uuid = $1 AND question_uuid = $2",
+            revision_uuid,
+            uuid
+        ).fetch_optional(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting question revision"))?;
+
+        Ok(record.map(|record| QuestionRevision {
+            revision_uuid: record.revision_uuid.to_string(),
+            question_uuid: record.question_uuid.to_string(),
+            title: record.title,
+            description: record.description,
+            tags: record.tags,
+            editor_uuid: record.editor_uuid.map(|uuid| uuid.to_string()),
+            edit_summary: record.edit_summary,
+            created_at: record.created_at.to_string(),
+        }))
+    }
+
+    async fn health_check(&self) -> Result<MigrationStatus, DBError> {
+        postgres_health_check(&self.db).await
+    }
 }
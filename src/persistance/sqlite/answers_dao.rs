@@ -0,0 +1,364 @@
+use async_trait::async_trait;
+use sqlx::{types::time::PrimitiveDateTime, FromRow, SqlitePool};
+
+use crate::{
+    models::{Answer, AnswerDetail, AnswerRevision, Cursor, DBError, MigrationStatus, Page, PageRequest},
+    persistance::answers_dao::AnswersDao,
+};
+
+use super::{
+    cursor, ensure_answer_not_deleted, ensure_question_not_deleted, fetch_returning, health_check, is_foreign_key_violation,
+    now, parse_uuid,
+};
+
+#[derive(FromRow)]
+struct AnswerRecord {
+    answer_uuid: String,
+    question_uuid: String,
+    content: String,
+    author_uuid: Option<String>,
+    created_at: PrimitiveDateTime,
+    deleted_at: Option<PrimitiveDateTime>,
+    deleted_by: Option<String>,
+    score: i64,
+    is_accepted: bool,
+}
+
+impl From<AnswerRecord> for AnswerDetail {
+    fn from(record: AnswerRecord) -> Self {
+        AnswerDetail {
+            answer_uuid: record.answer_uuid,
+            question_uuid: record.question_uuid,
+            content: record.content,
+            author_uuid: record.author_uuid,
+            score: record.score,
+            is_accepted: record.is_accepted,
+            created_at: record.created_at.to_string(),
+            deleted_at: record.deleted_at.map(|deleted_at| deleted_at.to_string()),
+            deleted_by: record.deleted_by,
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct RevisionRecord {
+    revision_uuid: String,
+    answer_uuid: String,
+    question_uuid: String,
+    content: String,
+    editor_uuid: Option<String>,
+    edit_summary: Option<String>,
+    created_at: PrimitiveDateTime,
+}
+
+impl From<RevisionRecord> for AnswerRevision {
+    fn from(record: RevisionRecord) -> Self {
+        AnswerRevision {
+            revision_uuid: record.revision_uuid,
+            answer_uuid: record.answer_uuid,
+            question_uuid: record.question_uuid,
+            content: record.content,
+            editor_uuid: record.editor_uuid,
+            edit_summary: record.edit_summary,
+            created_at: record.created_at.to_string(),
+        }
+    }
+}
+
+pub struct SqliteAnswersDao {
+    db: SqlitePool,
+}
+
+impl SqliteAnswersDao {
+    pub fn new(db: SqlitePool) -> Self {
+        Self { db }
+    }
+
+    async fn get_score(&self, answer_uuid: &str) -> Result<i64, DBError> {
+        sqlx::query_scalar("SELECT COALESCE(SUM(value), 0) FROM votes WHERE answer_uuid = $1")
+            .bind(answer_uuid)
+            .fetch_one(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting answer score"))
+    }
+}
+
+#[async_trait]
+impl AnswersDao for SqliteAnswersDao {
+    async fn create_answer(&self, answer: Answer, author_uuid: String) -> Result<AnswerDetail, DBError> {
+        let uuid = parse_uuid(&answer.question_uuid)?;
+        let author_uuid = parse_uuid(&author_uuid)?;
+
+        ensure_question_not_deleted(&self.db, &uuid).await?;
+
+        let record = fetch_returning(
+            sqlx::query_as::<_, AnswerRecord>(
+                "INSERT INTO answers ( question_uuid, content, author_uuid, created_at )
+                VALUES ( $1, $2, $3, $4 )
+                RETURNING answer_uuid, question_uuid, content, author_uuid, created_at, deleted_at, deleted_by,
+                    0 AS score, false AS is_accepted"
+            )
+                .bind(uuid)
+                .bind(&answer.content)
+                .bind(author_uuid)
+                .bind(now()),
+            &self.db,
+        ).await.map_err(|e| {
+            if is_foreign_key_violation(&e) {
+                DBError::InvalidUUID(answer.question_uuid.clone())
+            } else {
+                DBError::from_sqlx(e, "Error creating answer")
+            }
+        })?;
+
+        Ok(record.into())
+    }
+
+    async fn delete_answer(&self, answer_uuid: String, deleted_by: String) -> Result<(), DBError> {
+        let uuid = parse_uuid(&answer_uuid)?;
+        let deleted_by = parse_uuid(&deleted_by)?;
+
+        let deleted = sqlx::query(
+            "UPDATE answers SET deleted_at = $3, deleted_by = $2
+            WHERE answer_uuid = $1 AND deleted_at IS NULL"
+        )
+            .bind(uuid)
+            .bind(deleted_by)
+            .bind(now())
+            .execute(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error deleting answer"))?;
+
+        if deleted.rows_affected() == 0 {
+            return Err(DBError::not_found("answer_not_found", "Answer not found"));
+        }
+
+        Ok(())
+    }
+
+    async fn undelete_answer(&self, answer_uuid: String) -> Result<(), DBError> {
+        let uuid = parse_uuid(&answer_uuid)?;
+
+        let undeleted = sqlx::query("UPDATE answers SET deleted_at = NULL, deleted_by = NULL WHERE answer_uuid = $1")
+            .bind(uuid)
+            .execute(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error undeleting answer"))?;
+
+        if undeleted.rows_affected() == 0 {
+            return Err(DBError::not_found("answer_not_found", "Answer not found"));
+        }
+
+        Ok(())
+    }
+
+    async fn get_answers(&self, question_uuid: String, page: PageRequest, include_deleted: bool) -> Result<Page<AnswerDetail>, DBError> {
+        let uuid = parse_uuid(&question_uuid)?;
+
+        // The accepted answer is pinned to the top of the first page and left out of the keyset order,
+        // the remaining answers follow oldest first. One extra row is fetched to know whether another page exists.
+        let mut records = sqlx::query_as::<_, AnswerRecord>(
+            "SELECT answers.answer_uuid, answers.question_uuid, answers.content, answers.author_uuid, answers.created_at,
+                answers.deleted_at, answers.deleted_by,
+                (SELECT COALESCE(SUM(value), 0) FROM votes WHERE votes.answer_uuid = answers.answer_uuid) AS score,
+                answers.answer_uuid IS questions.accepted_answer_uuid AS is_accepted
+            FROM answers
+            JOIN questions ON questions.question_uuid = answers.question_uuid
+            WHERE answers.question_uuid = $1
+                AND ($5 OR (answers.deleted_at IS NULL AND questions.deleted_at IS NULL))
+                AND CASE WHEN answers.answer_uuid IS questions.accepted_answer_uuid
+                    THEN $2 IS NULL
+                    ELSE $2 IS NULL OR (answers.created_at, answers.answer_uuid) > ($2, $3)
+                END
+            ORDER BY is_accepted DESC, answers.created_at, answers.answer_uuid
+            LIMIT $4"
+        )
+            .bind(uuid)
+            .bind(page.cursor.map(|cursor| cursor.created_at))
+            .bind(page.cursor.map(|cursor| cursor.uuid.to_string()))
+            .bind(page.limit + 1)
+            .bind(include_deleted)
+            .fetch_all(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting answers"))?;
+
+        let next_cursor = if records.len() as i64 > page.limit {
+            records.truncate(page.limit as usize);
+            records.last().map(|record| {
+                if record.is_accepted {
+                    Ok(Cursor::start())
+                } else {
+                    cursor(record.created_at, &record.answer_uuid)
+                }.map(|cursor| cursor.encode())
+            }).transpose()?
+        } else {
+            None
+        };
+
+        Ok(Page { items: records.into_iter().map(AnswerDetail::from).collect(), next_cursor })
+    }
+
+    async fn get_answer(&self, answer_uuid: String, include_deleted: bool) -> Result<Option<AnswerDetail>, DBError> {
+        let uuid = parse_uuid(&answer_uuid)?;
+
+        let record = sqlx::query_as::<_, AnswerRecord>(
+            "SELECT answers.answer_uuid, answers.question_uuid, answers.content, answers.author_uuid, answers.created_at,
+                answers.deleted_at, answers.deleted_by,
+                (SELECT COALESCE(SUM(value), 0) FROM votes WHERE votes.answer_uuid = answers.answer_uuid) AS score,
+                answers.answer_uuid IS questions.accepted_answer_uuid AS is_accepted
+            FROM answers
+            JOIN questions ON questions.question_uuid = answers.question_uuid
+            WHERE answers.answer_uuid = $1
+                AND ($2 OR (answers.deleted_at IS NULL AND questions.deleted_at IS NULL))"
+        )
+            .bind(uuid)
+            .bind(include_deleted)
+            .fetch_optional(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting answer"))?;
+
+        Ok(record.map(AnswerDetail::from))
+    }
+
+    async fn update_answer(
+        &self,
+        updated_answer: Answer,
+        answer_uuid: String,
+        editor_uuid: String,
+        edit_summary: Option<String>,
+    ) -> Result<AnswerDetail, DBError> {
+        let uuid = parse_uuid(&answer_uuid)?;
+        let editor_uuid = parse_uuid(&editor_uuid)?;
+
+        let mut tx = self.db.begin().await.map_err(|e| DBError::from_sqlx(e, "Error updating answer"))?;
+
+        sqlx::query(
+            "INSERT INTO answer_revisions ( answer_uuid, content, editor_uuid, edit_summary, created_at )
+            SELECT answer_uuid, content, $2, $3, $4 FROM answers WHERE answer_uuid = $1"
+        )
+            .bind(&uuid)
+            .bind(editor_uuid)
+            .bind(edit_summary)
+            .bind(now())
+            .execute(&mut tx).await.map_err(|e| DBError::from_sqlx(e, "Error saving answer revision"))?;
+
+        let updated = sqlx::query("UPDATE answers SET content = $1 WHERE answer_uuid = $2")
+            .bind(updated_answer.content)
+            .bind(&uuid)
+            .execute(&mut tx).await.map_err(|e| DBError::from_sqlx(e, "Error updating answer"))?;
+
+        if updated.rows_affected() == 0 {
+            return Err(DBError::not_found("answer_not_found", "Answer not found"));
+        }
+
+        let record = sqlx::query_as::<_, AnswerRecord>(
+            "SELECT answer_uuid, question_uuid, content, author_uuid, created_at, deleted_at, deleted_by,
+                (SELECT COALESCE(SUM(value), 0) FROM votes WHERE votes.answer_uuid = answers.answer_uuid) AS score,
+                EXISTS(SELECT 1 FROM questions WHERE questions.accepted_answer_uuid = answers.answer_uuid) AS is_accepted
+            FROM answers WHERE answer_uuid = $1"
+        )
+            .bind(&uuid)
+            .fetch_one(&mut tx).await.map_err(|e| DBError::from_sqlx(e, "Error updating answer"))?;
+
+        tx.commit().await.map_err(|e| DBError::from_sqlx(e, "Error updating answer"))?;
+
+        Ok(record.into())
+    }
+
+    async fn get_answer_author(&self, answer_uuid: String, include_deleted: bool) -> Result<Option<String>, DBError> {
+        let uuid = parse_uuid(&answer_uuid)?;
+
+        let author_uuid: Option<Option<String>> = sqlx::query_scalar(
+            "SELECT answers.author_uuid FROM answers
+            JOIN questions ON questions.question_uuid = answers.question_uuid
+            WHERE answers.answer_uuid = $1 AND ($2 OR (answers.deleted_at IS NULL AND questions.deleted_at IS NULL))"
+        )
+            .bind(uuid)
+            .bind(include_deleted)
+            .fetch_optional(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting answer author"))?;
+
+        author_uuid.ok_or_else(|| DBError::not_found("answer_not_found", "Answer not found"))
+    }
+
+    async fn vote_answer(&self, answer_uuid: String, user_uuid: String, value: i16) -> Result<i64, DBError> {
+        let uuid = parse_uuid(&answer_uuid)?;
+        let user_uuid = parse_uuid(&user_uuid)?;
+
+        ensure_answer_not_deleted(&self.db, &uuid).await?;
+
+        sqlx::query(
+            "INSERT INTO votes ( user_uuid, answer_uuid, value, created_at )
+            VALUES ( $1, $2, $3, $4 )
+            ON CONFLICT ( user_uuid, answer_uuid ) WHERE answer_uuid IS NOT NULL
+            DO UPDATE SET value = excluded.value, created_at = excluded.created_at"
+        )
+            .bind(user_uuid)
+            .bind(&uuid)
+            .bind(value)
+            .bind(now())
+            .execute(&self.db).await.map_err(|e| {
+                if is_foreign_key_violation(&e) {
+                    DBError::InvalidUUID(answer_uuid.clone())
+                } else {
+                    DBError::from_sqlx(e, "Error voting on answer")
+                }
+            })?;
+
+        self.get_score(&uuid).await
+    }
+
+    async fn retract_answer_vote(&self, answer_uuid: String, user_uuid: String) -> Result<i64, DBError> {
+        let uuid = parse_uuid(&answer_uuid)?;
+        let user_uuid = parse_uuid(&user_uuid)?;
+
+        ensure_answer_not_deleted(&self.db, &uuid).await?;
+
+        sqlx::query("DELETE FROM votes WHERE user_uuid = $1 AND answer_uuid = $2")
+            .bind(user_uuid)
+            .bind(&uuid)
+            .execute(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error retracting answer vote"))?;
+
+        self.get_score(&uuid).await
+    }
+
+    async fn answer_belongs_to_question(&self, answer_uuid: String, question_uuid: String) -> Result<bool, DBError> {
+        let uuid = parse_uuid(&answer_uuid)?;
+        let question_uuid = parse_uuid(&question_uuid)?;
+
+        ensure_answer_not_deleted(&self.db, &uuid).await?;
+
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM answers WHERE answer_uuid = $1 AND question_uuid = $2)")
+            .bind(uuid)
+            .bind(question_uuid)
+            .fetch_one(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error checking answer"))
+    }
+
+    async fn get_answer_revisions(&self, answer_uuid: String) -> Result<Vec<AnswerRevision>, DBError> {
+        let uuid = parse_uuid(&answer_uuid)?;
+
+        let records = sqlx::query_as::<_, RevisionRecord>(
+            "SELECT answer_revisions.revision_uuid, answer_revisions.answer_uuid, answers.question_uuid,
+                answer_revisions.content, answer_revisions.editor_uuid, answer_revisions.edit_summary, answer_revisions.created_at
+            FROM answer_revisions
+            JOIN answers ON answers.answer_uuid = answer_revisions.answer_uuid
+            WHERE answer_revisions.answer_uuid = $1
+            ORDER BY answer_revisions.created_at DESC, answer_revisions.revision_uuid DESC"
+        )
+            .bind(uuid)
+            .fetch_all(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting answer revisions"))?;
+
+        Ok(records.into_iter().map(AnswerRevision::from).collect())
+    }
+
+    async fn get_answer_revision(&self, answer_uuid: String, revision_uuid: String) -> Result<Option<AnswerRevision>, DBError> {
+        let uuid = parse_uuid(&answer_uuid)?;
+        let revision_uuid = parse_uuid(&revision_uuid)?;
+
+        let record = sqlx::query_as::<_, RevisionRecord>(
+            "SELECT answer_revisions.revision_uuid, answer_revisions.answer_uuid, answers.question_uuid,
+                answer_revisions.content, answer_revisions.editor_uuid, answer_revisions.edit_summary, answer_revisions.created_at
+            FROM answer_revisions
+            JOIN answers ON answers.answer_uuid = answer_revisions.answer_uuid
+            WHERE answer_revisions.revision_uuid = $1 AND answer_revisions.answer_uuid = $2"
+        )
+            .bind(revision_uuid)
+            .bind(uuid)
+            .fetch_optional(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting answer revision"))?;
+
+        Ok(record.map(AnswerRevision::from))
+    }
+
+    async fn health_check(&self) -> Result<MigrationStatus, DBError> {
+        health_check(&self.db).await
+    }
+}
@@ -0,0 +1,170 @@
+use async_trait::async_trait;
+use sqlx::{types::time::PrimitiveDateTime, FromRow, SqlitePool};
+
+use crate::{
+    models::{Comment, CommentDetail, CommentParent, DBError, Page, PageRequest},
+    persistance::comments_dao::CommentsDao,
+};
+
+use super::{
+    cursor, ensure_answer_not_deleted, ensure_question_not_deleted, fetch_returning, is_foreign_key_violation, now,
+    parse_uuid,
+};
+
+#[derive(FromRow)]
+struct CommentRecord {
+    comment_uuid: String,
+    question_uuid: Option<String>,
+    answer_uuid: Option<String>,
+    content: String,
+    author_uuid: Option<String>,
+    created_at: PrimitiveDateTime,
+}
+
+impl From<CommentRecord> for CommentDetail {
+    fn from(record: CommentRecord) -> Self {
+        CommentDetail {
+            comment_uuid: record.comment_uuid,
+            question_uuid: record.question_uuid,
+            answer_uuid: record.answer_uuid,
+            content: record.content,
+            author_uuid: record.author_uuid,
+            created_at: record.created_at.to_string(),
+        }
+    }
+}
+
+pub struct SqliteCommentsDao {
+    db: SqlitePool,
+}
+
+impl SqliteCommentsDao {
+    pub fn new(db: SqlitePool) -> Self {
+        Self { db }
+    }
+}
+
+fn parse_optional_uuid(uuid: Option<String>) -> Result<Option<String>, DBError> {
+    uuid.map(|uuid| parse_uuid(&uuid)).transpose()
+}
+
+async fn ensure_parent_not_deleted(db: &SqlitePool, question_uuid: Option<&str>, answer_uuid: Option<&str>) -> Result<(), DBError> {
+    if let Some(question_uuid) = question_uuid {
+        ensure_question_not_deleted(db, question_uuid).await?;
+    }
+    if let Some(answer_uuid) = answer_uuid {
+        ensure_answer_not_deleted(db, answer_uuid).await?;
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl CommentsDao for SqliteCommentsDao {
+    async fn create_comment(&self, comment: Comment, author_uuid: String) -> Result<CommentDetail, DBError> {
+        let question_uuid = parse_optional_uuid(comment.parent.question_uuid.clone())?;
+        let answer_uuid = parse_optional_uuid(comment.parent.answer_uuid.clone())?;
+        let author_uuid = parse_uuid(&author_uuid)?;
+        let parent_uuid = comment.parent.question_uuid.or(comment.parent.answer_uuid).unwrap_or_default();
+
+        ensure_parent_not_deleted(&self.db, question_uuid.as_deref(), answer_uuid.as_deref()).await?;
+
+        let record = fetch_returning(
+            sqlx::query_as::<_, CommentRecord>(
+                "INSERT INTO comments ( question_uuid, answer_uuid, content, author_uuid, created_at )
+                VALUES ( $1, $2, $3, $4, $5 )
+                RETURNING comment_uuid, question_uuid, answer_uuid, content, author_uuid, created_at"
+            )
+                .bind(question_uuid)
+                .bind(answer_uuid)
+                .bind(comment.content)
+                .bind(author_uuid)
+                .bind(now()),
+            &self.db,
+        ).await.map_err(|e| {
+            if is_foreign_key_violation(&e) {
+                DBError::InvalidUUID(parent_uuid.clone())
+            } else {
+                DBError::from_sqlx(e, "Error creating comment")
+            }
+        })?;
+
+        Ok(record.into())
+    }
+
+    async fn delete_comment(&self, comment_uuid: String) -> Result<(), DBError> {
+        let uuid = parse_uuid(&comment_uuid)?;
+
+        let deleted = sqlx::query("DELETE FROM comments WHERE comment_uuid = $1")
+            .bind(uuid)
+            .execute(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error deleting comment"))?;
+
+        if deleted.rows_affected() == 0 {
+            return Err(DBError::not_found("comment_not_found", "Comment not found"));
+        }
+
+        Ok(())
+    }
+
+    async fn get_comments(&self, parent: CommentParent, page: PageRequest, include_deleted: bool) -> Result<Page<CommentDetail>, DBError> {
+        let question_uuid = parse_optional_uuid(parent.question_uuid)?;
+        let answer_uuid = parse_optional_uuid(parent.answer_uuid)?;
+
+        if !include_deleted {
+            ensure_parent_not_deleted(&self.db, question_uuid.as_deref(), answer_uuid.as_deref()).await?;
+        }
+
+        // Oldest comments first; one extra row is fetched to know whether another page exists.
+        let mut records = sqlx::query_as::<_, CommentRecord>(
+            "SELECT comment_uuid, question_uuid, answer_uuid, content, author_uuid, created_at
+            FROM comments
+            WHERE question_uuid IS $1 AND answer_uuid IS $2
+                AND ($3 IS NULL OR (created_at, comment_uuid) > ($3, $4))
+            ORDER BY created_at, comment_uuid
+            LIMIT $5"
+        )
+            .bind(question_uuid)
+            .bind(answer_uuid)
+            .bind(page.cursor.map(|cursor| cursor.created_at))
+            .bind(page.cursor.map(|cursor| cursor.uuid.to_string()))
+            .bind(page.limit + 1)
+            .fetch_all(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting comments"))?;
+
+        let next_cursor = if records.len() as i64 > page.limit {
+            records.truncate(page.limit as usize);
+            records.last().map(|record| cursor(record.created_at, &record.comment_uuid).map(|cursor| cursor.encode())).transpose()?
+        } else {
+            None
+        };
+
+        Ok(Page { items: records.into_iter().map(CommentDetail::from).collect(), next_cursor })
+    }
+
+    async fn update_comment(&self, updated_comment: Comment, comment_uuid: String) -> Result<CommentDetail, DBError> {
+        let uuid = parse_uuid(&comment_uuid)?;
+
+        let record = fetch_returning(
+            sqlx::query_as::<_, CommentRecord>(
+                "UPDATE comments SET content = $1 WHERE comment_uuid = $2
+                RETURNING comment_uuid, question_uuid, answer_uuid, content, author_uuid, created_at"
+            )
+                .bind(updated_comment.content)
+                .bind(uuid),
+            &self.db,
+        ).await.map_err(|e| match e {
+            sqlx::Error::RowNotFound => DBError::not_found("comment_not_found", "Comment not found"),
+            e => DBError::from_sqlx(e, "Error updating comment"),
+        })?;
+
+        Ok(record.into())
+    }
+
+    async fn get_comment_author(&self, comment_uuid: String) -> Result<Option<String>, DBError> {
+        let uuid = parse_uuid(&comment_uuid)?;
+
+        let author_uuid: Option<Option<String>> = sqlx::query_scalar("SELECT author_uuid FROM comments WHERE comment_uuid = $1")
+            .bind(uuid)
+            .fetch_optional(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting comment author"))?;
+
+        author_uuid.ok_or_else(|| DBError::not_found("comment_not_found", "Comment not found"))
+    }
+}
@@ -0,0 +1,143 @@
+//! DAOs backed by SQLite, for deployments that cannot run Postgres. Their schema lives in `sqlite_migrations`
+//! and mirrors the Postgres one, with UUIDs stored as lowercase text.
+
+use std::{sync::Mutex, time::Duration};
+
+use sqlx::{
+    migrate::Migrator,
+    query::QueryAs,
+    sqlite::{SqliteArguments, SqliteRow},
+    types::{
+        time::{OffsetDateTime, PrimitiveDateTime},
+        Uuid,
+    },
+    FromRow, Sqlite, SqlitePool,
+};
+
+use super::migration_status;
+use crate::models::{sqlite_error_codes, Cursor, DBError, MigrationStatus};
+
+mod answers_dao;
+mod comments_dao;
+mod questions_dao;
+mod users_dao;
+
+pub use answers_dao::SqliteAnswersDao;
+pub use comments_dao::SqliteCommentsDao;
+pub use questions_dao::SqliteQuestionsDao;
+pub use users_dao::SqliteUsersDao;
+
+/// Migrations of the SQLite schema, bundled into the binary.
+pub static MIGRATOR: Migrator = sqlx::migrate!("./sqlite_migrations");
+
+/// Current UTC time at microsecond precision, like a Postgres `TIMESTAMP`. SQLite's own clock only has
+/// millisecond precision, so the DAOs set timestamps themselves. Every call returns a later timestamp than
+/// the previous one so that rows keep their insertion order.
+fn now() -> PrimitiveDateTime {
+    static LAST_TIMESTAMP: Mutex<Option<PrimitiveDateTime>> = Mutex::new(None);
+
+    let now = OffsetDateTime::now_utc();
+    let now = PrimitiveDateTime::new(now.date(), now.time())
+        .replace_nanosecond(now.microsecond() * 1_000)
+        .expect("Microseconds are always a valid nanosecond value");
+
+    let mut last_timestamp = LAST_TIMESTAMP.lock().unwrap_or_else(|e| e.into_inner());
+    let now = match *last_timestamp {
+        Some(last) if now <= last => last + Duration::from_micros(1),
+        _ => now,
+    };
+
+    *last_timestamp = Some(now);
+    now
+}
+
+/// Parses the UUID and returns it in the lowercase form it is stored in.
+fn parse_uuid(uuid: &str) -> Result<String, DBError> {
+    Uuid::parse_str(uuid)
+        .map(|uuid| uuid.to_string())
+        .map_err(|_| DBError::InvalidUUID(uuid.to_owned()))
+}
+
+fn cursor(created_at: PrimitiveDateTime, uuid: &str) -> Result<Cursor, DBError> {
+    let uuid = Uuid::parse_str(uuid).map_err(|_| DBError::InvalidUUID(uuid.to_owned()))?;
+    Ok(Cursor { created_at, uuid })
+}
+
+/// Runs an `INSERT`/`UPDATE ... RETURNING` statement to completion and returns its single row. `fetch_one`
+/// stops stepping after the first row, which keeps SQLite's implicit write transaction open, and defers
+/// constraint errors, until the connection happens to reset the statement.
+async fn fetch_returning<'q, T>(query: QueryAs<'q, Sqlite, T, SqliteArguments<'q>>, db: &SqlitePool) -> Result<T, sqlx::Error>
+where
+    T: Send + Unpin + for<'r> FromRow<'r, SqliteRow>,
+{
+    query.fetch_all(db).await?.pop().ok_or(sqlx::Error::RowNotFound)
+}
+
+// Statements with a RETURNING clause report a failed foreign key check as a generic error, so the message
+// is the only reliable signal there.
+fn is_foreign_key_violation(e: &sqlx::Error) -> bool {
+    e.as_database_error().is_some_and(|e| {
+        e.code().as_deref() == Some(sqlite_error_codes::FOREIGN_KEY_VIOLATION)
+            || e.message() == "FOREIGN KEY constraint failed"
+    })
+}
+
+fn is_unique_violation(e: &sqlx::Error) -> bool {
+    e.as_database_error().and_then(|e| e.code()).as_deref() == Some(sqlite_error_codes::UNIQUE_VIOLATION)
+}
+
+fn to_json(tags: &[String]) -> String {
+    serde_json::to_string(tags).expect("A list of strings always serializes")
+}
+
+/// Parses a JSON array of tag names, as stored in SQLite, sorted by name.
+fn from_json(tags: &str) -> Result<Vec<String>, DBError> {
+    let mut tags: Vec<String> = serde_json::from_str(tags).map_err(|e| DBError::Other(Box::new(e)))?;
+    tags.sort();
+    Ok(tags)
+}
+
+/// Fails with `NotFound` if the question exists but is deleted. Missing questions are left to the caller.
+async fn ensure_question_not_deleted(db: &SqlitePool, question_uuid: &str) -> Result<(), DBError> {
+    let deleted: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM questions WHERE question_uuid = $1 AND deleted_at IS NOT NULL)"
+    ).bind(question_uuid).fetch_one(db).await.map_err(|e| DBError::from_sqlx(e, "Error checking question"))?;
+
+    if deleted {
+        return Err(DBError::not_found("question_not_found", "Question not found"));
+    }
+
+    Ok(())
+}
+
+/// Like `ensure_question_not_deleted`, an answer also counts as deleted when its question is.
+async fn ensure_answer_not_deleted(db: &SqlitePool, answer_uuid: &str) -> Result<(), DBError> {
+    let deleted: bool = sqlx::query_scalar(
+        "SELECT EXISTS(
+            SELECT 1 FROM answers
+            JOIN questions ON questions.question_uuid = answers.question_uuid
+            WHERE answers.answer_uuid = $1 AND (answers.deleted_at IS NOT NULL OR questions.deleted_at IS NOT NULL)
+        )"
+    ).bind(answer_uuid).fetch_one(db).await.map_err(|e| DBError::from_sqlx(e, "Error checking answer"))?;
+
+    if deleted {
+        return Err(DBError::not_found("answer_not_found", "Answer not found"));
+    }
+
+    Ok(())
+}
+
+/// Pings the SQLite database and reports the status of its migrations.
+async fn health_check(db: &SqlitePool) -> Result<MigrationStatus, DBError> {
+    sqlx::query("SELECT 1").execute(db).await.map_err(|e| DBError::from_sqlx(e, "Error pinging database"))?;
+
+    let applied = sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success").fetch_all(db).await;
+    match applied {
+        Ok(applied) => Ok(migration_status(&MIGRATOR, &applied)),
+        // SQLite has no code of its own for a missing table.
+        Err(e) if e.as_database_error().is_some_and(|e| e.message().starts_with("no such table")) => {
+            Ok(MigrationStatus::Untracked)
+        }
+        Err(e) => Err(DBError::from_sqlx(e, "Error reading applied migrations")),
+    }
+}
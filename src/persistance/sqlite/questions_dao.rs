@@ -0,0 +1,505 @@
+use async_trait::async_trait;
+use sqlx::{types::time::PrimitiveDateTime, FromRow, Sqlite, SqlitePool, Transaction};
+
+use crate::{
+    models::{
+        AnswerDetail, DBError, MigrationStatus, Page, PageRequest, Question, QuestionDetail, QuestionRevision,
+        QuestionSearchResult, QuestionWithAnswers, TagDetail,
+    },
+    persistance::{questions_dao::QuestionsDao, text_search},
+};
+
+use super::{
+    cursor, ensure_question_not_deleted, from_json, health_check, is_foreign_key_violation, now, parse_uuid, to_json,
+};
+
+/// Columns making up a `QuestionRecord`, with the tags as a JSON array and the score summed from the votes.
+const QUESTION_COLUMNS: &str = "questions.question_uuid, questions.title, questions.description, questions.author_uuid,
+    questions.accepted_answer_uuid, questions.created_at, questions.deleted_at, questions.deleted_by,
+    (
+        SELECT json_group_array(tags.name) FROM question_tags JOIN tags ON tags.tag_uuid = question_tags.tag_uuid
+        WHERE question_tags.question_uuid = questions.question_uuid
+    ) AS tags,
+    (SELECT COALESCE(SUM(value), 0) FROM votes WHERE votes.question_uuid = questions.question_uuid) AS score";
+
+#[derive(FromRow)]
+struct QuestionRecord {
+    question_uuid: String,
+    title: String,
+    description: String,
+    author_uuid: Option<String>,
+    accepted_answer_uuid: Option<String>,
+    created_at: PrimitiveDateTime,
+    deleted_at: Option<PrimitiveDateTime>,
+    deleted_by: Option<String>,
+    tags: String,
+    score: i64,
+}
+
+impl QuestionRecord {
+    fn into_detail(self) -> Result<QuestionDetail, DBError> {
+        Ok(QuestionDetail {
+            question_uuid: self.question_uuid,
+            title: self.title,
+            description: self.description,
+            author_uuid: self.author_uuid,
+            accepted_answer_uuid: self.accepted_answer_uuid,
+            tags: from_json(&self.tags)?,
+            score: self.score,
+            created_at: self.created_at.to_string(),
+            deleted_at: self.deleted_at.map(|deleted_at| deleted_at.to_string()),
+            deleted_by: self.deleted_by,
+        })
+    }
+}
+
+/// A question joined with one of its answers, whose columns are null if it has none.
+#[derive(FromRow)]
+struct QuestionAnswerRecord {
+    #[sqlx(flatten)]
+    question: QuestionRecord,
+    answer_uuid: Option<String>,
+    answer_content: Option<String>,
+    answer_author_uuid: Option<String>,
+    answer_created_at: Option<PrimitiveDateTime>,
+    answer_deleted_at: Option<PrimitiveDateTime>,
+    answer_deleted_by: Option<String>,
+    answer_score: i64,
+}
+
+impl QuestionAnswerRecord {
+    fn answer_detail(&self) -> Option<AnswerDetail> {
+        let answer_uuid = self.answer_uuid.clone()?;
+
+        Some(AnswerDetail {
+            is_accepted: self.question.accepted_answer_uuid.as_ref() == Some(&answer_uuid),
+            answer_uuid,
+            question_uuid: self.question.question_uuid.clone(),
+            content: self.answer_content.clone()?,
+            author_uuid: self.answer_author_uuid.clone(),
+            score: self.answer_score,
+            created_at: self.answer_created_at?.to_string(),
+            deleted_at: self.answer_deleted_at.map(|deleted_at| deleted_at.to_string()),
+            deleted_by: self.answer_deleted_by.clone(),
+        })
+    }
+}
+
+#[derive(FromRow)]
+struct RevisionRecord {
+    revision_uuid: String,
+    question_uuid: String,
+    title: String,
+    description: String,
+    tags: String,
+    editor_uuid: Option<String>,
+    edit_summary: Option<String>,
+    created_at: PrimitiveDateTime,
+}
+
+impl RevisionRecord {
+    fn into_revision(self) -> Result<QuestionRevision, DBError> {
+        Ok(QuestionRevision {
+            revision_uuid: self.revision_uuid,
+            question_uuid: self.question_uuid,
+            title: self.title,
+            description: self.description,
+            tags: from_json(&self.tags)?,
+            editor_uuid: self.editor_uuid,
+            edit_summary: self.edit_summary,
+            created_at: self.created_at.to_string(),
+        })
+    }
+}
+
+pub struct SqliteQuestionsDao {
+    db: SqlitePool,
+}
+
+impl SqliteQuestionsDao {
+    pub fn new(db: SqlitePool) -> Self {
+        Self { db }
+    }
+
+    async fn get_question_detail(&self, question_uuid: &str) -> Result<QuestionDetail, DBError> {
+        sqlx::query_as::<_, QuestionRecord>(&format!("SELECT {} FROM questions WHERE question_uuid = $1", QUESTION_COLUMNS))
+            .bind(question_uuid)
+            .fetch_one(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting question"))?
+            .into_detail()
+    }
+
+    async fn get_score(&self, question_uuid: &str) -> Result<i64, DBError> {
+        sqlx::query_scalar("SELECT COALESCE(SUM(value), 0) FROM votes WHERE question_uuid = $1")
+            .bind(question_uuid)
+            .fetch_one(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting question score"))
+    }
+}
+
+/// Replaces the question's tags, creating any tag that doesn't exist yet.
+async fn replace_tags(tx: &mut Transaction<'_, Sqlite>, question_uuid: &str, tags: &[String]) -> Result<(), sqlx::Error> {
+    let tags = to_json(tags);
+
+    sqlx::query("DELETE FROM question_tags WHERE question_uuid = $1")
+        .bind(question_uuid)
+        .execute(&mut *tx).await?;
+
+    // The `WHERE true` keeps SQLite from reading `ON CONFLICT` as part of a join.
+    sqlx::query("INSERT INTO tags ( name ) SELECT value FROM json_each($1) WHERE true ON CONFLICT ( name ) DO NOTHING")
+        .bind(&tags)
+        .execute(&mut *tx).await?;
+
+    sqlx::query(
+        "INSERT INTO question_tags ( question_uuid, tag_uuid )
+        SELECT $1, tag_uuid FROM tags WHERE name IN (SELECT value FROM json_each($2))"
+    )
+        .bind(question_uuid)
+        .bind(&tags)
+        .execute(&mut *tx).await?;
+
+    Ok(())
+}
+
+#[async_trait]
+impl QuestionsDao for SqliteQuestionsDao {
+    async fn create_question(&self, question: Question, author_uuid: String) -> Result<QuestionDetail, DBError> {
+        let author_uuid = parse_uuid(&author_uuid)?;
+
+        let mut tx = self.db.begin().await.map_err(|e| DBError::from_sqlx(e, "Error creating question"))?;
+
+        let question_uuid: String = sqlx::query_scalar(
+            "INSERT INTO questions ( title, description, author_uuid, created_at )
+            VALUES ( $1, $2, $3, $4 )
+            RETURNING question_uuid"
+        )
+            .bind(&question.title)
+            .bind(&question.description)
+            .bind(&author_uuid)
+            .bind(now())
+            .fetch_one(&mut tx).await.map_err(|e| DBError::from_sqlx(e, "Error creating question"))?;
+
+        replace_tags(&mut tx, &question_uuid, &question.tags).await
+            .map_err(|e| DBError::from_sqlx(e, "Error tagging question"))?;
+
+        tx.commit().await.map_err(|e| DBError::from_sqlx(e, "Error creating question"))?;
+
+        self.get_question_detail(&question_uuid).await
+    }
+
+    async fn delete_question(&self, question_uuid: String, deleted_by: String) -> Result<(), DBError> {
+        let uuid = parse_uuid(&question_uuid)?;
+        let deleted_by = parse_uuid(&deleted_by)?;
+
+        let deleted = sqlx::query(
+            "UPDATE questions SET deleted_at = $3, deleted_by = $2
+            WHERE question_uuid = $1 AND deleted_at IS NULL"
+        )
+            .bind(uuid)
+            .bind(deleted_by)
+            .bind(now())
+            .execute(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error deleting question"))?;
+
+        if deleted.rows_affected() == 0 {
+            return Err(DBError::not_found("question_not_found", "Question not found"));
+        }
+
+        Ok(())
+    }
+
+    async fn undelete_question(&self, question_uuid: String) -> Result<(), DBError> {
+        let uuid = parse_uuid(&question_uuid)?;
+
+        let undeleted = sqlx::query("UPDATE questions SET deleted_at = NULL, deleted_by = NULL WHERE question_uuid = $1")
+            .bind(uuid)
+            .execute(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error undeleting question"))?;
+
+        if undeleted.rows_affected() == 0 {
+            return Err(DBError::not_found("question_not_found", "Question not found"));
+        }
+
+        Ok(())
+    }
+
+    async fn get_questions(&self, page: PageRequest, tagged: Vec<String>, include_deleted: bool) -> Result<Page<QuestionDetail>, DBError> {
+        // Newest questions first; one extra row is fetched to know whether another page exists.
+        let mut records = sqlx::query_as::<_, QuestionRecord>(&format!(
+            "SELECT {} FROM questions
+            WHERE ($1 IS NULL OR (created_at, question_uuid) < ($1, $2))
+                AND ($5 OR deleted_at IS NULL)
+                AND (json_array_length($4) = 0 OR question_uuid IN (
+                    SELECT question_tags.question_uuid FROM question_tags
+                    JOIN tags ON tags.tag_uuid = question_tags.tag_uuid
+                    WHERE tags.name IN (SELECT value FROM json_each($4))
+                    GROUP BY question_tags.question_uuid
+                    HAVING COUNT(*) = json_array_length($4)
+                ))
+            ORDER BY created_at DESC, question_uuid DESC
+            LIMIT $3",
+            QUESTION_COLUMNS
+        ))
+            .bind(page.cursor.map(|cursor| cursor.created_at))
+            .bind(page.cursor.map(|cursor| cursor.uuid.to_string()))
+            .bind(page.limit + 1)
+            .bind(to_json(&tagged))
+            .bind(include_deleted)
+            .fetch_all(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting questions"))?;
+
+        let next_cursor = if records.len() as i64 > page.limit {
+            records.truncate(page.limit as usize);
+            records.last().map(|record| cursor(record.created_at, &record.question_uuid).map(|cursor| cursor.encode())).transpose()?
+        } else {
+            None
+        };
+
+        let questions = records.into_iter().map(QuestionRecord::into_detail).collect::<Result<_, _>>()?;
+
+        Ok(Page { items: questions, next_cursor })
+    }
+
+    async fn get_question(&self, question_uuid: String, include_answers: bool, include_deleted: bool) -> Result<Option<QuestionWithAnswers>, DBError> {
+        let uuid = parse_uuid(&question_uuid)?;
+
+        // One row per answer, or a single row with null answer columns when there are none or they weren't asked for.
+        let records = sqlx::query_as::<_, QuestionAnswerRecord>(&format!(
+            "SELECT {},
+                answers.answer_uuid, answers.content AS answer_content, answers.author_uuid AS answer_author_uuid,
+                answers.created_at AS answer_created_at, answers.deleted_at AS answer_deleted_at,
+                answers.deleted_by AS answer_deleted_by,
+                (SELECT COALESCE(SUM(value), 0) FROM votes WHERE votes.answer_uuid = answers.answer_uuid) AS answer_score
+            FROM questions
+            LEFT JOIN answers ON answers.question_uuid = questions.question_uuid
+                AND $2 AND ($3 OR answers.deleted_at IS NULL)
+            WHERE questions.question_uuid = $1 AND ($3 OR questions.deleted_at IS NULL)
+            ORDER BY (answers.answer_uuid = questions.accepted_answer_uuid) IS TRUE DESC, answers.created_at, answers.answer_uuid",
+            QUESTION_COLUMNS
+        ))
+            .bind(uuid)
+            .bind(include_answers)
+            .bind(include_deleted)
+            .fetch_all(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting question"))?;
+
+        let answers = include_answers.then(|| records.iter().filter_map(QuestionAnswerRecord::answer_detail).collect());
+
+        let Some(record) = records.into_iter().next() else {
+            return Ok(None);
+        };
+
+        Ok(Some(QuestionWithAnswers { question: record.question.into_detail()?, answers }))
+    }
+
+    async fn update_question(
+        &self,
+        updated_question: Question,
+        question_uuid: String,
+        editor_uuid: String,
+        edit_summary: Option<String>,
+    ) -> Result<QuestionDetail, DBError> {
+        let uuid = parse_uuid(&question_uuid)?;
+        let editor_uuid = parse_uuid(&editor_uuid)?;
+
+        let mut tx = self.db.begin().await.map_err(|e| DBError::from_sqlx(e, "Error updating question"))?;
+
+        sqlx::query(
+            "INSERT INTO question_revisions ( question_uuid, title, description, tags, editor_uuid, edit_summary, created_at )
+            SELECT question_uuid, title, description,
+                (
+                    SELECT json_group_array(tags.name) FROM question_tags JOIN tags ON tags.tag_uuid = question_tags.tag_uuid
+                    WHERE question_tags.question_uuid = questions.question_uuid
+                ),
+                $2, $3, $4
+            FROM questions WHERE question_uuid = $1"
+        )
+            .bind(&uuid)
+            .bind(editor_uuid)
+            .bind(edit_summary)
+            .bind(now())
+            .execute(&mut tx).await.map_err(|e| DBError::from_sqlx(e, "Error saving question revision"))?;
+
+        replace_tags(&mut tx, &uuid, &updated_question.tags).await
+            .map_err(|e| DBError::from_sqlx(e, "Error tagging question"))?;
+
+        let updated = sqlx::query("UPDATE questions SET title = $1, description = $2 WHERE question_uuid = $3")
+            .bind(updated_question.title)
+            .bind(updated_question.description)
+            .bind(&uuid)
+            .execute(&mut tx).await.map_err(|e| DBError::from_sqlx(e, "Error updating question"))?;
+
+        if updated.rows_affected() == 0 {
+            return Err(DBError::not_found("question_not_found", "Question not found"));
+        }
+
+        tx.commit().await.map_err(|e| DBError::from_sqlx(e, "Error updating question"))?;
+
+        self.get_question_detail(&uuid).await
+    }
+
+    async fn search(&self, query: String, limit: i64) -> Result<Vec<QuestionSearchResult>, DBError> {
+        let terms = text_search::terms(&query);
+        if terms.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // SQLite has no stemming full text search built in, so rows containing every term are narrowed down
+        // here with LIKE and ranked with the same word matching as the in-memory backend.
+        let patterns = to_json(&terms.iter().map(|term| format!("%{}%", text_search::stem(term))).collect::<Vec<_>>());
+
+        let records = sqlx::query_as::<_, QuestionRecord>(&format!(
+            "SELECT {} FROM questions
+            WHERE questions.deleted_at IS NULL AND NOT EXISTS (
+                SELECT 1 FROM json_each($1) AS pattern
+                WHERE questions.title || ' ' || questions.description NOT LIKE pattern.value
+                    AND NOT EXISTS (
+                        SELECT 1 FROM answers
+                        WHERE answers.question_uuid = questions.question_uuid
+                            AND answers.deleted_at IS NULL AND answers.content LIKE pattern.value
+                    )
+            )",
+            QUESTION_COLUMNS
+        ))
+            .bind(&patterns)
+            .fetch_all(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error searching questions"))?;
+
+        let question_uuids: Vec<String> = records.iter().map(|record| record.question_uuid.clone()).collect();
+        let answers: Vec<(String, String)> = sqlx::query_as(
+            "SELECT question_uuid, content FROM answers
+            WHERE deleted_at IS NULL AND question_uuid IN (SELECT value FROM json_each($1))"
+        )
+            .bind(to_json(&question_uuids))
+            .fetch_all(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error searching questions"))?;
+
+        let mut results = vec![];
+        for record in records {
+            let created_at = record.created_at;
+            let question_uuid = record.question_uuid.clone();
+            let question = record.into_detail()?;
+            let question_answers = answers.iter()
+                .filter(|(answer_question_uuid, _)| *answer_question_uuid == question_uuid)
+                .map(|(_, content)| content.as_str());
+
+            if let Some(result) = text_search::search_result(question, question_answers, &terms) {
+                results.push((result, created_at));
+            }
+        }
+        results.sort_by(|(a, a_created_at), (b, b_created_at)| b.rank.total_cmp(&a.rank).then(b_created_at.cmp(a_created_at)));
+
+        Ok(results.into_iter().take(limit.max(0) as usize).map(|(result, _)| result).collect())
+    }
+
+    async fn get_question_author(&self, question_uuid: String, include_deleted: bool) -> Result<Option<String>, DBError> {
+        let uuid = parse_uuid(&question_uuid)?;
+
+        let author_uuid: Option<Option<String>> = sqlx::query_scalar(
+            "SELECT author_uuid FROM questions WHERE question_uuid = $1 AND ($2 OR deleted_at IS NULL)"
+        )
+            .bind(uuid)
+            .bind(include_deleted)
+            .fetch_optional(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting question author"))?;
+
+        author_uuid.ok_or_else(|| DBError::not_found("question_not_found", "Question not found"))
+    }
+
+    async fn vote_question(&self, question_uuid: String, user_uuid: String, value: i16) -> Result<i64, DBError> {
+        let uuid = parse_uuid(&question_uuid)?;
+        let user_uuid = parse_uuid(&user_uuid)?;
+
+        ensure_question_not_deleted(&self.db, &uuid).await?;
+
+        sqlx::query(
+            "INSERT INTO votes ( user_uuid, question_uuid, value, created_at )
+            VALUES ( $1, $2, $3, $4 )
+            ON CONFLICT ( user_uuid, question_uuid ) WHERE question_uuid IS NOT NULL
+            DO UPDATE SET value = excluded.value, created_at = excluded.created_at"
+        )
+            .bind(user_uuid)
+            .bind(&uuid)
+            .bind(value)
+            .bind(now())
+            .execute(&self.db).await.map_err(|e| {
+                if is_foreign_key_violation(&e) {
+                    DBError::InvalidUUID(question_uuid.clone())
+                } else {
+                    DBError::from_sqlx(e, "Error voting on question")
+                }
+            })?;
+
+        self.get_score(&uuid).await
+    }
+
+    async fn retract_question_vote(&self, question_uuid: String, user_uuid: String) -> Result<i64, DBError> {
+        let uuid = parse_uuid(&question_uuid)?;
+        let user_uuid = parse_uuid(&user_uuid)?;
+
+        ensure_question_not_deleted(&self.db, &uuid).await?;
+
+        sqlx::query("DELETE FROM votes WHERE user_uuid = $1 AND question_uuid = $2")
+            .bind(user_uuid)
+            .bind(&uuid)
+            .execute(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error retracting question vote"))?;
+
+        self.get_score(&uuid).await
+    }
+
+    async fn set_accepted_answer(&self, question_uuid: String, answer_uuid: Option<String>) -> Result<(), DBError> {
+        let uuid = parse_uuid(&question_uuid)?;
+        let answer_uuid = answer_uuid.map(|answer_uuid| parse_uuid(&answer_uuid)).transpose()?;
+
+        let updated = sqlx::query("UPDATE questions SET accepted_answer_uuid = $1 WHERE question_uuid = $2")
+            .bind(answer_uuid)
+            .bind(uuid)
+            .execute(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error setting accepted answer"))?;
+
+        if updated.rows_affected() == 0 {
+            return Err(DBError::not_found("question_not_found", "Question not found"));
+        }
+
+        Ok(())
+    }
+
+    async fn get_tags(&self) -> Result<Vec<TagDetail>, DBError> {
+        let records: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT tags.name, COUNT(*) AS question_count
+            FROM tags
+            JOIN question_tags ON question_tags.tag_uuid = tags.tag_uuid
+            JOIN questions ON questions.question_uuid = question_tags.question_uuid
+            WHERE questions.deleted_at IS NULL
+            GROUP BY tags.name
+            ORDER BY question_count DESC, tags.name"
+        ).fetch_all(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting tags"))?;
+
+        Ok(records.into_iter().map(|(name, question_count)| TagDetail { name, question_count }).collect())
+    }
+
+    async fn get_question_revisions(&self, question_uuid: String) -> Result<Vec<QuestionRevision>, DBError> {
+        let uuid = parse_uuid(&question_uuid)?;
+
+        let records = sqlx::query_as::<_, RevisionRecord>(
+            "SELECT revision_uuid, question_uuid, title, description, tags, editor_uuid, edit_summary, created_at
+            FROM question_revisions
+            WHERE question_uuid = $1
+            ORDER BY created_at DESC, revision_uuid DESC"
+        )
+            .bind(uuid)
+            .fetch_all(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting question revisions"))?;
+
+        records.into_iter().map(RevisionRecord::into_revision).collect()
+    }
+
+    async fn get_question_revision(&self, question_uuid: String, revision_uuid: String) -> Result<Option<QuestionRevision>, DBError> {
+        let uuid = parse_uuid(&question_uuid)?;
+        let revision_uuid = parse_uuid(&revision_uuid)?;
+
+        let record = sqlx::query_as::<_, RevisionRecord>(
+            "SELECT revision_uuid, question_uuid, title, description, tags, editor_uuid, edit_summary, created_at
+            FROM question_revisions
+            WHERE revision_uuid = $1 AND question_uuid = $2"
+        )
+            .bind(revision_uuid)
+            .bind(uuid)
+            .fetch_optional(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting question revision"))?;
+
+        record.map(RevisionRecord::into_revision).transpose()
+    }
+
+    async fn health_check(&self) -> Result<MigrationStatus, DBError> {
+        health_check(&self.db).await
+    }
+}
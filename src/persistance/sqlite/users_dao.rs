@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use sqlx::{types::time::PrimitiveDateTime, FromRow, SqlitePool};
+
+use crate::{
+    models::{DBError, Role, UserCredentials, UserDetail},
+    persistance::users_dao::UsersDao,
+};
+
+use super::{fetch_returning, is_unique_violation, now, parse_uuid};
+
+#[derive(FromRow)]
+struct UserRecord {
+    user_uuid: String,
+    username: String,
+    role: String,
+    created_at: PrimitiveDateTime,
+}
+
+impl UserRecord {
+    fn into_detail(self) -> Result<UserDetail, DBError> {
+        Ok(UserDetail {
+            user_uuid: self.user_uuid,
+            username: self.username,
+            role: self.role.parse::<Role>().map_err(|e| DBError::Other(e.into()))?,
+            created_at: self.created_at.to_string(),
+        })
+    }
+}
+
+pub struct SqliteUsersDao {
+    db: SqlitePool,
+}
+
+impl SqliteUsersDao {
+    pub fn new(db: SqlitePool) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl UsersDao for SqliteUsersDao {
+    async fn create_user(&self, username: String, password_hash: String) -> Result<UserDetail, DBError> {
+        fetch_returning(
+            sqlx::query_as::<_, UserRecord>(
+                "INSERT INTO users ( username, password_hash, created_at )
+                VALUES ( $1, $2, $3 )
+                RETURNING user_uuid, username, role, created_at"
+            )
+                .bind(&username)
+                .bind(password_hash)
+                .bind(now()),
+            &self.db,
+        ).await.map_err(|e| {
+            if is_unique_violation(&e) {
+                DBError::DuplicateUsername(username.clone())
+            } else {
+                DBError::from_sqlx(e, "Error creating user")
+            }
+        })?
+        .into_detail()
+    }
+
+    async fn get_user_credentials(&self, username: String) -> Result<Option<UserCredentials>, DBError> {
+        let record: Option<(String, String)> = sqlx::query_as("SELECT user_uuid, password_hash FROM users WHERE username = $1")
+            .bind(username)
+            .fetch_optional(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting user"))?;
+
+        Ok(record.map(|(user_uuid, password_hash)| UserCredentials { user_uuid, password_hash }))
+    }
+
+    async fn create_session(&self, user_uuid: String, token_hash: String, ttl_seconds: i64) -> Result<String, DBError> {
+        let uuid = parse_uuid(&user_uuid)?;
+        let expires_at = now() + Duration::from_secs(ttl_seconds.max(0) as u64);
+
+        sqlx::query("INSERT INTO sessions ( token_hash, user_uuid, created_at, expires_at ) VALUES ( $1, $2, $3, $4 )")
+            .bind(token_hash)
+            .bind(uuid)
+            .bind(now())
+            .bind(expires_at)
+            .execute(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error creating session"))?;
+
+        Ok(expires_at.to_string())
+    }
+
+    async fn get_session_user(&self, token_hash: String) -> Result<Option<UserDetail>, DBError> {
+        let record = sqlx::query_as::<_, UserRecord>(
+            "SELECT users.user_uuid, users.username, users.role, users.created_at
+            FROM sessions
+            JOIN users ON users.user_uuid = sessions.user_uuid
+            WHERE sessions.token_hash = $1 AND sessions.expires_at > $2"
+        )
+            .bind(token_hash)
+            .bind(now())
+            .fetch_optional(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting session"))?;
+
+        record.map(UserRecord::into_detail).transpose()
+    }
+
+    async fn delete_session(&self, token_hash: String) -> Result<(), DBError> {
+        sqlx::query("DELETE FROM sessions WHERE token_hash = $1")
+            .bind(token_hash)
+            .execute(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error deleting session"))?;
+
+        Ok(())
+    }
+}
@@ -1,494 +1,2751 @@
-mod answers_tests {
-    use sqlx::PgPool;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::persistance::{
+    answers_dao::{AnswersDao, AnswersDaoImpl},
+    comments_dao::{CommentsDao, CommentsDaoImpl},
+    questions_dao::{QuestionsDao, QuestionsDaoImpl},
+    users_dao::{UsersDao, UsersDaoImpl},
+};
+
+const TEST_AUTHOR_UUID: &str = "b33bcde3-33bc-3333-b33c-3bcd3b3c33dd";
+
+/// A fresh database for one scenario, with DAOs of the backend it belongs to.
+#[async_trait]
+trait TestDatabase {
+    fn questions_dao(&self) -> Box<dyn QuestionsDao + Sync + Send>;
+    fn answers_dao(&self) -> Box<dyn AnswersDao + Sync + Send>;
+    fn comments_dao(&self) -> Box<dyn CommentsDao + Sync + Send>;
+    fn users_dao(&self) -> Box<dyn UsersDao + Sync + Send>;
+    /// Deletes the question row itself, which the DAOs never do.
+    async fn purge_question(&self, question_uuid: &str) -> Result<(), String>;
+}
 
-    use crate::{
-        models::{Answer, DBError, Question},
-        persistance::{
-            answers_dao::{AnswersDao, AnswersDaoImpl},
-            questions_dao::{QuestionsDao, QuestionsDaoImpl},
-        },
+#[async_trait]
+impl TestDatabase for PgPool {
+    fn questions_dao(&self) -> Box<dyn QuestionsDao + Sync + Send> {
+        Box::new(QuestionsDaoImpl::new(self.clone()))
+    }
+
+    fn answers_dao(&self) -> Box<dyn AnswersDao + Sync + Send> {
+        Box::new(AnswersDaoImpl::new(self.clone()))
+    }
+
+    fn comments_dao(&self) -> Box<dyn CommentsDao + Sync + Send> {
+        Box::new(CommentsDaoImpl::new(self.clone()))
+    }
+
+    fn users_dao(&self) -> Box<dyn UsersDao + Sync + Send> {
+        Box::new(UsersDaoImpl::new(self.clone()))
+    }
+
+    async fn purge_question(&self, question_uuid: &str) -> Result<(), String> {
+        sqlx::query("DELETE FROM questions WHERE question_uuid = $1::uuid")
+            .bind(question_uuid)
+            .execute(self)
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+#[async_trait]
+impl TestDatabase for sqlx::SqlitePool {
+    fn questions_dao(&self) -> Box<dyn QuestionsDao + Sync + Send> {
+        Box::new(crate::persistance::sqlite::SqliteQuestionsDao::new(self.clone()))
+    }
+
+    fn answers_dao(&self) -> Box<dyn AnswersDao + Sync + Send> {
+        Box::new(crate::persistance::sqlite::SqliteAnswersDao::new(self.clone()))
+    }
+
+    fn comments_dao(&self) -> Box<dyn CommentsDao + Sync + Send> {
+        Box::new(crate::persistance::sqlite::SqliteCommentsDao::new(self.clone()))
+    }
+
+    fn users_dao(&self) -> Box<dyn UsersDao + Sync + Send> {
+        Box::new(crate::persistance::sqlite::SqliteUsersDao::new(self.clone()))
+    }
+
+    async fn purge_question(&self, question_uuid: &str) -> Result<(), String> {
+        sqlx::query("DELETE FROM questions WHERE question_uuid = $1")
+            .bind(question_uuid)
+            .execute(self)
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+
+        Ok(())
+    }
+}
+
+/// Runs every scenario against a fresh Postgres database and, with the `sqlite` feature, a fresh SQLite one.
+macro_rules! dao_tests {
+    ($(async fn $name:ident($pool:ident) -> Result<(), String> $body:block)*) => {
+        $(
+            mod $name {
+                use super::*;
+
+                #[sqlx::test]
+                async fn postgres($pool: sqlx::PgPool) -> Result<(), String> $body
+
+                #[cfg(feature = "sqlite")]
+                #[sqlx::test(migrations = "./sqlite_migrations")]
+                async fn sqlite($pool: sqlx::SqlitePool) -> Result<(), String> $body
+            }
+        )*
     };
+}
 
-    #[sqlx::test]
-    async fn create_answer_should_fail_with_malformed_uuid(pool: PgPool) -> Result<(), String> {
-        let answer_doa = AnswersDaoImpl::new(pool);
+async fn create_test_user(pool: &impl TestDatabase, username: &str) -> Result<String, String> {
+    let user = pool.users_dao()
+        .create_user(username.to_owned(), "test password hash".to_owned())
+        .await
+        .map_err(|e| format!("{:?}", e))?;
 
-        let result = answer_doa
-            .create_answer(Answer {
-                question_uuid: "malformed".to_owned(),
-                content: "test content".to_owned(),
-            })
-            .await;
+    Ok(user.user_uuid)
+}
+
+mod answers_tests {
+    use super::{create_test_user, TestDatabase, TEST_AUTHOR_UUID};
+
+    use crate::models::{Answer, Cursor, DBError, PageRequest, Question};
+
+    dao_tests! {
+        async fn create_answer_should_fail_with_malformed_uuid(pool) -> Result<(), String> {
+            let author_uuid = TEST_AUTHOR_UUID.to_owned();
+            let answer_doa = pool.answers_dao();
+
+            let result = answer_doa
+                .create_answer(Answer {
+                    question_uuid: "malformed".to_owned(),
+                    content: "test content".to_owned(),
+                }, author_uuid.clone())
+                .await;
+
+            if result.is_ok() {
+                return Err(format!(
+                    "Expected an error but got the following result: {:?}",
+                    result.unwrap()
+                ));
+            }
+
+            if let Err(DBError::InvalidUUID(_)) = result {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Expected an invalid UUID error but got the following error: {:?}",
+                    result.err()
+                ))
+            }
+        }
+
+        async fn create_answer_should_fail_with_non_existent_uuid(pool) -> Result<(), String> {
+            let author_uuid = TEST_AUTHOR_UUID.to_owned();
+            let answer_doa = pool.answers_dao();
+
+            let result = answer_doa
+                .create_answer(Answer {
+                    question_uuid: "a22abcd2-22ab-2222-a22b-2abc2a2b22cc".to_owned(),
+                    content: "test content".to_owned(),
+                }, author_uuid.clone())
+                .await;
+
+            if result.is_ok() {
+                return Err(format!(
+                    "Expected an error but got the following result: {:?}",
+                    result.unwrap()
+                ));
+            }
+
+            if let Err(DBError::InvalidUUID(_)) = result {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Expected an invalid UUID error but got the following error: {:?}",
+                    result.err()
+                ))
+            }
+        }
 
-        if result.is_ok() {
-            return Err(format!(
-                "Expected an error but got the following result: {:?}",
-                result.unwrap()
-            ));
+        async fn create_answer_should_fail_if_database_error_occurs(pool) -> Result<(), String> {
+            let author_uuid = TEST_AUTHOR_UUID.to_owned();
+            let answer_doa = pool.answers_dao();
+
+            pool.close().await;
+
+            let result = answer_doa
+                .create_answer(Answer {
+                    question_uuid: "a22abcd2-22ab-2222-a22b-2abc2a2b22cc".to_owned(),
+                    content: "test content".to_owned(),
+                }, author_uuid.clone())
+                .await;
+
+            if result.is_ok() {
+                return Err(format!(
+                    "Expected an error but got the following result: {:?}",
+                    result.unwrap()
+                ));
+            }
+
+            if let Err(DBError::Unavailable { .. }) = result {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Expected an Unavailable error but got the following error: {:?}",
+                    result.err()
+                ))
+            }
         }
 
-        if let Err(DBError::InvalidUUID(_)) = result {
+        async fn create_answer_should_succeed(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let question_doa = pool.questions_dao();
+            let answer_doa = pool.answers_dao();
+
+            let result = question_doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let result = answer_doa
+                .create_answer(Answer {
+                    question_uuid: result.question_uuid,
+                    content: "test content".to_owned(),
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if result.content != "test content" {
+                return Err("Incorrect answer content".to_owned());
+            }
+
             Ok(())
-        } else {
-            Err(format!(
-                "Expected an invalid UUID error but got the following error: {:?}",
-                result.err()
-            ))
         }
-    }
 
-    #[sqlx::test]
-    async fn create_answer_should_fail_with_non_existent_uuid(pool: PgPool) -> Result<(), String> {
-        let answer_doa = AnswersDaoImpl::new(pool);
+        async fn delete_answer_should_fail_with_malformed_uuid(pool) -> Result<(), String> {
+            let answer_doa = pool.answers_dao();
+
+            let result = answer_doa.delete_answer("malformed".to_owned(), TEST_AUTHOR_UUID.to_owned()).await;
+
+            if result.is_ok() {
+                return Err(format!(
+                    "Expected an error but got the following result: {:?}",
+                    result.unwrap()
+                ));
+            }
+
+            if let Err(DBError::InvalidUUID(_)) = result {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Expected an invalid UUID error but got the following error: {:?}",
+                    result.err()
+                ))
+            }
+        }
 
-        let result = answer_doa
-            .create_answer(Answer {
-                question_uuid: "a22abcd2-22ab-2222-a22b-2abc2a2b22cc".to_owned(),
-                content: "test content".to_owned(),
-            })
-            .await;
+        async fn delete_answer_should_fail_if_database_error_occurs(pool) -> Result<(), String> {
+            let answer_doa = pool.answers_dao();
+
+            pool.close().await;
+
+            let result = answer_doa
+                .delete_answer("a22abcd2-22ab-2222-a22b-2abc2a2b22cc".to_owned(), TEST_AUTHOR_UUID.to_owned())
+                .await;
+
+            if result.is_ok() {
+                return Err(format!(
+                    "Expected an error but got the following result: {:?}",
+                    result.unwrap()
+                ));
+            }
+
+            if let Err(DBError::Unavailable { .. }) = result {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Expected an Unavailable error but got the following error: {:?}",
+                    result.err()
+                ))
+            }
+        }
+
+        async fn delete_answer_should_succeed(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let question_doa = pool.questions_dao();
+            let answer_doa = pool.answers_dao();
+
+            let question = question_doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let result = answer_doa
+                .create_answer(Answer {
+                    question_uuid: question.question_uuid.clone(),
+                    content: "test content".to_owned(),
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            answer_doa
+                .delete_answer(result.answer_uuid, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let results = answer_doa
+                .get_answers(question.question_uuid.clone(), PageRequest::default(), false)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if !results.items.is_empty() {
+                return Err("Answer was not deleted".to_owned());
+            }
+
+            Ok(())
+        }
+
+        async fn get_answers_should_fail_with_malformed_uuid(pool) -> Result<(), String> {
+            let answer_doa = pool.answers_dao();
+
+            let result = answer_doa.get_answers("malformed".to_owned(), PageRequest::default(), false).await;
+
+            if result.is_ok() {
+                return Err(format!(
+                    "Expected an error but got the following result: {:?}",
+                    result.unwrap()
+                ));
+            }
+
+            if let Err(DBError::InvalidUUID(_)) = result {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Expected an invalid UUID error but got the following error: {:?}",
+                    result.err()
+                ))
+            }
+        }
 
-        if result.is_ok() {
-            return Err(format!(
-                "Expected an error but got the following result: {:?}",
-                result.unwrap()
-            ));
+        async fn get_answers_should_fail_if_database_error_occurs(pool) -> Result<(), String> {
+            let answer_doa = pool.answers_dao();
+
+            pool.close().await;
+
+            let result = answer_doa
+                .get_answers("a22abcd2-22ab-2222-a22b-2abc2a2b22cc".to_owned(), PageRequest::default(), false)
+                .await;
+
+            if result.is_ok() {
+                return Err(format!(
+                    "Expected an error but got the following result: {:?}",
+                    result.unwrap()
+                ));
+            }
+
+            if let Err(DBError::Unavailable { .. }) = result {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Expected an Unavailable error but got the following error: {:?}",
+                    result.err()
+                ))
+            }
         }
 
-        if let Err(DBError::InvalidUUID(_)) = result {
+        async fn get_answers_should_succeed(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let question_doa = pool.questions_dao();
+            let answer_doa = pool.answers_dao();
+
+            let question = question_doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let result = answer_doa
+                .create_answer(Answer {
+                    question_uuid: question.question_uuid.clone(),
+                    content: "test content".to_owned(),
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let results = answer_doa
+                .get_answers(question.question_uuid.clone(), PageRequest::default(), false)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if results.items.len() != 1 {
+                return Err("Incorrect number of results returned.".to_owned());
+            }
+
+            if results.items.first().unwrap().answer_uuid != result.answer_uuid {
+                return Err("Incorrect answer returned.".to_owned());
+            }
+
             Ok(())
-        } else {
-            Err(format!(
-                "Expected an invalid UUID error but got the following error: {:?}",
-                result.err()
-            ))
         }
-    }
 
-    #[sqlx::test]
-    async fn create_answer_should_fail_if_database_error_occurs(
-        pool: PgPool,
-    ) -> Result<(), String> {
-        let answer_doa = AnswersDaoImpl::new(pool.clone());
+        async fn get_answers_should_paginate(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let question_doa = pool.questions_dao();
+            let answer_doa = pool.answers_dao();
+
+            let question = question_doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let mut created = Vec::new();
+
+            for i in 0..3 {
+                let answer = answer_doa
+                    .create_answer(Answer {
+                        question_uuid: question.question_uuid.clone(),
+                        content: format!("test content {}", i),
+                    }, author_uuid.clone())
+                    .await
+                    .map_err(|e| format!("{:?}", e))?;
+
+                created.push(answer.answer_uuid);
+            }
+
+            let first_page = answer_doa
+                .get_answers(question.question_uuid.clone(), PageRequest { limit: 2, cursor: None }, false)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let cursor = first_page
+                .next_cursor
+                .as_deref()
+                .and_then(Cursor::decode)
+                .ok_or("Expected a cursor for the next page")?;
+
+            let second_page = answer_doa
+                .get_answers(question.question_uuid.clone(), PageRequest { limit: 2, cursor: Some(cursor) }, false)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if second_page.next_cursor.is_some() {
+                return Err("Expected the second page to be the last one".to_owned());
+            }
+
+            let returned: Vec<String> = first_page
+                .items
+                .into_iter()
+                .chain(second_page.items)
+                .map(|answer| answer.answer_uuid)
+                .collect();
+
+            if returned != created {
+                return Err(format!("Expected answers {:?} but got {:?}", created, returned));
+            }
 
-        pool.close().await;
+            Ok(())
+        }
 
-        let result = answer_doa
-            .create_answer(Answer {
-                question_uuid: "a22abcd2-22ab-2222-a22b-2abc2a2b22cc".to_owned(),
-                content: "test content".to_owned(),
-            })
-            .await;
+        async fn vote_answer_should_update_score(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let voter = create_test_user(&pool, "voter").await?;
+            let question_doa = pool.questions_dao();
+            let answer_doa = pool.answers_dao();
+
+            let question = question_doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let answer = answer_doa
+                .create_answer(Answer {
+                    question_uuid: question.question_uuid.clone(),
+                    content: "test content".to_owned(),
+                }, author_uuid)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let score = answer_doa
+                .vote_answer(answer.answer_uuid.clone(), voter.clone(), 1)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if score != 1 {
+                return Err(format!("Incorrect score after voting: {}", score));
+            }
+
+            let results = answer_doa
+                .get_answers(question.question_uuid.clone(), PageRequest::default(), false)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if results.items.first().map(|answer| answer.score) != Some(1) {
+                return Err("Incorrect score listed".to_owned());
+            }
+
+            let score = answer_doa
+                .retract_answer_vote(answer.answer_uuid, voter)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if score != 0 {
+                return Err(format!("Incorrect score after retracting: {}", score));
+            }
 
-        if result.is_ok() {
-            return Err(format!(
-                "Expected an error but got the following result: {:?}",
-                result.unwrap()
-            ));
+            Ok(())
         }
 
-        if let Err(DBError::Other(_)) = result {
+        async fn get_answers_should_pin_accepted_answer(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let question_doa = pool.questions_dao();
+            let answer_doa = pool.answers_dao();
+
+            let question = question_doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let mut created = Vec::new();
+
+            for i in 0..3 {
+                let answer = answer_doa
+                    .create_answer(Answer {
+                        question_uuid: question.question_uuid.clone(),
+                        content: format!("test content {}", i),
+                    }, author_uuid.clone())
+                    .await
+                    .map_err(|e| format!("{:?}", e))?;
+
+                created.push(answer.answer_uuid);
+            }
+
+            let accepted = created[2].clone();
+
+            if !answer_doa
+                .answer_belongs_to_question(accepted.clone(), question.question_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?
+            {
+                return Err("Answer should belong to its question".to_owned());
+            }
+
+            question_doa
+                .set_accepted_answer(question.question_uuid.clone(), Some(accepted.clone()))
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            // Walk through single-answer pages to make sure the pinned answer doesn't break the keyset order.
+            let mut returned = Vec::new();
+            let mut cursor = None;
+
+            loop {
+                let page = answer_doa
+                    .get_answers(question.question_uuid.clone(), PageRequest { limit: 1, cursor }, false)
+                    .await
+                    .map_err(|e| format!("{:?}", e))?;
+
+                for answer in page.items {
+                    if answer.is_accepted != (answer.answer_uuid == accepted) {
+                        return Err(format!("Incorrect is_accepted flag on {:?}", answer));
+                    }
+
+                    returned.push(answer.answer_uuid);
+                }
+
+                match page.next_cursor {
+                    Some(next_cursor) => cursor = Cursor::decode(&next_cursor),
+                    None => break,
+                }
+            }
+
+            let expected = vec![accepted, created[0].clone(), created[1].clone()];
+
+            if returned != expected {
+                return Err(format!("Expected answers {:?} but got {:?}", expected, returned));
+            }
+
             Ok(())
-        } else {
-            Err(format!(
-                "Expected an Other error but got the following error: {:?}",
-                result.err()
-            ))
         }
-    }
 
-    #[sqlx::test]
-    async fn create_answer_should_succeed(pool: PgPool) -> Result<(), String> {
-        let question_doa = QuestionsDaoImpl::new(pool.clone());
-        let answer_doa = AnswersDaoImpl::new(pool);
+        async fn answer_belongs_to_question_should_reject_other_question(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let question_doa = pool.questions_dao();
+            let answer_doa = pool.answers_dao();
+
+            let mut questions = Vec::new();
+
+            for _ in 0..2 {
+                let question = question_doa
+                    .create_question(Question {
+                        title: "test title".to_owned(),
+                        description: "test description".to_owned(),
+                        tags: vec![],
+                    }, author_uuid.clone())
+                    .await
+                    .map_err(|e| format!("{:?}", e))?;
+
+                questions.push(question.question_uuid);
+            }
+
+            let answer = answer_doa
+                .create_answer(Answer {
+                    question_uuid: questions[0].clone(),
+                    content: "test content".to_owned(),
+                }, author_uuid)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let result = answer_doa
+                .answer_belongs_to_question(answer.answer_uuid, questions[1].clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if result {
+                return Err("Answer should not belong to another question".to_owned());
+            }
 
-        let result = question_doa
-            .create_question(Question {
-                title: "test title".to_owned(),
-                description: "test description".to_owned(),
-            })
-            .await
-            .map_err(|e| format!("{:?}", e))?;
+            Ok(())
+        }
 
-        let result = answer_doa
-            .create_answer(Answer {
-                question_uuid: result.question_uuid,
-                content: "test content".to_owned(),
-            })
-            .await
-            .map_err(|e| format!("{:?}", e))?;
+        async fn update_answer_should_record_previous_content_as_revision(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let question_doa = pool.questions_dao();
+            let answer_doa = pool.answers_dao();
+
+            let question = question_doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let answer = answer_doa
+                .create_answer(Answer {
+                    question_uuid: question.question_uuid.clone(),
+                    content: "original content".to_owned(),
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let updated = answer_doa
+                .update_answer(Answer {
+                    question_uuid: question.question_uuid.clone(),
+                    content: "edited content".to_owned(),
+                }, answer.answer_uuid.clone(), author_uuid.clone(), None)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if updated.content != "edited content" {
+                return Err(format!("Incorrect answer returned: {:?}", updated));
+            }
+
+            let revisions = answer_doa
+                .get_answer_revisions(answer.answer_uuid)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            match revisions.as_slice() {
+                [revision] if revision.content == "original content"
+                    && revision.question_uuid == question.question_uuid
+                    && revision.editor_uuid == Some(author_uuid) => Ok(()),
+                _ => Err(format!("Incorrect revisions recorded: {:?}", revisions)),
+            }
+        }
+
+        async fn get_answers_should_hide_answers_of_deleted_question(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let question_doa = pool.questions_dao();
+            let answer_doa = pool.answers_dao();
+
+            let question = question_doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let answer = answer_doa
+                .create_answer(Answer {
+                    question_uuid: question.question_uuid.clone(),
+                    content: "test content".to_owned(),
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            question_doa
+                .delete_question(question.question_uuid.clone(), author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let visible = answer_doa
+                .get_answers(question.question_uuid.clone(), PageRequest::default(), false)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if !visible.items.is_empty() {
+                return Err(format!("Expected no answers but got {:?}", visible.items));
+            }
+
+            let all = answer_doa
+                .get_answers(question.question_uuid.clone(), PageRequest::default(), true)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if all.items != vec![answer] {
+                return Err(format!("Incorrect answers returned: {:?}", all.items));
+            }
+
+            Ok(())
+        }
 
-        if result.content != "test content".to_owned() {
-            return Err("Incorrect answer content".to_owned());
+        async fn undelete_answer_should_restore_answer(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let question_doa = pool.questions_dao();
+            let answer_doa = pool.answers_dao();
+
+            let question = question_doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let answer = answer_doa
+                .create_answer(Answer {
+                    question_uuid: question.question_uuid.clone(),
+                    content: "test content".to_owned(),
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            answer_doa
+                .delete_answer(answer.answer_uuid.clone(), author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            answer_doa
+                .undelete_answer(answer.answer_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let results = answer_doa
+                .get_answers(question.question_uuid, PageRequest::default(), false)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if results.items != vec![answer] {
+                return Err(format!("Incorrect answers returned: {:?}", results.items));
+            }
+
+            Ok(())
         }
 
-        Ok(())
-    }
+        async fn get_answer_should_hide_deleted_answers(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let question_doa = pool.questions_dao();
+            let answer_doa = pool.answers_dao();
+
+            let question = question_doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let answer = answer_doa
+                .create_answer(Answer {
+                    question_uuid: question.question_uuid.clone(),
+                    content: "test content".to_owned(),
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let found = answer_doa
+                .get_answer(answer.answer_uuid.clone(), false)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if found.as_ref() != Some(&answer) {
+                return Err(format!("Incorrect answer returned: {:?}", found));
+            }
+
+            answer_doa
+                .delete_answer(answer.answer_uuid.clone(), author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let hidden = answer_doa
+                .get_answer(answer.answer_uuid.clone(), false)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if hidden.is_some() {
+                return Err(format!("Expected no answer but got {:?}", hidden));
+            }
+
+            let deleted = answer_doa
+                .get_answer(answer.answer_uuid.clone(), true)
+                .await
+                .map_err(|e| format!("{:?}", e))?
+                .ok_or("Expected the deleted answer")?;
+
+            if deleted.deleted_by != Some(author_uuid) {
+                return Err(format!("Incorrect deleted_by: {:?}", deleted.deleted_by));
+            }
+
+            let missing = answer_doa
+                .get_answer("9f1ae8a6-4bd4-4b43-a1b8-1d7f5c0a3e2b".to_owned(), true)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if missing.is_some() {
+                return Err(format!("Expected no answer but got {:?}", missing));
+            }
 
-    #[sqlx::test]
-    async fn delete_answer_should_fail_with_malformed_uuid(pool: PgPool) -> Result<(), String> {
-        let answer_doa = AnswersDaoImpl::new(pool);
+            Ok(())
+        }
 
-        let result = answer_doa.delete_answer("malformed".to_owned()).await;
+        async fn deleted_answer_should_reject_author_lookup_votes_and_acceptance(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let voter = create_test_user(&pool, "voter").await?;
+            let question_doa = pool.questions_dao();
+            let answer_doa = pool.answers_dao();
+
+            let question = question_doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let answer = answer_doa
+                .create_answer(Answer {
+                    question_uuid: question.question_uuid.clone(),
+                    content: "test content".to_owned(),
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            answer_doa
+                .delete_answer(answer.answer_uuid.clone(), author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let author = answer_doa.get_answer_author(answer.answer_uuid.clone(), false).await;
+            if !matches!(author, Err(DBError::NotFound { code: "answer_not_found", .. })) {
+                return Err(format!("Expected a NotFound error for the author but got: {:?}", author));
+            }
+
+            let author = answer_doa
+                .get_answer_author(answer.answer_uuid.clone(), true)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+            if author != Some(author_uuid.clone()) {
+                return Err(format!("Incorrect author returned: {:?}", author));
+            }
+
+            let vote = answer_doa.vote_answer(answer.answer_uuid.clone(), voter.clone(), 1).await;
+            if !matches!(vote, Err(DBError::NotFound { .. })) {
+                return Err(format!("Expected a NotFound error for the vote but got: {:?}", vote));
+            }
+
+            let retraction = answer_doa.retract_answer_vote(answer.answer_uuid.clone(), voter).await;
+            if !matches!(retraction, Err(DBError::NotFound { .. })) {
+                return Err(format!("Expected a NotFound error for the retraction but got: {:?}", retraction));
+            }
+
+            let belongs = answer_doa
+                .answer_belongs_to_question(answer.answer_uuid.clone(), question.question_uuid.clone())
+                .await;
+            if !matches!(belongs, Err(DBError::NotFound { .. })) {
+                return Err(format!("Expected a NotFound error for acceptance but got: {:?}", belongs));
+            }
 
-        if result.is_ok() {
-            return Err(format!(
-                "Expected an error but got the following result: {:?}",
-                result.unwrap()
-            ));
+            Ok(())
         }
 
-        if let Err(DBError::InvalidUUID(_)) = result {
+        async fn answers_of_deleted_question_should_be_not_found(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let question_doa = pool.questions_dao();
+            let answer_doa = pool.answers_dao();
+
+            let question = question_doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let answer = answer_doa
+                .create_answer(Answer {
+                    question_uuid: question.question_uuid.clone(),
+                    content: "test content".to_owned(),
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            question_doa
+                .delete_question(question.question_uuid.clone(), author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let author = answer_doa.get_answer_author(answer.answer_uuid.clone(), false).await;
+            if !matches!(author, Err(DBError::NotFound { .. })) {
+                return Err(format!("Expected a NotFound error for the author but got: {:?}", author));
+            }
+
+            let created = answer_doa
+                .create_answer(Answer {
+                    question_uuid: question.question_uuid.clone(),
+                    content: "late answer".to_owned(),
+                }, author_uuid)
+                .await;
+            if !matches!(created, Err(DBError::NotFound { code: "question_not_found", .. })) {
+                return Err(format!("Expected a NotFound error for the new answer but got: {:?}", created));
+            }
+
             Ok(())
-        } else {
-            Err(format!(
-                "Expected an invalid UUID error but got the following error: {:?}",
-                result.err()
-            ))
         }
     }
+}
 
-    #[sqlx::test]
-    async fn delete_answer_should_fail_if_database_error_occurs(
-        pool: PgPool,
-    ) -> Result<(), String> {
-        let answer_doa = AnswersDaoImpl::new(pool.clone());
+mod questions_tests {
+    use super::{create_test_user, TestDatabase, TEST_AUTHOR_UUID};
+
+    use crate::models::{Answer, Cursor, DBError, MigrationStatus, PageRequest, Question, TagDetail};
+
+    dao_tests! {
+        async fn create_question_should_fail_if_database_error_occurs(pool) -> Result<(), String> {
+            let author_uuid = TEST_AUTHOR_UUID.to_owned();
+            let doa = pool.questions_dao();
+
+            pool.close().await;
+
+            let result = doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await;
+
+            if result.is_ok() {
+                return Err(format!(
+                    "Expected an error but got the following result: {:?}",
+                    result.unwrap()
+                ));
+            }
+
+            if let Err(DBError::Unavailable { .. }) = result {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Expected an Unavailable error but got the following error: {:?}",
+                    result.err()
+                ))
+            }
+        }
 
-        pool.close().await;
+        async fn create_question_should_succeed(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let doa = pool.questions_dao();
+
+            let result = doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if result.title != "test title"
+                || result.description != "test description"
+            {
+                return Err("Incorrect title or description".to_owned());
+            }
+
+            if result.author_uuid != Some(author_uuid) {
+                return Err("Incorrect author".to_owned());
+            }
 
-        let result = answer_doa
-            .delete_answer("a22abcd2-22ab-2222-a22b-2abc2a2b22cc".to_owned())
-            .await;
+            Ok(())
+        }
+
+        async fn delete_question_should_fail_with_malformed_uuid(pool) -> Result<(), String> {
+            let doa = pool.questions_dao();
+
+            let result = doa.delete_question("malformed".to_owned(), TEST_AUTHOR_UUID.to_owned()).await;
+
+            if result.is_ok() {
+                return Err(format!(
+                    "Expected an error but got the following result: {:?}",
+                    result.unwrap()
+                ));
+            }
+
+            if let Err(DBError::InvalidUUID(_)) = result {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Expected an invalid UUID error but got the following error: {:?}",
+                    result.err()
+                ))
+            }
+        }
+
+        async fn delete_question_should_fail_if_database_error_occurs(pool) -> Result<(), String> {
+            let doa = pool.questions_dao();
+
+            pool.close().await;
+
+            let result = doa
+                .delete_question("a22abcd2-22ab-2222-a22b-2abc2a2b22cc".to_owned(), TEST_AUTHOR_UUID.to_owned())
+                .await;
+
+            if result.is_ok() {
+                return Err(format!(
+                    "Expected an error but got the following result: {:?}",
+                    result.unwrap()
+                ));
+            }
+
+            if let Err(DBError::Unavailable { .. }) = result {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Expected an Unavailable error but got the following error: {:?}",
+                    result.err()
+                ))
+            }
+        }
+
+        async fn delete_question_should_fail_if_question_does_not_exist(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let doa = pool.questions_dao();
+
+            let result = doa
+                .delete_question("a22abcd2-22ab-2222-a22b-2abc2a2b22cc".to_owned(), author_uuid)
+                .await;
+
+            if let Err(DBError::NotFound { .. }) = result {
+                Ok(())
+            } else {
+                Err(format!("Expected a NotFound error but got: {:?}", result))
+            }
+        }
+
+        async fn update_question_should_fail_if_question_does_not_exist(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let doa = pool.questions_dao();
+
+            let result = doa
+                .update_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec![],
+                }, "a22abcd2-22ab-2222-a22b-2abc2a2b22cc".to_owned(), author_uuid, None)
+                .await;
+
+            if let Err(DBError::NotFound { .. }) = result {
+                Ok(())
+            } else {
+                Err(format!("Expected a NotFound error but got: {:?}", result))
+            }
+        }
+
+        async fn delete_question_should_succeed(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let doa = pool.questions_dao();
+
+            let result = doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
 
-        if result.is_ok() {
-            return Err(format!(
-                "Expected an error but got the following result: {:?}",
-                result.unwrap()
-            ));
+            doa.delete_question(result.question_uuid, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let results = doa.get_questions(PageRequest::default(), vec![], false).await.map_err(|e| format!("{:?}", e))?;
+
+            if !results.items.is_empty() {
+                return Err("Question was not deleted".to_owned());
+            }
+
+            Ok(())
         }
 
-        if let Err(DBError::Other(_)) = result {
+        async fn deleted_question_should_only_be_listed_when_requested(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let moderator_uuid = create_test_user(&pool, "test_moderator").await?;
+            let doa = pool.questions_dao();
+
+            let question = doa
+                .create_question(Question {
+                    title: "deleted question".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec!["rust".to_owned()],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            doa.delete_question(question.question_uuid.clone(), moderator_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let search_results = doa.search("deleted".to_owned(), 10).await.map_err(|e| format!("{:?}", e))?;
+
+            if !search_results.is_empty() {
+                return Err(format!("Deleted question was found by search: {:?}", search_results));
+            }
+
+            let tags = doa.get_tags().await.map_err(|e| format!("{:?}", e))?;
+
+            if !tags.is_empty() {
+                return Err(format!("Deleted question was counted in tags: {:?}", tags));
+            }
+
+            let results = doa
+                .get_questions(PageRequest::default(), vec![], true)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            match results.items.as_slice() {
+                [deleted] if deleted.deleted_at.is_some() && deleted.deleted_by == Some(moderator_uuid) => Ok(()),
+                _ => Err(format!("Incorrect questions returned: {:?}", results.items)),
+            }
+        }
+
+        async fn undelete_question_should_restore_question(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let doa = pool.questions_dao();
+
+            let question = doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            doa.delete_question(question.question_uuid.clone(), author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            doa.undelete_question(question.question_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let results = doa
+                .get_questions(PageRequest::default(), vec![], false)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if results.items != vec![question] {
+                return Err(format!("Incorrect questions returned: {:?}", results.items));
+            }
+
             Ok(())
-        } else {
-            Err(format!(
-                "Expected an Other error but got the following error: {:?}",
-                result.err()
-            ))
         }
-    }
 
-    #[sqlx::test]
-    async fn delete_answer_should_succeed(pool: PgPool) -> Result<(), String> {
-        let question_doa = QuestionsDaoImpl::new(pool.clone());
-        let answer_doa = AnswersDaoImpl::new(pool);
+        async fn get_questions_should_fail_if_database_error_occurs(pool) -> Result<(), String> {
+            let doa = pool.questions_dao();
 
-        let question = question_doa
-            .create_question(Question {
-                title: "test title".to_owned(),
+            pool.close().await;
+
+            let result = doa.get_questions(PageRequest::default(), vec![], false).await;
+
+            if result.is_ok() {
+                return Err(format!(
+                    "Expected an error but got the following result: {:?}",
+                    result.unwrap()
+                ));
+            }
+
+            if let Err(DBError::Unavailable { .. }) = result {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Expected an Unavailable error but got the following error: {:?}",
+                    result.err()
+                ))
+            }
+        }
+
+        async fn get_questions_should_succeed(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let doa = pool.questions_dao();
+
+            let result = doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let results = doa.get_questions(PageRequest::default(), vec![], false).await.map_err(|e| format!("{:?}", e))?;
+
+            if results.items.len() != 1 {
+                return Err("Incorrect number of results returned.".to_owned());
+            }
+
+            if results.items.first().unwrap().question_uuid != result.question_uuid {
+                return Err("Incorrect question returned.".to_owned());
+            }
+
+            Ok(())
+        }
+
+        async fn get_question_should_embed_answers_with_accepted_first(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let question_doa = pool.questions_dao();
+            let answer_doa = pool.answers_dao();
+
+            let question = question_doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec!["rust".to_owned()],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let mut created = Vec::new();
+
+            for i in 0..4 {
+                let answer = answer_doa
+                    .create_answer(Answer {
+                        question_uuid: question.question_uuid.clone(),
+                        content: format!("test content {}", i),
+                    }, author_uuid.clone())
+                    .await
+                    .map_err(|e| format!("{:?}", e))?;
+
+                created.push(answer.answer_uuid);
+            }
+
+            question_doa
+                .set_accepted_answer(question.question_uuid.clone(), Some(created[2].clone()))
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+            answer_doa
+                .delete_answer(created[3].clone(), author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let result = question_doa
+                .get_question(question.question_uuid.clone(), true, false)
+                .await
+                .map_err(|e| format!("{:?}", e))?
+                .ok_or("Expected the question to be found")?;
+
+            if result.question.question_uuid != question.question_uuid || result.question.tags != vec!["rust".to_owned()] {
+                return Err(format!("Incorrect question returned: {:?}", result.question));
+            }
+
+            let answers = result.answers.ok_or("Expected answers to be embedded")?;
+            let uuids: Vec<String> = answers.iter().map(|answer| answer.answer_uuid.clone()).collect();
+
+            if uuids != vec![created[2].clone(), created[0].clone(), created[1].clone()] {
+                return Err(format!("Incorrect answers returned: {:?}", answers));
+            }
+
+            if answers.iter().any(|answer| answer.is_accepted != (answer.answer_uuid == created[2])) {
+                return Err(format!("Incorrect is_accepted flags: {:?}", answers));
+            }
+
+            let without_answers = question_doa
+                .get_question(question.question_uuid.clone(), false, false)
+                .await
+                .map_err(|e| format!("{:?}", e))?
+                .ok_or("Expected the question to be found")?;
+
+            if without_answers.answers.is_some() {
+                return Err("Answers should only be embedded when asked for".to_owned());
+            }
+
+            Ok(())
+        }
+
+        async fn get_question_should_return_none_for_missing_or_deleted_question(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let doa = pool.questions_dao();
+
+            let missing = doa
+                .get_question("a22abcd2-22ab-2222-a22b-2abc2a2b22cc".to_owned(), true, true)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if missing.is_some() {
+                return Err("A missing question should not be found".to_owned());
+            }
+
+            let question = doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            doa.delete_question(question.question_uuid.clone(), author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let hidden = doa
+                .get_question(question.question_uuid.clone(), false, false)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+            let shown = doa
+                .get_question(question.question_uuid.clone(), false, true)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if hidden.is_some() || shown.map(|question| question.question.deleted_at.is_some()) != Some(true) {
+                return Err("Deleted questions should only be returned with include_deleted".to_owned());
+            }
+
+            Ok(())
+        }
+
+        async fn get_questions_should_paginate(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let doa = pool.questions_dao();
+
+            let mut created = Vec::new();
+
+            for i in 0..3 {
+                let question = doa
+                    .create_question(Question {
+                        title: format!("test title {}", i),
+                        description: "test description".to_owned(),
+                        tags: vec![],
+                    }, author_uuid.clone())
+                    .await
+                    .map_err(|e| format!("{:?}", e))?;
+
+                created.push(question.question_uuid);
+            }
+
+            let first_page = doa
+                .get_questions(PageRequest { limit: 2, cursor: None }, vec![], false)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let cursor = first_page
+                .next_cursor
+                .as_deref()
+                .and_then(Cursor::decode)
+                .ok_or("Expected a cursor for the next page")?;
+
+            let second_page = doa
+                .get_questions(PageRequest { limit: 2, cursor: Some(cursor) }, vec![], false)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if second_page.next_cursor.is_some() {
+                return Err("Expected the second page to be the last one".to_owned());
+            }
+
+            let returned: Vec<String> = first_page
+                .items
+                .into_iter()
+                .chain(second_page.items)
+                .map(|question| question.question_uuid)
+                .collect();
+
+            created.reverse();
+
+            if returned != created {
+                return Err(format!("Expected questions {:?} but got {:?}", created, returned));
+            }
+
+            Ok(())
+        }
+
+        async fn get_questions_should_filter_by_all_tags(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let doa = pool.questions_dao();
+
+            let tagged_both = doa
+                .create_question(Question {
+                    title: "test title 1".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec!["sqlx".to_owned(), "rust".to_owned()],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            doa.create_question(Question {
+                title: "test title 2".to_owned(),
                 description: "test description".to_owned(),
-            })
+                tags: vec!["rust".to_owned()],
+            }, author_uuid.clone())
             .await
             .map_err(|e| format!("{:?}", e))?;
 
-        let result = answer_doa
-            .create_answer(Answer {
-                question_uuid: question.question_uuid.clone(),
-                content: "test content".to_owned(),
-            })
+            if tagged_both.tags != vec!["rust".to_owned(), "sqlx".to_owned()] {
+                return Err(format!("Incorrect tags returned: {:?}", tagged_both.tags));
+            }
+
+            let results = doa
+                .get_questions(PageRequest::default(), vec!["rust".to_owned(), "sqlx".to_owned()], false)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if results.items != vec![tagged_both] {
+                return Err(format!("Incorrect questions returned: {:?}", results.items));
+            }
+
+            let results = doa
+                .get_questions(PageRequest::default(), vec!["rust".to_owned()], false)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if results.items.len() != 2 {
+                return Err("Incorrect number of results returned.".to_owned());
+            }
+
+            Ok(())
+        }
+
+        async fn update_question_should_replace_tags(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let doa = pool.questions_dao();
+
+            let question = doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec!["rust".to_owned(), "rocket".to_owned()],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let updated = doa
+                .update_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec!["sqlx".to_owned(), "rust".to_owned()],
+                }, question.question_uuid, author_uuid.clone(), None)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if updated.tags != vec!["rust".to_owned(), "sqlx".to_owned()] {
+                return Err(format!("Incorrect tags returned: {:?}", updated.tags));
+            }
+
+            Ok(())
+        }
+
+        async fn update_question_should_record_previous_content_as_revision(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let editor_uuid = create_test_user(&pool, "test_editor").await?;
+            let doa = pool.questions_dao();
+
+            let question = doa
+                .create_question(Question {
+                    title: "original title".to_owned(),
+                    description: "original description".to_owned(),
+                    tags: vec!["rust".to_owned()],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            doa.update_question(Question {
+                title: "edited title".to_owned(),
+                description: "edited description".to_owned(),
+                tags: vec![],
+            }, question.question_uuid.clone(), editor_uuid.clone(), Some("fix title".to_owned()))
             .await
             .map_err(|e| format!("{:?}", e))?;
 
-        answer_doa
-            .delete_answer(result.answer_uuid)
+            let revisions = doa
+                .get_question_revisions(question.question_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let revision = match revisions.as_slice() {
+                [revision] => revision,
+                _ => return Err(format!("Expected exactly one revision but got {:?}", revisions)),
+            };
+
+            if revision.title != "original title"
+                || revision.description != "original description"
+                || revision.tags != vec!["rust".to_owned()]
+                || revision.editor_uuid != Some(editor_uuid)
+                || revision.edit_summary.as_deref() != Some("fix title")
+            {
+                return Err(format!("Incorrect revision recorded: {:?}", revision));
+            }
+
+            let fetched = doa
+                .get_question_revision(question.question_uuid, revision.revision_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if fetched.as_ref() != Some(revision) {
+                return Err(format!("Incorrect revision returned: {:?}", fetched));
+            }
+
+            Ok(())
+        }
+
+        async fn get_question_revision_should_not_return_other_questions_revisions(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let doa = pool.questions_dao();
+
+            let mut question_uuids = Vec::new();
+
+            for _ in 0..2 {
+                let question = doa
+                    .create_question(Question {
+                        title: "test title".to_owned(),
+                        description: "test description".to_owned(),
+                        tags: vec![],
+                    }, author_uuid.clone())
+                    .await
+                    .map_err(|e| format!("{:?}", e))?;
+
+                question_uuids.push(question.question_uuid);
+            }
+
+            doa.update_question(Question {
+                title: "edited title".to_owned(),
+                description: "test description".to_owned(),
+                tags: vec![],
+            }, question_uuids[0].clone(), author_uuid.clone(), None)
             .await
             .map_err(|e| format!("{:?}", e))?;
 
-        let results = answer_doa
-            .get_answers(question.question_uuid.clone())
+            let revisions = doa
+                .get_question_revisions(question_uuids[0].clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let revision = doa
+                .get_question_revision(question_uuids[1].clone(), revisions[0].revision_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if revision.is_some() {
+                return Err(format!("Expected no revision but got {:?}", revision));
+            }
+
+            Ok(())
+        }
+
+        async fn health_check_should_report_applied_migrations(pool) -> Result<(), String> {
+            let status = pool.questions_dao().health_check().await.map_err(|e| format!("{:?}", e))?;
+            if status != MigrationStatus::UpToDate {
+                return Err(format!("Expected migrations to be up to date but got: {:?}", status));
+            }
+
+            let status = pool.answers_dao().health_check().await.map_err(|e| format!("{:?}", e))?;
+            if status != MigrationStatus::UpToDate {
+                return Err(format!("Expected migrations to be up to date but got: {:?}", status));
+            }
+
+            Ok(())
+        }
+
+        async fn health_check_should_fail_if_database_is_closed(pool) -> Result<(), String> {
+            let dao = pool.questions_dao();
+
+            pool.close().await;
+
+            match dao.health_check().await {
+                Err(DBError::Unavailable { .. }) => Ok(()),
+                result => Err(format!("Expected an Unavailable error but got: {:?}", result)),
+            }
+        }
+
+        async fn get_tags_should_count_questions(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let doa = pool.questions_dao();
+
+            for tags in [vec!["rust", "sqlx"], vec!["rust"]] {
+                doa.create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: tags.into_iter().map(str::to_owned).collect(),
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+            }
+
+            let tags = doa.get_tags().await.map_err(|e| format!("{:?}", e))?;
+
+            let expected = vec![
+                TagDetail { name: "rust".to_owned(), question_count: 2 },
+                TagDetail { name: "sqlx".to_owned(), question_count: 1 },
+            ];
+
+            if tags != expected {
+                return Err(format!("Incorrect tags returned: {:?}", tags));
+            }
+
+            Ok(())
+        }
+
+        async fn search_should_rank_and_highlight_matches(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let doa = pool.questions_dao();
+            let answer_doa = pool.answers_dao();
+
+            let direct_match = doa
+                .create_question(Question {
+                    title: "How do lifetimes work?".to_owned(),
+                    description: "I keep fighting the borrow checker over lifetimes.".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let answer_match = doa
+                .create_question(Question {
+                    title: "Compiler error".to_owned(),
+                    description: "My code does not compile.".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            doa.create_question(Question {
+                title: "Unrelated".to_owned(),
+                description: "Something else entirely.".to_owned(),
+                tags: vec![],
+            }, author_uuid.clone())
             .await
             .map_err(|e| format!("{:?}", e))?;
 
-        if results.len() != 0 {
-            return Err("Answer was not deleted".to_owned());
+            answer_doa
+                .create_answer(Answer {
+                    question_uuid: answer_match.question_uuid.clone(),
+                    content: "You need to annotate the lifetime of the reference.".to_owned(),
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let results = doa
+                .search("lifetimes".to_owned(), 10)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let returned: Vec<&str> = results
+                .iter()
+                .map(|result| result.question.question_uuid.as_str())
+                .collect();
+
+            if returned != vec![direct_match.question_uuid.as_str(), answer_match.question_uuid.as_str()] {
+                return Err(format!("Incorrect search results returned: {:?}", returned));
+            }
+
+            if !results[0].snippet.contains("<mark>lifetimes</mark>") {
+                return Err(format!("Question snippet was not highlighted: {}", results[0].snippet));
+            }
+
+            match &results[1].answer_snippet {
+                Some(snippet) if snippet.contains("<mark>lifetime</mark>") => Ok(()),
+                other => Err(format!("Answer snippet was not highlighted: {:?}", other)),
+            }
         }
 
-        Ok(())
-    }
+        async fn search_should_escape_html_in_snippets(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let doa = pool.questions_dao();
+            let answer_doa = pool.answers_dao();
+
+            let question = doa
+                .create_question(Question {
+                    title: "Bold lifetimes".to_owned(),
+                    description: "Why are <b>lifetimes</b> & <script>alert(1)</script> bold?".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            answer_doa
+                .create_answer(Answer {
+                    question_uuid: question.question_uuid.clone(),
+                    content: "Elided lifetimes <img src=x onerror=alert(1)>".to_owned(),
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let results = doa
+                .search("lifetimes".to_owned(), 10)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            // Postgres drops the tags from headlines while the other backends escape them, either way the only
+            // markup left is the highlighting.
+            let is_escaped = |snippet: &str| {
+                snippet.contains("<mark>lifetimes</mark>")
+                    && !snippet.replace("<mark>", "").replace("</mark>", "").contains(['<', '>'])
+            };
+
+            let snippet = &results.first().ok_or("Expected a search result")?.snippet;
+            if !is_escaped(snippet) {
+                return Err(format!("Question snippet was not escaped: {}", snippet));
+            }
+
+            match &results[0].answer_snippet {
+                Some(snippet) if is_escaped(snippet) => Ok(()),
+                other => Err(format!("Answer snippet was not escaped: {:?}", other)),
+            }
+        }
 
-    #[sqlx::test]
-    async fn get_answers_should_fail_with_malformed_uuid(pool: PgPool) -> Result<(), String> {
-        let answer_doa = AnswersDaoImpl::new(pool);
+        async fn get_question_author_should_succeed(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let doa = pool.questions_dao();
 
-        let result = answer_doa.get_answers("malformed".to_owned()).await;
+            let question = doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
 
-        if result.is_ok() {
-            return Err(format!(
-                "Expected an error but got the following result: {:?}",
-                result.unwrap()
-            ));
-        }
+            let result = doa
+                .get_question_author(question.question_uuid, false)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if result != Some(author_uuid) {
+                return Err(format!("Incorrect author returned: {:?}", result));
+            }
+
+            let result = doa
+                .get_question_author("a22abcd2-22ab-2222-a22b-2abc2a2b22cc".to_owned(), false)
+                .await;
+
+            if !matches!(result, Err(DBError::NotFound { .. })) {
+                return Err(format!("Expected a NotFound error for a non-existent question but got: {:?}", result));
+            }
 
-        if let Err(DBError::InvalidUUID(_)) = result {
             Ok(())
-        } else {
-            Err(format!(
-                "Expected an invalid UUID error but got the following error: {:?}",
-                result.err()
-            ))
         }
-    }
 
-    #[sqlx::test]
-    async fn get_answers_should_fail_if_database_error_occurs(pool: PgPool) -> Result<(), String> {
-        let answer_doa = AnswersDaoImpl::new(pool.clone());
+        async fn vote_question_should_update_score(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let first_voter = create_test_user(&pool, "first_voter").await?;
+            let second_voter = create_test_user(&pool, "second_voter").await?;
+            let doa = pool.questions_dao();
 
-        pool.close().await;
+            let question = doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec![],
+                }, author_uuid)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
 
-        let result = answer_doa
-            .get_answers("a22abcd2-22ab-2222-a22b-2abc2a2b22cc".to_owned())
-            .await;
+            let mut scores = Vec::new();
+
+            scores.push(doa.vote_question(question.question_uuid.clone(), first_voter.clone(), 1).await);
+            scores.push(doa.vote_question(question.question_uuid.clone(), second_voter, -1).await);
+            scores.push(doa.vote_question(question.question_uuid.clone(), first_voter.clone(), -1).await);
+            scores.push(doa.retract_question_vote(question.question_uuid.clone(), first_voter).await);
+
+            let scores = scores
+                .into_iter()
+                .collect::<Result<Vec<i64>, _>>()
+                .map_err(|e| format!("{:?}", e))?;
+
+            if scores != vec![1, 0, -2, -1] {
+                return Err(format!("Incorrect scores returned: {:?}", scores));
+            }
 
-        if result.is_ok() {
-            return Err(format!(
-                "Expected an error but got the following result: {:?}",
-                result.unwrap()
-            ));
+            let results = doa.get_questions(PageRequest::default(), vec![], false).await.map_err(|e| format!("{:?}", e))?;
+
+            if results.items.first().map(|question| question.score) != Some(-1) {
+                return Err("Incorrect score listed".to_owned());
+            }
+
+            Ok(())
         }
 
-        if let Err(DBError::Other(_)) = result {
+        async fn deleted_question_should_reject_author_lookup_and_votes(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let voter = create_test_user(&pool, "voter").await?;
+            let doa = pool.questions_dao();
+
+            let question = doa
+                .create_question(Question {
+                    title: "test title".to_owned(),
+                    description: "test description".to_owned(),
+                    tags: vec![],
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            doa.vote_question(question.question_uuid.clone(), voter.clone(), 1)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+            doa.delete_question(question.question_uuid.clone(), author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let author = doa.get_question_author(question.question_uuid.clone(), false).await;
+            if !matches!(author, Err(DBError::NotFound { code: "question_not_found", .. })) {
+                return Err(format!("Expected a NotFound error for the author but got: {:?}", author));
+            }
+
+            let author = doa
+                .get_question_author(question.question_uuid.clone(), true)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+            if author != Some(author_uuid) {
+                return Err(format!("Incorrect author returned: {:?}", author));
+            }
+
+            let vote = doa.vote_question(question.question_uuid.clone(), voter.clone(), -1).await;
+            if !matches!(vote, Err(DBError::NotFound { .. })) {
+                return Err(format!("Expected a NotFound error for the vote but got: {:?}", vote));
+            }
+
+            let retraction = doa.retract_question_vote(question.question_uuid.clone(), voter).await;
+            if !matches!(retraction, Err(DBError::NotFound { .. })) {
+                return Err(format!("Expected a NotFound error for the retraction but got: {:?}", retraction));
+            }
+
             Ok(())
-        } else {
-            Err(format!(
-                "Expected an Other error but got the following error: {:?}",
-                result.err()
-            ))
+        }
+
+        async fn vote_question_should_fail_with_non_existent_uuid(pool) -> Result<(), String> {
+            let voter = create_test_user(&pool, "test_user").await?;
+            let doa = pool.questions_dao();
+
+            let result = doa
+                .vote_question("a22abcd2-22ab-2222-a22b-2abc2a2b22cc".to_owned(), voter, 1)
+                .await;
+
+            if let Err(DBError::InvalidUUID(_)) = result {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Expected an invalid UUID error but got the following result: {:?}",
+                    result
+                ))
+            }
         }
     }
+}
 
-    #[sqlx::test]
-    async fn get_answers_should_succeed(pool: PgPool) -> Result<(), String> {
-        let question_doa = QuestionsDaoImpl::new(pool.clone());
-        let answer_doa = AnswersDaoImpl::new(pool);
+mod comments_tests {
+    use super::{create_test_user, TestDatabase, TEST_AUTHOR_UUID};
 
-        let question = question_doa
+    use crate::models::{Answer, Comment, CommentParent, Cursor, DBError, PageRequest, Question};
+
+    async fn create_test_question(pool: &impl TestDatabase, author_uuid: &str) -> Result<String, String> {
+        let question = pool.questions_dao()
             .create_question(Question {
                 title: "test title".to_owned(),
                 description: "test description".to_owned(),
-            })
+                tags: vec![],
+            }, author_uuid.to_owned())
             .await
             .map_err(|e| format!("{:?}", e))?;
 
-        let result = answer_doa
-            .create_answer(Answer {
-                question_uuid: question.question_uuid.clone(),
-                content: "test content".to_owned(),
-            })
-            .await
-            .map_err(|e| format!("{:?}", e))?;
+        Ok(question.question_uuid)
+    }
 
-        let results = answer_doa
-            .get_answers(question.question_uuid.clone())
-            .await
-            .map_err(|e| format!("{:?}", e))?;
+    fn question_comment(question_uuid: &str, content: &str) -> Comment {
+        Comment {
+            parent: CommentParent {
+                question_uuid: Some(question_uuid.to_owned()),
+                answer_uuid: None,
+            },
+            content: content.to_owned(),
+        }
+    }
+
+    dao_tests! {
+        async fn create_comment_should_fail_with_non_existent_parent(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let doa = pool.comments_dao();
+
+            let result = doa
+                .create_comment(question_comment("a22abcd2-22ab-2222-a22b-2abc2a2b22cc", "test content"), author_uuid)
+                .await;
+
+            if let Err(DBError::InvalidUUID(_)) = result {
+                Ok(())
+            } else {
+                Err(format!("Expected an invalid UUID error but got: {:?}", result))
+            }
+        }
 
-        if results.len() != 1 {
-            return Err("Incorrect number of results returned.".to_owned());
+        async fn create_comment_should_fail_if_database_error_occurs(pool) -> Result<(), String> {
+            let doa = pool.comments_dao();
+
+            pool.close().await;
+
+            let result = doa
+                .create_comment(question_comment("a22abcd2-22ab-2222-a22b-2abc2a2b22cc", "test content"), TEST_AUTHOR_UUID.to_owned())
+                .await;
+
+            if let Err(DBError::Unavailable { .. }) = result {
+                Ok(())
+            } else {
+                Err(format!("Expected an Unavailable error but got: {:?}", result))
+            }
         }
 
-        if results.get(0).unwrap().answer_uuid != result.answer_uuid {
-            return Err("Incorrect answer returned.".to_owned());
+        async fn create_comment_should_succeed_on_answer(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let question_uuid = create_test_question(&pool, &author_uuid).await?;
+
+            let answer = pool.answers_dao()
+                .create_answer(Answer {
+                    question_uuid,
+                    content: "test content".to_owned(),
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let comment = pool.comments_dao()
+                .create_comment(Comment {
+                    parent: CommentParent {
+                        question_uuid: None,
+                        answer_uuid: Some(answer.answer_uuid.clone()),
+                    },
+                    content: "test comment".to_owned(),
+                }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if comment.answer_uuid != Some(answer.answer_uuid) || comment.question_uuid.is_some() {
+                return Err(format!("Comment attached to the wrong post: {:?}", comment));
+            }
+
+            if comment.author_uuid != Some(author_uuid) {
+                return Err(format!("Incorrect author returned: {:?}", comment.author_uuid));
+            }
+
+            Ok(())
         }
 
-        Ok(())
+        async fn get_comments_should_paginate(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let question_uuid = create_test_question(&pool, &author_uuid).await?;
+            let other_question_uuid = create_test_question(&pool, &author_uuid).await?;
+            let doa = pool.comments_dao();
+
+            let mut created = Vec::new();
+
+            for i in 0..3 {
+                let comment = doa
+                    .create_comment(question_comment(&question_uuid, &format!("test comment {}", i)), author_uuid.clone())
+                    .await
+                    .map_err(|e| format!("{:?}", e))?;
+
+                created.push(comment.comment_uuid);
+            }
+
+            doa.create_comment(question_comment(&other_question_uuid, "other comment"), author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let parent = CommentParent {
+                question_uuid: Some(question_uuid),
+                answer_uuid: None,
+            };
+
+            let first_page = doa
+                .get_comments(parent.clone(), PageRequest { limit: 2, cursor: None }, false)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let cursor = first_page
+                .next_cursor
+                .as_deref()
+                .and_then(Cursor::decode)
+                .ok_or("Expected a cursor for the next page")?;
+
+            let second_page = doa
+                .get_comments(parent, PageRequest { limit: 2, cursor: Some(cursor) }, false)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if second_page.next_cursor.is_some() {
+                return Err("Expected the second page to be the last one".to_owned());
+            }
+
+            let returned: Vec<String> = first_page
+                .items
+                .into_iter()
+                .chain(second_page.items)
+                .map(|comment| comment.comment_uuid)
+                .collect();
+
+            if returned != created {
+                return Err(format!("Expected comments {:?} but got {:?}", created, returned));
+            }
+
+            Ok(())
+        }
+
+        async fn comments_on_deleted_question_should_be_not_found(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let question_uuid = create_test_question(&pool, &author_uuid).await?;
+            let doa = pool.comments_dao();
+
+            doa.create_comment(question_comment(&question_uuid, "test comment"), author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            pool.questions_dao()
+                .delete_question(question_uuid.clone(), author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let created = doa
+                .create_comment(question_comment(&question_uuid, "late comment"), author_uuid)
+                .await;
+            if !matches!(created, Err(DBError::NotFound { code: "question_not_found", .. })) {
+                return Err(format!("Expected a NotFound error for the new comment but got: {:?}", created));
+            }
+
+            let parent = CommentParent {
+                question_uuid: Some(question_uuid),
+                answer_uuid: None,
+            };
+
+            let listed = doa.get_comments(parent.clone(), PageRequest::default(), false).await;
+            if !matches!(listed, Err(DBError::NotFound { .. })) {
+                return Err(format!("Expected a NotFound error for the comments but got: {:?}", listed));
+            }
+
+            let listed = doa
+                .get_comments(parent, PageRequest::default(), true)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+            if listed.items.len() != 1 {
+                return Err(format!("Expected the existing comment but got {:?}", listed.items));
+            }
+
+            Ok(())
+        }
+
+        async fn update_comment_should_succeed(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let question_uuid = create_test_question(&pool, &author_uuid).await?;
+            let doa = pool.comments_dao();
+
+            let comment = doa
+                .create_comment(question_comment(&question_uuid, "test comment"), author_uuid)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let updated = doa
+                .update_comment(Comment {
+                    parent: CommentParent::default(),
+                    content: "updated comment".to_owned(),
+                }, comment.comment_uuid)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if updated.content != "updated comment" || updated.question_uuid != Some(question_uuid) {
+                return Err(format!("Incorrect comment returned: {:?}", updated));
+            }
+
+            Ok(())
+        }
+
+        async fn update_comment_should_fail_if_comment_does_not_exist(pool) -> Result<(), String> {
+            let doa = pool.comments_dao();
+
+            let result = doa
+                .update_comment(Comment {
+                    parent: CommentParent::default(),
+                    content: "updated comment".to_owned(),
+                }, "a22abcd2-22ab-2222-a22b-2abc2a2b22cc".to_owned())
+                .await;
+
+            if let Err(DBError::NotFound { .. }) = result {
+                Ok(())
+            } else {
+                Err(format!("Expected a NotFound error but got: {:?}", result))
+            }
+        }
+
+        async fn delete_comment_should_fail_if_comment_does_not_exist(pool) -> Result<(), String> {
+            let doa = pool.comments_dao();
+
+            let result = doa.delete_comment("a22abcd2-22ab-2222-a22b-2abc2a2b22cc".to_owned()).await;
+
+            if let Err(DBError::NotFound { .. }) = result {
+                Ok(())
+            } else {
+                Err(format!("Expected a NotFound error but got: {:?}", result))
+            }
+        }
+
+        async fn comments_should_be_purged_with_their_question(pool) -> Result<(), String> {
+            let author_uuid = create_test_user(&pool, "test_user").await?;
+            let question_uuid = create_test_question(&pool, &author_uuid).await?;
+            let doa = pool.comments_dao();
+
+            let comment = doa
+                .create_comment(question_comment(&question_uuid, "test comment"), author_uuid)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            // The DAO only soft deletes questions, purging the row itself has to take its comments along.
+            pool.purge_question(&question_uuid).await?;
+
+            let result = doa.get_comment_author(comment.comment_uuid).await;
+
+            if !matches!(result, Err(DBError::NotFound { .. })) {
+                return Err(format!("Expected the comment to be deleted but got: {:?}", result));
+            }
+
+            Ok(())
+        }
     }
 }
 
-mod questions_tests {
-    use sqlx::PgPool;
+mod users_tests {
+    use super::TestDatabase;
+
+    use crate::models::{DBError, Role};
+
+    dao_tests! {
+        async fn create_user_should_fail_with_duplicate_username(pool) -> Result<(), String> {
+            let doa = pool.users_dao();
+
+            doa.create_user("test_user".to_owned(), "hash".to_owned())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let result = doa.create_user("test_user".to_owned(), "hash".to_owned()).await;
+
+            if let Err(DBError::DuplicateUsername(_)) = result {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Expected a duplicate username error but got the following result: {:?}",
+                    result
+                ))
+            }
+        }
+
+        async fn get_user_credentials_should_succeed(pool) -> Result<(), String> {
+            let doa = pool.users_dao();
+
+            let user = doa
+                .create_user("test_user".to_owned(), "hash".to_owned())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let credentials = doa
+                .get_user_credentials("test_user".to_owned())
+                .await
+                .map_err(|e| format!("{:?}", e))?
+                .ok_or("Expected credentials to be found")?;
+
+            if credentials.user_uuid != user.user_uuid || credentials.password_hash != "hash" {
+                return Err("Incorrect credentials returned".to_owned());
+            }
+
+            let missing = doa
+                .get_user_credentials("nobody".to_owned())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if missing.is_some() {
+                return Err("Expected no credentials for an unknown user".to_owned());
+            }
+
+            Ok(())
+        }
+
+        async fn create_session_should_fail_with_conflict_for_duplicate_token(pool) -> Result<(), String> {
+            let doa = pool.users_dao();
+
+            let user = doa
+                .create_user("test_user".to_owned(), "hash".to_owned())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            doa.create_session(user.user_uuid.clone(), "token hash".to_owned(), 60)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let result = doa.create_session(user.user_uuid, "token hash".to_owned(), 60).await;
+
+            if let Err(DBError::Conflict { .. }) = result {
+                Ok(())
+            } else {
+                Err(format!("Expected a Conflict error but got: {:?}", result))
+            }
+        }
+
+        async fn session_should_resolve_to_user_until_deleted(pool) -> Result<(), String> {
+            let doa = pool.users_dao();
+
+            let user = doa
+                .create_user("test_user".to_owned(), "hash".to_owned())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            doa.create_session(user.user_uuid.clone(), "token hash".to_owned(), 60)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let session_user = doa
+                .get_session_user("token hash".to_owned())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if session_user.as_ref() != Some(&user) || user.role != Role::User {
+                return Err(format!("Incorrect session user returned: {:?}", session_user));
+            }
+
+            doa.delete_session("token hash".to_owned())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let session_user = doa
+                .get_session_user("token hash".to_owned())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if session_user.is_some() {
+                return Err("Session was not deleted".to_owned());
+            }
+
+            Ok(())
+        }
+
+        async fn expired_session_should_not_resolve(pool) -> Result<(), String> {
+            let doa = pool.users_dao();
+
+            let user = doa
+                .create_user("test_user".to_owned(), "hash".to_owned())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            doa.create_session(user.user_uuid, "token hash".to_owned(), -60)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            let session_user = doa
+                .get_session_user("token hash".to_owned())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            if session_user.is_some() {
+                return Err("Expired session should not resolve to a user".to_owned());
+            }
+
+            Ok(())
+        }
+    }
+}
 
+mod in_memory_tests {
     use crate::{
-        models::{DBError, Question},
-        persistance::questions_dao::{QuestionsDao, QuestionsDaoImpl},
+        models::{Answer, Comment, CommentParent, Cursor, DBError, PageRequest, Question},
+        persistance::{
+            answers_dao::AnswersDao,
+            comments_dao::CommentsDao,
+            in_memory::{InMemoryAnswersDao, InMemoryCommentsDao, InMemoryQuestionsDao, InMemoryStore, InMemoryUsersDao},
+            questions_dao::QuestionsDao,
+            users_dao::UsersDao,
+        },
     };
 
-    #[sqlx::test]
-    async fn create_question_should_fail_if_database_error_occurs(
-        pool: PgPool,
-    ) -> Result<(), String> {
-        let doa = QuestionsDaoImpl::new(pool.clone());
+    struct Daos {
+        questions: InMemoryQuestionsDao,
+        answers: InMemoryAnswersDao,
+        comments: InMemoryCommentsDao,
+        users: InMemoryUsersDao,
+    }
 
-        pool.close().await;
+    fn daos() -> Daos {
+        let store = InMemoryStore::new();
 
-        let result = doa
-            .create_question(Question {
-                title: "test title".to_owned(),
-                description: "test description".to_owned(),
-            })
+        Daos {
+            questions: InMemoryQuestionsDao::new(store.clone()),
+            answers: InMemoryAnswersDao::new(store.clone()),
+            comments: InMemoryCommentsDao::new(store.clone()),
+            users: InMemoryUsersDao::new(store),
+        }
+    }
+
+    async fn create_test_user(daos: &Daos, username: &str) -> Result<String, String> {
+        let user = daos.users
+            .create_user(username.to_owned(), "test password hash".to_owned())
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+
+        Ok(user.user_uuid)
+    }
+
+    fn new_question(title: &str, tags: &[&str]) -> Question {
+        Question {
+            title: title.to_owned(),
+            description: format!("{} description", title),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn create_answer_should_fail_with_non_existent_question() -> Result<(), String> {
+        let daos = daos();
+        let author_uuid = create_test_user(&daos, "test user").await?;
+
+        let result = daos.answers
+            .create_answer(Answer {
+                question_uuid: "a22abcd2-22ab-2222-a22b-2abc2a2b22cc".to_owned(),
+                content: "test content".to_owned(),
+            }, author_uuid)
             .await;
 
-        if result.is_ok() {
-            return Err(format!(
-                "Expected an error but got the following result: {:?}",
-                result.unwrap()
-            ));
+        match result {
+            Err(DBError::InvalidUUID(uuid)) if uuid == "a22abcd2-22ab-2222-a22b-2abc2a2b22cc" => Ok(()),
+            result => Err(format!("Expected an invalid UUID error but got: {:?}", result)),
         }
+    }
 
-        if let Err(DBError::Other(_)) = result {
-            Ok(())
-        } else {
-            Err(format!(
-                "Expected an Other error but got the following error: {:?}",
-                result.err()
-            ))
+    #[tokio::test]
+    async fn create_answer_should_fail_with_malformed_uuid() -> Result<(), String> {
+        let daos = daos();
+        let author_uuid = create_test_user(&daos, "test user").await?;
+
+        let result = daos.answers
+            .create_answer(Answer { question_uuid: "malformed".to_owned(), content: "test content".to_owned() }, author_uuid)
+            .await;
+
+        match result {
+            Err(DBError::InvalidUUID(_)) => Ok(()),
+            result => Err(format!("Expected an invalid UUID error but got: {:?}", result)),
         }
     }
 
-    #[sqlx::test]
-    async fn create_question_should_succeed(pool: PgPool) -> Result<(), String> {
-        let doa = QuestionsDaoImpl::new(pool);
+    #[tokio::test]
+    async fn create_question_should_fail_with_unknown_author() -> Result<(), String> {
+        let daos = daos();
 
-        let result = doa
-            .create_question(Question {
-                title: "test title".to_owned(),
-                description: "test description".to_owned(),
-            })
+        let result = daos.questions.create_question(new_question("title", &[]), super::TEST_AUTHOR_UUID.to_owned()).await;
+
+        match result {
+            Err(DBError::Other(_)) => Ok(()),
+            result => Err(format!("Expected an error but got: {:?}", result)),
+        }
+    }
+
+    #[tokio::test]
+    async fn get_questions_should_paginate_newest_first_and_filter_by_tags() -> Result<(), String> {
+        let daos = daos();
+        let author_uuid = create_test_user(&daos, "test user").await?;
+
+        let mut created = vec![];
+        for i in 0..3 {
+            let tags: &[&str] = if i == 1 { &["rust"] } else { &["rust", "sql"] };
+            let question = daos.questions
+                .create_question(new_question(&format!("title {}", i), tags), author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+            created.push(question);
+        }
+
+        let first = daos.questions
+            .get_questions(PageRequest { limit: 2, cursor: None }, vec![], false)
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        let cursor = first.next_cursor.as_deref().and_then(Cursor::decode).ok_or("Expected a next cursor")?;
+        let second = daos.questions
+            .get_questions(PageRequest { limit: 2, cursor: Some(cursor) }, vec![], false)
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        let tagged = daos.questions
+            .get_questions(PageRequest::default(), vec!["rust".to_owned(), "sql".to_owned()], false)
             .await
             .map_err(|e| format!("{:?}", e))?;
 
-        if result.title != "test title".to_owned()
-            || result.description != "test description".to_owned()
-        {
-            return Err("Incorrect title or description".to_owned());
-        }
+        assert_eq!(first.items, vec![created[2].clone(), created[1].clone()]);
+        assert_eq!(second.items, vec![created[0].clone()]);
+        assert_eq!(second.next_cursor, None);
+        assert_eq!(tagged.items, vec![created[2].clone(), created[0].clone()]);
 
         Ok(())
     }
 
-    #[sqlx::test]
-    async fn delete_question_should_fail_with_malformed_uuid(pool: PgPool) -> Result<(), String> {
-        let doa = QuestionsDaoImpl::new(pool);
+    #[tokio::test]
+    async fn get_answers_should_pin_accepted_answer() -> Result<(), String> {
+        let daos = daos();
+        let author_uuid = create_test_user(&daos, "test user").await?;
 
-        let result = doa.delete_question("malformed".to_owned()).await;
+        let question = daos.questions
+            .create_question(new_question("title", &[]), author_uuid.clone())
+            .await
+            .map_err(|e| format!("{:?}", e))?;
 
-        if result.is_ok() {
-            return Err(format!(
-                "Expected an error but got the following result: {:?}",
-                result.unwrap()
-            ));
+        let mut answers = vec![];
+        for i in 0..3 {
+            let answer = daos.answers
+                .create_answer(Answer { question_uuid: question.question_uuid.clone(), content: format!("content {}", i) }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+            answers.push(answer.answer_uuid);
         }
 
-        if let Err(DBError::InvalidUUID(_)) = result {
-            Ok(())
-        } else {
-            Err(format!(
-                "Expected an invalid UUID error but got the following error: {:?}",
-                result.err()
-            ))
+        daos.questions
+            .set_accepted_answer(question.question_uuid.clone(), Some(answers[2].clone()))
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+
+        let first = daos.answers
+            .get_answers(question.question_uuid.clone(), PageRequest { limit: 2, cursor: None }, false)
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        let cursor = first.next_cursor.as_deref().and_then(Cursor::decode).ok_or("Expected a next cursor")?;
+        let second = daos.answers
+            .get_answers(question.question_uuid.clone(), PageRequest { limit: 2, cursor: Some(cursor) }, false)
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+
+        let uuids: Vec<String> = first.items.iter().chain(second.items.iter()).map(|answer| answer.answer_uuid.clone()).collect();
+        assert_eq!(uuids, vec![answers[2].clone(), answers[0].clone(), answers[1].clone()]);
+        assert!(first.items[0].is_accepted);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_question_should_embed_answers_with_accepted_first() -> Result<(), String> {
+        let daos = daos();
+        let author_uuid = create_test_user(&daos, "test user").await?;
+
+        let question = daos.questions
+            .create_question(new_question("title", &[]), author_uuid.clone())
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+
+        let mut answers = vec![];
+        for i in 0..3 {
+            let answer = daos.answers
+                .create_answer(Answer { question_uuid: question.question_uuid.clone(), content: format!("content {}", i) }, author_uuid.clone())
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+            answers.push(answer.answer_uuid);
         }
+
+        daos.questions
+            .set_accepted_answer(question.question_uuid.clone(), Some(answers[1].clone()))
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+
+        let result = daos.questions
+            .get_question(question.question_uuid.clone(), true, false)
+            .await
+            .map_err(|e| format!("{:?}", e))?
+            .ok_or("Expected the question to be found")?;
+
+        let uuids: Vec<String> = result.answers.unwrap_or_default().into_iter().map(|answer| answer.answer_uuid).collect();
+        assert_eq!(uuids, vec![answers[1].clone(), answers[0].clone(), answers[2].clone()]);
+        assert_eq!(result.question.accepted_answer_uuid, Some(answers[1].clone()));
+
+        Ok(())
     }
 
-    #[sqlx::test]
-    async fn delete_question_should_fail_if_database_error_occurs(
-        pool: PgPool,
-    ) -> Result<(), String> {
-        let doa = QuestionsDaoImpl::new(pool.clone());
+    #[tokio::test]
+    async fn votes_should_replace_previous_vote_and_be_retractable() -> Result<(), String> {
+        let daos = daos();
+        let author_uuid = create_test_user(&daos, "author").await?;
+        let voter_uuid = create_test_user(&daos, "voter").await?;
 
-        pool.close().await;
+        let question = daos.questions
+            .create_question(new_question("title", &[]), author_uuid.clone())
+            .await
+            .map_err(|e| format!("{:?}", e))?;
 
-        let result = doa
-            .delete_question("a22abcd2-22ab-2222-a22b-2abc2a2b22cc".to_owned())
-            .await;
+        let up = daos.questions.vote_question(question.question_uuid.clone(), author_uuid.clone(), 1).await.map_err(|e| format!("{:?}", e))?;
+        let down = daos.questions.vote_question(question.question_uuid.clone(), voter_uuid.clone(), 1).await.map_err(|e| format!("{:?}", e))?;
+        let replaced = daos.questions.vote_question(question.question_uuid.clone(), voter_uuid.clone(), -1).await.map_err(|e| format!("{:?}", e))?;
+        let retracted = daos.questions.retract_question_vote(question.question_uuid.clone(), voter_uuid).await.map_err(|e| format!("{:?}", e))?;
 
-        if result.is_ok() {
-            return Err(format!(
-                "Expected an error but got the following result: {:?}",
-                result.unwrap()
-            ));
-        }
+        assert_eq!((up, down, replaced, retracted), (1, 2, 0, 1));
 
-        if let Err(DBError::Other(_)) = result {
-            Ok(())
-        } else {
-            Err(format!(
-                "Expected an Other error but got the following error: {:?}",
-                result.err()
-            ))
+        let result = daos.answers.vote_answer("a22abcd2-22ab-2222-a22b-2abc2a2b22cc".to_owned(), author_uuid, 1).await;
+        match result {
+            Err(DBError::InvalidUUID(_)) => Ok(()),
+            result => Err(format!("Expected an invalid UUID error but got: {:?}", result)),
         }
     }
 
-    #[sqlx::test]
-    async fn delete_question_should_succeed(pool: PgPool) -> Result<(), String> {
-        let doa = QuestionsDaoImpl::new(pool);
+    #[tokio::test]
+    async fn deleted_questions_should_be_hidden_until_undeleted() -> Result<(), String> {
+        let daos = daos();
+        let author_uuid = create_test_user(&daos, "test user").await?;
 
-        let result = doa
-            .create_question(Question {
-                title: "test title".to_owned(),
-                description: "test description".to_owned(),
-            })
+        let question = daos.questions
+            .create_question(new_question("searchable title", &["rust"]), author_uuid.clone())
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        daos.answers
+            .create_answer(Answer { question_uuid: question.question_uuid.clone(), content: "content".to_owned() }, author_uuid.clone())
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+
+        daos.questions.delete_question(question.question_uuid.clone(), author_uuid).await.map_err(|e| format!("{:?}", e))?;
+
+        let listed = daos.questions.get_questions(PageRequest::default(), vec![], false).await.map_err(|e| format!("{:?}", e))?;
+        let with_deleted = daos.questions.get_questions(PageRequest::default(), vec![], true).await.map_err(|e| format!("{:?}", e))?;
+        let answers = daos.answers.get_answers(question.question_uuid.clone(), PageRequest::default(), false).await.map_err(|e| format!("{:?}", e))?;
+        let found = daos.questions.search("searchable".to_owned(), 10).await.map_err(|e| format!("{:?}", e))?;
+        let tags = daos.questions.get_tags().await.map_err(|e| format!("{:?}", e))?;
+
+        assert!(listed.items.is_empty());
+        assert!(with_deleted.items[0].deleted_at.is_some());
+        assert!(answers.items.is_empty());
+        assert!(found.is_empty());
+        assert!(tags.is_empty());
+
+        daos.questions.undelete_question(question.question_uuid.clone()).await.map_err(|e| format!("{:?}", e))?;
+
+        let listed = daos.questions.get_questions(PageRequest::default(), vec![], false).await.map_err(|e| format!("{:?}", e))?;
+        assert_eq!(listed.items, vec![question]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn deleted_posts_should_reject_votes_and_comments() -> Result<(), String> {
+        let daos = daos();
+        let author_uuid = create_test_user(&daos, "test user").await?;
+        let voter = create_test_user(&daos, "voter").await?;
+
+        let question = daos.questions
+            .create_question(new_question("test title", &[]), author_uuid.clone())
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        let answer = daos.answers
+            .create_answer(Answer { question_uuid: question.question_uuid.clone(), content: "content".to_owned() }, author_uuid.clone())
             .await
             .map_err(|e| format!("{:?}", e))?;
 
-        doa.delete_question(result.question_uuid)
+        daos.questions.delete_question(question.question_uuid.clone(), author_uuid.clone()).await.map_err(|e| format!("{:?}", e))?;
+
+        let answer_parent = CommentParent { question_uuid: None, answer_uuid: Some(answer.answer_uuid.clone()) };
+
+        assert!(matches!(daos.questions.get_question_author(question.question_uuid.clone(), false).await, Err(DBError::NotFound { .. })));
+        assert_eq!(daos.questions.get_question_author(question.question_uuid.clone(), true).await.ok(), Some(Some(author_uuid.clone())));
+        assert!(matches!(daos.questions.vote_question(question.question_uuid.clone(), voter.clone(), 1).await, Err(DBError::NotFound { .. })));
+        assert!(matches!(daos.answers.get_answer_author(answer.answer_uuid.clone(), false).await, Err(DBError::NotFound { .. })));
+        assert!(matches!(daos.answers.vote_answer(answer.answer_uuid.clone(), voter.clone(), 1).await, Err(DBError::NotFound { .. })));
+        assert!(matches!(daos.answers.retract_answer_vote(answer.answer_uuid.clone(), voter).await, Err(DBError::NotFound { .. })));
+        assert!(matches!(
+            daos.answers.answer_belongs_to_question(answer.answer_uuid.clone(), question.question_uuid.clone()).await,
+            Err(DBError::NotFound { .. })
+        ));
+        assert!(matches!(
+            daos.comments.create_comment(Comment { parent: answer_parent.clone(), content: "comment".to_owned() }, author_uuid).await,
+            Err(DBError::NotFound { .. })
+        ));
+        assert!(matches!(daos.comments.get_comments(answer_parent.clone(), PageRequest::default(), false).await, Err(DBError::NotFound { .. })));
+        assert!(daos.comments.get_comments(answer_parent, PageRequest::default(), true).await.is_ok());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn search_should_match_questions_and_answers() -> Result<(), String> {
+        let daos = daos();
+        let author_uuid = create_test_user(&daos, "test user").await?;
+
+        let direct = daos.questions
+            .create_question(new_question("Borrow checker errors", &[]), author_uuid.clone())
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        let through_answer = daos.questions
+            .create_question(new_question("Lifetimes", &[]), author_uuid.clone())
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        daos.answers
+            .create_answer(Answer { question_uuid: through_answer.question_uuid.clone(), content: "Ask the borrow checker".to_owned() }, author_uuid)
             .await
             .map_err(|e| format!("{:?}", e))?;
 
-        let results = doa.get_questions().await.map_err(|e| format!("{:?}", e))?;
+        let results = daos.questions.search("borrow".to_owned(), 10).await.map_err(|e| format!("{:?}", e))?;
 
-        if results.len() != 0 {
-            return Err("Question was not deleted".to_owned());
-        }
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].question, direct);
+        assert_eq!(results[0].snippet, "<mark>Borrow</mark> checker errors <mark>Borrow</mark> checker errors description");
+        assert_eq!(results[1].question.question_uuid, through_answer.question_uuid);
+        assert_eq!(results[1].answer_snippet.as_deref(), Some("Ask the <mark>borrow</mark> checker"));
 
         Ok(())
     }
 
-    #[sqlx::test]
-    async fn get_questions_should_fail_if_database_error_occurs(
-        pool: PgPool,
-    ) -> Result<(), String> {
-        let doa = QuestionsDaoImpl::new(pool.clone());
+    #[tokio::test]
+    async fn search_should_return_nothing_for_punctuation_only_query() -> Result<(), String> {
+        let daos = daos();
+        let author_uuid = create_test_user(&daos, "test user").await?;
 
-        pool.close().await;
+        daos.questions
+            .create_question(new_question("Borrow checker errors!!!", &[]), author_uuid)
+            .await
+            .map_err(|e| format!("{:?}", e))?;
 
-        let result = doa.get_questions().await;
+        let results = daos.questions.search("!!!".to_owned(), 10).await.map_err(|e| format!("{:?}", e))?;
 
-        if result.is_ok() {
-            return Err(format!(
-                "Expected an error but got the following result: {:?}",
-                result.unwrap()
-            ));
-        }
+        assert!(results.is_empty());
 
-        if let Err(DBError::Other(_)) = result {
-            Ok(())
-        } else {
-            Err(format!(
-                "Expected an Other error but got the following error: {:?}",
-                result.err()
-            ))
-        }
+        Ok(())
     }
 
-    #[sqlx::test]
-    async fn get_questions_should_succeed(pool: PgPool) -> Result<(), String> {
-        let doa = QuestionsDaoImpl::new(pool);
+    #[tokio::test]
+    async fn search_should_escape_html_in_snippets() -> Result<(), String> {
+        let daos = daos();
+        let author_uuid = create_test_user(&daos, "test user").await?;
 
-        let result = doa
-            .create_question(Question {
-                title: "test title".to_owned(),
-                description: "test description".to_owned(),
-            })
+        daos.questions
+            .create_question(new_question("<b>Borrow</b> checker", &[]), author_uuid)
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+
+        let results = daos.questions.search("borrow".to_owned(), 10).await.map_err(|e| format!("{:?}", e))?;
+
+        assert_eq!(
+            results[0].snippet,
+            "&lt;b&gt;<mark>Borrow</mark>&lt;/b&gt; checker &lt;b&gt;<mark>Borrow</mark>&lt;/b&gt; checker description"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_question_should_record_revision() -> Result<(), String> {
+        let daos = daos();
+        let author_uuid = create_test_user(&daos, "test user").await?;
+
+        let question = daos.questions
+            .create_question(new_question("old title", &["rust"]), author_uuid.clone())
             .await
             .map_err(|e| format!("{:?}", e))?;
 
-        let results = doa.get_questions().await.map_err(|e| format!("{:?}", e))?;
+        let updated = daos.questions
+            .update_question(new_question("new title", &["sql", "rust"]), question.question_uuid.clone(), author_uuid.clone(), Some("retitle".to_owned()))
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        let revisions = daos.questions.get_question_revisions(question.question_uuid.clone()).await.map_err(|e| format!("{:?}", e))?;
+        let revision = daos.questions
+            .get_question_revision(question.question_uuid.clone(), revisions[0].revision_uuid.clone())
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+
+        assert_eq!(updated.tags, vec!["rust".to_owned(), "sql".to_owned()]);
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].title, "old title");
+        assert_eq!(revisions[0].tags, vec!["rust".to_owned()]);
+        assert_eq!(revisions[0].editor_uuid, Some(author_uuid));
+        assert_eq!(revision, Some(revisions[0].clone()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn create_comment_should_fail_with_non_existent_parent() -> Result<(), String> {
+        let daos = daos();
+        let author_uuid = create_test_user(&daos, "test user").await?;
 
-        if results.len() != 1 {
-            return Err("Incorrect number of results returned.".to_owned());
+        let result = daos.comments
+            .create_comment(Comment {
+                parent: CommentParent { question_uuid: Some("a22abcd2-22ab-2222-a22b-2abc2a2b22cc".to_owned()), answer_uuid: None },
+                content: "test content".to_owned(),
+            }, author_uuid)
+            .await;
+
+        match result {
+            Err(DBError::InvalidUUID(_)) => Ok(()),
+            result => Err(format!("Expected an invalid UUID error but got: {:?}", result)),
         }
+    }
+
+    #[tokio::test]
+    async fn create_user_should_reject_duplicate_username() -> Result<(), String> {
+        let daos = daos();
+        create_test_user(&daos, "test user").await?;
 
-        if results.get(0).unwrap().question_uuid != result.question_uuid {
-            return Err("Incorrect question returned.".to_owned());
+        let result = daos.users.create_user("test user".to_owned(), "other hash".to_owned()).await;
+
+        match result {
+            Err(DBError::DuplicateUsername(username)) if username == "test user" => Ok(()),
+            result => Err(format!("Expected a duplicate username error but got: {:?}", result)),
         }
+    }
+
+    #[tokio::test]
+    async fn sessions_should_expire() -> Result<(), String> {
+        let daos = daos();
+        let user_uuid = create_test_user(&daos, "test user").await?;
+
+        daos.users.create_session(user_uuid.clone(), "live".to_owned(), 60).await.map_err(|e| format!("{:?}", e))?;
+        daos.users.create_session(user_uuid.clone(), "expired".to_owned(), 0).await.map_err(|e| format!("{:?}", e))?;
+
+        let live = daos.users.get_session_user("live".to_owned()).await.map_err(|e| format!("{:?}", e))?;
+        let expired = daos.users.get_session_user("expired".to_owned()).await.map_err(|e| format!("{:?}", e))?;
+
+        assert_eq!(live.map(|user| user.user_uuid), Some(user_uuid));
+        assert_eq!(expired, None);
+
+        daos.users.delete_session("live".to_owned()).await.map_err(|e| format!("{:?}", e))?;
+        let deleted = daos.users.get_session_user("live".to_owned()).await.map_err(|e| format!("{:?}", e))?;
+        assert_eq!(deleted, None);
 
         Ok(())
     }
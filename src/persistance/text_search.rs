@@ -0,0 +1,127 @@
+//! Plain word matching used by the storage backends without Postgres full text search.
+//! A term matches any word it is a prefix of, ignoring case and a trailing plural "s".
+//!
+//! Snippets are HTML in every backend, so the post text in them is escaped and only the `<mark>` tags are markup.
+
+use crate::models::{QuestionDetail, QuestionSearchResult};
+
+/// The word without a trailing plural "s".
+pub fn stem(word: &str) -> &str {
+    match word.strip_suffix('s') {
+        Some(stem) if stem.len() >= 3 => stem,
+        _ => word,
+    }
+}
+
+fn matches(word: &str, terms: &[String]) -> bool {
+    let word = word.to_lowercase();
+    terms.iter().any(|term| stem(&word).starts_with(stem(term)))
+}
+
+/// Lowercased words of the query, splitting on anything that isn't alphanumeric.
+pub fn terms(query: &str) -> Vec<String> {
+    query.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Share of the words of `text` that match, or `None` unless every term matches some word. Without any terms
+/// nothing matches.
+fn match_rank(text: &str, terms: &[String]) -> Option<f32> {
+    if terms.is_empty() {
+        return None;
+    }
+
+    let words: Vec<&str> = text.split(|c: char| !c.is_alphanumeric()).filter(|word| !word.is_empty()).collect();
+    let all_match = terms.iter()
+        .all(|term| words.iter().any(|word| matches(word, std::slice::from_ref(term))));
+
+    all_match.then(|| {
+        let matching = words.iter().filter(|word| matches(word, terms)).count();
+        matching as f32 / words.len() as f32
+    })
+}
+
+/// Delimits the matches in the snippets `ts_headline` returns. Private use characters can't be confused with
+/// markup, and are stripped from the text first so that posts can't inject them.
+pub const MARK_START: char = '\u{E000}';
+pub const MARK_END: char = '\u{E001}';
+
+fn push_escaped(html: &mut String, c: char) {
+    match c {
+        '&' => html.push_str("&amp;"),
+        '<' => html.push_str("&lt;"),
+        '>' => html.push_str("&gt;"),
+        '"' => html.push_str("&quot;"),
+        '\'' => html.push_str("&#39;"),
+        c => html.push(c),
+    }
+}
+
+/// Escapes a snippet delimited with `MARK_START`/`MARK_END` and turns the delimiters into `<mark>` tags.
+pub fn escape_snippet(snippet: &str) -> String {
+    let mut html = String::with_capacity(snippet.len());
+    for c in snippet.chars() {
+        match c {
+            MARK_START => html.push_str("<mark>"),
+            MARK_END => html.push_str("</mark>"),
+            c => push_escaped(&mut html, c),
+        }
+    }
+    html
+}
+
+/// Escapes `text` and wraps every word matching one of the terms in `<mark>` tags.
+fn highlight(text: &str, terms: &[String]) -> String {
+    let mut highlighted = String::with_capacity(text.len());
+    let mut word = String::new();
+
+    let flush = |word: &mut String, highlighted: &mut String| {
+        if !word.is_empty() && matches(word, terms) {
+            highlighted.push_str(&format!("<mark>{}</mark>", word));
+        } else {
+            highlighted.push_str(word);
+        }
+        word.clear();
+    };
+
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            word.push(c);
+        } else {
+            flush(&mut word, &mut highlighted);
+            push_escaped(&mut highlighted, c);
+        }
+    }
+    flush(&mut word, &mut highlighted);
+
+    highlighted
+}
+
+/// Ranks the question the way the Postgres search does: it matches on its own title/description or through
+/// any of its answers, and answer matches count for half as much as direct matches.
+/// Returns `None` if neither the question nor any answer contains every term.
+pub fn search_result<'a>(
+    question: QuestionDetail,
+    answers: impl IntoIterator<Item = &'a str>,
+    terms: &[String],
+) -> Option<QuestionSearchResult> {
+    let text = format!("{} {}", question.title, question.description);
+    let question_rank = match_rank(&text, terms);
+
+    let answer_match = answers.into_iter()
+        .filter_map(|content| match_rank(content, terms).map(|rank| (rank, content)))
+        .max_by(|(a, _), (b, _)| a.total_cmp(b));
+
+    if question_rank.is_none() && answer_match.is_none() {
+        return None;
+    }
+
+    Some(QuestionSearchResult {
+        rank: question_rank.unwrap_or(0.0) + answer_match.map_or(0.0, |(rank, _)| rank * 0.5),
+        snippet: highlight(&text, terms),
+        answer_snippet: answer_match.map(|(_, content)| highlight(content, terms)),
+        question,
+    })
+}
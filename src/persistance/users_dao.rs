@@ -0,0 +1,106 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::models::{postgres_error_codes, DBError, Role, UserCredentials, UserDetail};
+
+#[async_trait]
+pub trait UsersDao {
+    async fn create_user(&self, username: String, password_hash: String) -> Result<UserDetail, DBError>;
+    async fn get_user_credentials(&self, username: String) -> Result<Option<UserCredentials>, DBError>;
+    async fn create_session(&self, user_uuid: String, token_hash: String, ttl_seconds: i64) -> Result<String, DBError>;
+    async fn get_session_user(&self, token_hash: String) -> Result<Option<UserDetail>, DBError>;
+    async fn delete_session(&self, token_hash: String) -> Result<(), DBError>;
+}
+
+pub struct UsersDaoImpl {
+    db: PgPool,
+}
+
+impl UsersDaoImpl {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl UsersDao for UsersDaoImpl {
+    async fn create_user(&self, username: String, password_hash: String) -> Result<UserDetail, DBError> {
+        let record = sqlx::query!(
+            "INSERT INTO users ( username, password_hash )
+            VALUES ( $1, $2 )
+            RETURNING user_uuid, username, role, created_at",
+            username,
+            password_hash
+        ).fetch_one(&self.db).await.map_err(|e| {
+            if e.as_database_error().map(|e| e.code().expect("Error reading &dyn DatabaseError code").to_string()) == Some(postgres_error_codes::UNIQUE_VIOLATION.to_string()) {
+                DBError::DuplicateUsername(username.clone())
+            } else {
+                DBError::from_sqlx(e, "Error creating user")
+            }
+        })?;
+
+        Ok(UserDetail {
+            user_uuid: record.user_uuid.to_string(),
+            username: record.username,
+            role: parse_role(&record.role)?,
+            created_at: record.created_at.to_string(),
+        })
+    }
+
+    async fn get_user_credentials(&self, username: String) -> Result<Option<UserCredentials>, DBError> {
+        let record = sqlx::query!(
+            "SELECT user_uuid, password_hash FROM users WHERE username = $1",
+            username
+        ).fetch_optional(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting user"))?;
+
+        Ok(record.map(|record| UserCredentials {
+            user_uuid: record.user_uuid.to_string(),
+            password_hash: record.password_hash,
+        }))
+    }
+
+    async fn create_session(&self, user_uuid: String, token_hash: String, ttl_seconds: i64) -> Result<String, DBError> {
+        let uuid = sqlx::types::Uuid::parse_str(&user_uuid).map_err(|_| DBError::InvalidUUID(user_uuid.clone()))?;
+
+        let record = sqlx::query!(
+            "INSERT INTO sessions ( token_hash, user_uuid, expires_at )
+            VALUES ( $1, $2, CURRENT_TIMESTAMP + $3 * INTERVAL '1 second' )
+            RETURNING expires_at",
+            token_hash,
+            uuid,
+            ttl_seconds as f64
+        ).fetch_one(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error creating session"))?;
+
+        Ok(record.expires_at.to_string())
+    }
+
+    async fn get_session_user(&self, token_hash: String) -> Result<Option<UserDetail>, DBError> {
+        let record = sqlx::query!(
+            "SELECT users.user_uuid, users.username, users.role, users.created_at
+            FROM sessions
+            JOIN users ON users.user_uuid = sessions.user_uuid
+            WHERE sessions.token_hash = $1 AND sessions.expires_at > CURRENT_TIMESTAMP",
+            token_hash
+        ).fetch_optional(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error getting session"))?;
+
+        record.map(|record| {
+            Ok(UserDetail {
+                user_uuid: record.user_uuid.to_string(),
+                username: record.username,
+                role: parse_role(&record.role)?,
+                created_at: record.created_at.to_string(),
+            })
+        }).transpose()
+    }
+
+    async fn delete_session(&self, token_hash: String) -> Result<(), DBError> {
+        sqlx::query!("DELETE FROM sessions WHERE token_hash = $1", token_hash)
+            .execute(&self.db).await.map_err(|e| DBError::from_sqlx(e, "Error deleting session"))?;
+
+        Ok(())
+    }
+}
+
+fn parse_role(role: &str) -> Result<Role, DBError> {
+    role.parse().map_err(|e: String| DBError::Other(e.into()))
+}
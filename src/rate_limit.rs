@@ -0,0 +1,546 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::http::{Header, Status};
+use rocket::route::{Handler, Outcome};
+use rocket::{Build, Data, Request, Response, Rocket, Route};
+use serde::{Deserialize, Serialize};
+
+use crate::auth::AuthenticatedUser;
+
+/// Buckets are only pruned once there are this many, and only the full ones are dropped.
+const PRUNE_THRESHOLD: usize = 10_000;
+/// Pruning scans every bucket while holding the lock, so it runs at most this often.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A token bucket holding up to `burst` requests, refilled at `per_minute` requests a minute.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Limit {
+    pub burst: u32,
+    pub per_minute: u32,
+}
+
+impl Limit {
+    fn refill_per_second(&self) -> f64 {
+        f64::from(self.per_minute) / 60.0
+    }
+}
+
+/// Rate limit settings, read from the `rate_limit` table of the configuration, e.g.
+/// `ROCKET_RATE_LIMIT='{routes={create_question={burst=5, per_minute=5}}, deny_list=["10.0.0.1"]}'`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    pub enabled: bool,
+    /// Shared by every route without an entry in `routes`.
+    pub default: Limit,
+    /// Limits keyed by route function name, replacing the built-in ones when set. Deprecated routes share the
+    /// limit of their successor.
+    pub routes: HashMap<String, Limit>,
+    /// Clients that are always answered with a 403.
+    pub deny_list: Vec<IpAddr>,
+    /// Identifies clients by Rocket's `ip_header` (`X-Real-IP` by default) instead of the connection's address.
+    /// Only enable it behind a proxy that sets the header, anyone can send it otherwise.
+    pub trust_ip_header: bool,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        let strict = Limit { burst: 5, per_minute: 5 };
+
+        RateLimitConfig {
+            enabled: true,
+            default: Limit { burst: 60, per_minute: 60 },
+            routes: HashMap::from([
+                ("register".to_owned(), strict),
+                ("login".to_owned(), Limit { burst: 10, per_minute: 10 }),
+                ("create_question".to_owned(), strict),
+            ]),
+            deny_list: vec![],
+            trust_ip_header: false,
+        }
+    }
+}
+
+impl RateLimitConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        let limits = std::iter::once(("default", &self.default))
+            .chain(self.routes.iter().map(|(name, limit)| (name.as_str(), limit)));
+
+        for (name, limit) in limits {
+            if limit.burst == 0 {
+                return Err(format!("the burst of {} must be at least 1", name));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Outcome of taking a token, turned into `RateLimit-*` headers on the response.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Decision {
+    limit: u32,
+    remaining: u32,
+    /// Until the bucket is full again.
+    reset: Duration,
+    /// Set when the request was rejected.
+    retry_after: Option<Duration>,
+}
+
+impl Decision {
+    /// The decision to report when a request took tokens from two buckets.
+    fn tighter(self, other: Decision) -> Decision {
+        match (self.retry_after, other.retry_after) {
+            (Some(_), _) => self,
+            (None, Some(_)) => other,
+            (None, None) if other.remaining < self.remaining => other,
+            (None, None) => self,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl Bucket {
+    fn full(limit: &Limit, now: Instant) -> Self {
+        Bucket { tokens: f64::from(limit.burst), updated_at: now }
+    }
+
+    fn refill(&mut self, limit: &Limit, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * limit.refill_per_second()).min(f64::from(limit.burst));
+        self.updated_at = now;
+    }
+
+    fn take(&mut self, limit: &Limit, now: Instant) -> Decision {
+        self.refill(limit, now);
+
+        let retry_after = if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            Some(seconds_until(1.0 - self.tokens, limit))
+        };
+
+        Decision {
+            limit: limit.burst,
+            remaining: self.tokens.floor() as u32,
+            reset: seconds_until(f64::from(limit.burst) - self.tokens, limit),
+            retry_after,
+        }
+    }
+
+    fn is_full(&self, limit: &Limit, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens + elapsed * limit.refill_per_second() >= f64::from(limit.burst)
+    }
+}
+
+fn seconds_until(tokens: f64, limit: &Limit) -> Duration {
+    if tokens <= 0.0 {
+        return Duration::ZERO;
+    }
+    if limit.per_minute == 0 {
+        return Duration::MAX;
+    }
+    Duration::from_secs_f64(tokens / limit.refill_per_second())
+}
+
+/// One bucket per limit and client.
+struct Buckets {
+    by_client: HashMap<(String, String), Bucket>,
+    last_pruned: Instant,
+}
+
+/// Managed state holding the configuration and the buckets.
+pub struct RateLimits {
+    config: RateLimitConfig,
+    buckets: Mutex<Buckets>,
+}
+
+impl RateLimits {
+    pub fn new(config: RateLimitConfig) -> Self {
+        RateLimits {
+            config,
+            buckets: Mutex::new(Buckets { by_client: HashMap::new(), last_pruned: Instant::now() }),
+        }
+    }
+
+    /// Routes without their own limit share the `default` bucket.
+    fn limit_for(&self, route_name: Option<&str>) -> (&str, &Limit) {
+        route_name
+            .and_then(|name| self.config.routes.get_key_value(name))
+            .map(|(name, limit)| (name.as_str(), limit))
+            .unwrap_or(("default", &self.config.default))
+    }
+
+    fn take(&self, route_name: Option<&str>, client: String, now: Instant) -> Decision {
+        let (name, limit) = self.limit_for(route_name);
+        let mut buckets = self.buckets.lock().expect("Rate limit buckets lock poisoned");
+
+        if buckets.by_client.len() >= PRUNE_THRESHOLD && now.saturating_duration_since(buckets.last_pruned) >= PRUNE_INTERVAL {
+            buckets.by_client.retain(|(name, _), bucket| !bucket.is_full(self.limit_for(Some(name)).1, now));
+            buckets.last_pruned = now;
+        }
+
+        buckets
+            .by_client
+            .entry((name.to_owned(), client))
+            .or_insert_with(|| Bucket::full(limit, now))
+            .take(limit, now)
+    }
+}
+
+/// Route handler that takes a token before running the wrapped route.
+#[derive(Clone)]
+struct RateLimited {
+    route_name: Option<String>,
+    inner: Box<dyn Handler>,
+}
+
+#[rocket::async_trait]
+impl Handler for RateLimited {
+    async fn handle<'r>(&self, request: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        let Some(limits) = request.rocket().state::<RateLimits>().filter(|limits| limits.config.enabled) else {
+            return self.inner.handle(request, data).await;
+        };
+
+        let client_ip = if limits.config.trust_ip_header {
+            request.client_ip()
+        } else {
+            request.remote().map(|remote| remote.ip())
+        };
+        if client_ip.is_some_and(|ip| limits.config.deny_list.contains(&ip)) {
+            return Outcome::Error(Status::Forbidden);
+        }
+
+        // A forwarded request reaches several routes, only the first one takes a token.
+        let cached = request.local_cache(OnceLock::<Decision>::new);
+        let decision = match cached.get() {
+            Some(decision) => *decision,
+            None => {
+                let decision = self.take(limits, request, client_ip).await;
+                *cached.get_or_init(|| decision)
+            }
+        };
+
+        if decision.retry_after.is_some() {
+            return Outcome::Error(Status::TooManyRequests);
+        }
+
+        self.inner.handle(request, data).await
+    }
+}
+
+impl RateLimited {
+    /// Every request takes a token from its IP's bucket first. Resolving a session costs a query, so it only
+    /// happens once the request is admitted, after which authenticated users also take one from their own.
+    async fn take(&self, limits: &RateLimits, request: &Request<'_>, client_ip: Option<IpAddr>) -> Decision {
+        let route_name = self.route_name.as_deref();
+        let ip = format!("ip:{}", client_ip.map(|ip| ip.to_string()).unwrap_or_default());
+
+        let decision = limits.take(route_name, ip, Instant::now());
+        if decision.retry_after.is_some() {
+            return decision;
+        }
+
+        match request.guard::<Option<AuthenticatedUser>>().await.succeeded().flatten() {
+            Some(user) => decision.tighter(limits.take(route_name, format!("user:{}", user.user_uuid), Instant::now())),
+            None => decision,
+        }
+    }
+}
+
+/// Wraps the handlers of `routes` so that every request takes a token from its client's bucket.
+pub fn rate_limited(routes: Vec<Route>) -> Vec<Route> {
+    routes
+        .into_iter()
+        .map(|mut route| {
+            route.handler = Box::new(RateLimited {
+                route_name: route.name.as_ref().map(|name| name.to_string()),
+                inner: route.handler,
+            });
+            route
+        })
+        .collect()
+}
+
+fn ceil_seconds(duration: Duration) -> u64 {
+    duration.as_secs() + u64::from(duration.subsec_nanos() > 0)
+}
+
+/// Manages the `RateLimits` for a `RateLimitConfig` that `AppConfig` has already validated, and adds the
+/// `RateLimit-*` headers to responses.
+pub struct RateLimiter {
+    config: RateLimitConfig,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        RateLimiter { config }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for RateLimiter {
+    fn info(&self) -> Info {
+        Info {
+            name: "Rate limit requests",
+            kind: Kind::Ignite | Kind::Response,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        Ok(rocket.manage(RateLimits::new(self.config.clone())))
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let Some(decision) = request.local_cache(OnceLock::<Decision>::new).get() else {
+            return;
+        };
+
+        response.set_header(Header::new("RateLimit-Limit", decision.limit.to_string()));
+        response.set_header(Header::new("RateLimit-Remaining", decision.remaining.to_string()));
+        response.set_header(Header::new("RateLimit-Reset", ceil_seconds(decision.reset).to_string()));
+        if let Some(retry_after) = decision.retry_after {
+            response.set_header(Header::new("Retry-After", ceil_seconds(retry_after).to_string()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use async_trait::async_trait;
+    use rocket::{local::asynchronous::Client, Config};
+
+    use super::*;
+    use crate::{
+        auth::hash_session_token,
+        handlers::default_catcher,
+        models::{DBError, Role, UserCredentials, UserDetail},
+        persistance::users_dao::UsersDao,
+    };
+
+    #[test]
+    fn bucket_should_refill_over_time() {
+        let limit = Limit { burst: 2, per_minute: 60 };
+        let start = Instant::now();
+        let mut bucket = Bucket::full(&limit, start);
+
+        assert_eq!(bucket.take(&limit, start).remaining, 1);
+        assert_eq!(bucket.take(&limit, start).remaining, 0);
+
+        let rejected = bucket.take(&limit, start);
+        assert_eq!(rejected.retry_after, Some(Duration::from_secs(1)));
+        assert_eq!(rejected.reset, Duration::from_secs(2));
+
+        let refilled = bucket.take(&limit, start + Duration::from_secs(1));
+        assert_eq!(refilled.retry_after, None);
+        assert_eq!(refilled.remaining, 0);
+    }
+
+    #[test]
+    fn routes_without_limit_should_share_the_default_bucket() {
+        let limits = RateLimits::new(RateLimitConfig {
+            default: Limit { burst: 1, per_minute: 1 },
+            routes: HashMap::from([("login".to_owned(), Limit { burst: 1, per_minute: 1 })]),
+            ..RateLimitConfig::default()
+        });
+        let now = Instant::now();
+
+        assert_eq!(limits.take(Some("read_tags"), "ip:1".to_owned(), now).retry_after, None);
+        assert!(limits.take(Some("read_questions"), "ip:1".to_owned(), now).retry_after.is_some());
+        assert_eq!(limits.take(Some("login"), "ip:1".to_owned(), now).retry_after, None);
+        assert_eq!(limits.take(Some("read_tags"), "ip:2".to_owned(), now).retry_after, None);
+    }
+
+    #[test]
+    fn full_buckets_should_be_pruned_at_most_once_per_interval() {
+        let limits = RateLimits::new(RateLimitConfig::default());
+        let start = Instant::now();
+        let bucket_count = || limits.buckets.lock().unwrap().by_client.len();
+
+        for client in 0..PRUNE_THRESHOLD {
+            limits.take(None, format!("ip:{}", client), start);
+        }
+        assert_eq!(bucket_count(), PRUNE_THRESHOLD);
+
+        // Every bucket has refilled, but the last prune was too recent.
+        limits.take(None, "ip:new".to_owned(), start + Duration::from_secs(59));
+        assert_eq!(bucket_count(), PRUNE_THRESHOLD + 1);
+
+        limits.take(None, "ip:newer".to_owned(), start + PRUNE_INTERVAL + Duration::from_secs(1));
+        assert_eq!(bucket_count(), 1);
+    }
+
+    #[get("/tags")]
+    fn read_tags() -> &'static str {
+        "tags"
+    }
+
+    async fn client(config: RateLimitConfig) -> Client {
+        let rocket = rocket::custom(Config::debug_default())
+            .mount("/", rate_limited(routes![read_tags]))
+            .register("/", catchers![default_catcher])
+            .attach(RateLimiter::new(config));
+
+        Client::tracked(rocket).await.unwrap()
+    }
+
+    #[rocket::async_test]
+    async fn exhausted_bucket_should_respond_with_too_many_requests() {
+        let client = client(RateLimitConfig {
+            routes: HashMap::from([("read_tags".to_owned(), Limit { burst: 1, per_minute: 6 })]),
+            ..RateLimitConfig::default()
+        })
+        .await;
+
+        let response = client.get("/tags").dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.headers().get_one("RateLimit-Limit"), Some("1"));
+        assert_eq!(response.headers().get_one("RateLimit-Remaining"), Some("0"));
+        assert_eq!(response.headers().get_one("RateLimit-Reset"), Some("10"));
+
+        let response = client.get("/tags").dispatch().await;
+        assert_eq!(response.status(), Status::TooManyRequests);
+        assert_eq!(response.headers().get_one("Retry-After"), Some("10"));
+        assert!(response.into_string().await.unwrap().contains("too_many_requests"));
+    }
+
+    #[rocket::async_test]
+    async fn denied_ip_should_be_forbidden() {
+        let client = client(RateLimitConfig {
+            deny_list: vec!["10.0.0.1".parse().unwrap()],
+            ..RateLimitConfig::default()
+        })
+        .await;
+        let denied = "10.0.0.1:4000".parse().unwrap();
+
+        let response = client.get("/tags").remote(denied).dispatch().await;
+        assert_eq!(response.status(), Status::Forbidden);
+
+        // The header is ignored unless the proxy setting the header is trusted.
+        let response = client.get("/tags").remote(denied).header(Header::new("X-Real-IP", "10.0.0.2")).dispatch().await;
+        assert_eq!(response.status(), Status::Forbidden);
+
+        let response = client.get("/tags").remote("10.0.0.2:4000".parse().unwrap()).dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    #[rocket::async_test]
+    async fn trusted_ip_header_should_identify_clients() {
+        let client = client(RateLimitConfig {
+            deny_list: vec!["10.0.0.1".parse().unwrap()],
+            trust_ip_header: true,
+            ..RateLimitConfig::default()
+        })
+        .await;
+        let proxy = "10.0.0.9:4000".parse().unwrap();
+
+        let response = client.get("/tags").remote(proxy).header(Header::new("X-Real-IP", "10.0.0.1")).dispatch().await;
+        assert_eq!(response.status(), Status::Forbidden);
+
+        let response = client.get("/tags").remote(proxy).header(Header::new("X-Real-IP", "10.0.0.2")).dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    /// Counts session lookups; only the token `valid` resolves to a user.
+    struct CountingUsersDao {
+        session_lookups: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl UsersDao for CountingUsersDao {
+        async fn create_user(&self, _: String, _: String) -> Result<UserDetail, DBError> {
+            unimplemented!()
+        }
+
+        async fn get_user_credentials(&self, _: String) -> Result<Option<UserCredentials>, DBError> {
+            unimplemented!()
+        }
+
+        async fn create_session(&self, _: String, _: String, _: i64) -> Result<String, DBError> {
+            unimplemented!()
+        }
+
+        async fn get_session_user(&self, token_hash: String) -> Result<Option<UserDetail>, DBError> {
+            self.session_lookups.fetch_add(1, Ordering::SeqCst);
+
+            Ok((token_hash == hash_session_token("valid")).then(|| UserDetail {
+                user_uuid: "123".to_owned(),
+                username: "test user".to_owned(),
+                role: Role::User,
+                created_at: "now".to_owned(),
+            }))
+        }
+
+        async fn delete_session(&self, _: String) -> Result<(), DBError> {
+            unimplemented!()
+        }
+    }
+
+    async fn client_with_users(config: RateLimitConfig, session_lookups: Arc<AtomicUsize>) -> Client {
+        let users_dao: Box<dyn UsersDao + Send + Sync> = Box::new(CountingUsersDao { session_lookups });
+        let rocket = rocket::custom(Config::debug_default())
+            .mount("/", rate_limited(routes![read_tags]))
+            .register("/", catchers![default_catcher])
+            .manage(users_dao)
+            .attach(RateLimiter::new(config));
+
+        Client::tracked(rocket).await.unwrap()
+    }
+
+    fn bearer(token: &str) -> Header<'static> {
+        Header::new("Authorization", format!("Bearer {}", token))
+    }
+
+    #[rocket::async_test]
+    async fn bad_tokens_should_be_throttled_without_session_lookups() {
+        let session_lookups = Arc::new(AtomicUsize::new(0));
+        let client = client_with_users(RateLimitConfig {
+            routes: HashMap::from([("read_tags".to_owned(), Limit { burst: 2, per_minute: 1 })]),
+            ..RateLimitConfig::default()
+        }, session_lookups.clone())
+        .await;
+
+        let mut statuses = Vec::new();
+        for attempt in 0..5 {
+            let response = client.get("/tags").header(bearer(&format!("guess-{}", attempt))).dispatch().await;
+            statuses.push(response.status());
+        }
+
+        assert_eq!(statuses, [vec![Status::Ok; 2], vec![Status::TooManyRequests; 3]].concat());
+        assert_eq!(session_lookups.load(Ordering::SeqCst), 2);
+    }
+
+    #[rocket::async_test]
+    async fn authenticated_users_should_be_limited_across_ips() {
+        let client = client_with_users(RateLimitConfig {
+            routes: HashMap::from([("read_tags".to_owned(), Limit { burst: 1, per_minute: 1 })]),
+            ..RateLimitConfig::default()
+        }, Arc::new(AtomicUsize::new(0)))
+        .await;
+
+        let response = client.get("/tags").remote("10.0.0.1:4000".parse().unwrap()).header(bearer("valid")).dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = client.get("/tags").remote("10.0.0.2:4000".parse().unwrap()).header(bearer("valid")).dispatch().await;
+        assert_eq!(response.status(), Status::TooManyRequests);
+
+        let response = client.get("/tags").remote("10.0.0.3:4000".parse().unwrap()).dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+}